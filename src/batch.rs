@@ -0,0 +1,523 @@
+//! Running the items of the batch operations concurrently.
+//!
+//! The items run on the task of the operation, so the concurrency is that of
+//! the service calls and transfers waiting at the same time, which is what the
+//! large batches spend their time on. The decoding, drawing and encoding of
+//! their images is handed to the blocking threads with [`blocking`], so that it
+//! doesn't hold up the other items meanwhile.
+//!
+//! Every item that finishes is recorded in the checkpoint of the run directory,
+//! so that a run that stopped halfway can be resumed without processing the
+//! items that were already done.
+use colored::Colorize;
+use futures::stream::{self, StreamExt};
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs::{read_to_string, rename, write};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result, WithPath};
+
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+const CHECKPOINT_FILE: &str = "checkpoint.json";
+
+/// Runs the CPU-bound part of an item on the blocking threads.
+pub async fn blocking<R, F>(work: F) -> Result<R>
+where
+    F: FnOnce() -> Result<R> + Send + 'static,
+    R: Send + 'static,
+{
+    match tokio::task::spawn_blocking(work).await {
+        Ok(result) => result,
+        Err(error) if error.is_panic() => std::panic::resume_unwind(error.into_panic()),
+        Err(_) => Err(Error::Cancelled),
+    }
+}
+
+/// How many items of a batch are processed at the same time.
+#[derive(Clone, Copy)]
+pub struct Batch {
+    pub concurrency: usize,
+}
+
+impl Default for Batch {
+    fn default() -> Self {
+        Self {
+            concurrency: DEFAULT_CONCURRENCY,
+        }
+    }
+}
+
+/// A run that is continued in its own directory instead of a new one.
+#[derive(Clone)]
+pub struct Resume {
+    pub run_dir: PathBuf,
+    /// Only the items that failed are processed again, leaving out the ones
+    /// the run never got to.
+    pub failed_only: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum ItemState {
+    Done { result: Value },
+    Skipped,
+    Failed { error: String },
+}
+
+/// The items of a run that are done, with their results, and the ones that
+/// were skipped or failed, kept in 'checkpoint.json' of the run directory.
+#[derive(Default, Serialize, Deserialize)]
+pub struct Checkpoint {
+    #[serde(skip)]
+    path: PathBuf,
+    #[serde(skip)]
+    failed_only: bool,
+    items: BTreeMap<String, ItemState>,
+}
+
+impl Checkpoint {
+    /// Reads the checkpoint of the run directory, which is empty for a new run.
+    pub fn open(run_dir: &Path, failed_only: bool) -> Result<Self> {
+        let path = run_dir.join(CHECKPOINT_FILE);
+        let mut checkpoint = if path.exists() {
+            let json = read_to_string(&path).with_path(&path)?;
+            serde_json::from_str(&json).with_path(&path)?
+        } else {
+            Self::default()
+        };
+        checkpoint.path = path;
+        checkpoint.failed_only = failed_only;
+        Ok(checkpoint)
+    }
+
+    /// Writes the checkpoint next to itself first, so that stopping the run
+    /// while it's written never leaves half of it behind.
+    fn save(&self) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).with_path(&self.path)?;
+        let partial = self.path.with_extension("json.partial");
+        write(&partial, json).with_path(&partial)?;
+        rename(&partial, &self.path).with_path(&self.path)
+    }
+
+    fn record<R: Serialize>(&mut self, name: String, result: &Result<Option<R>>) -> Result<()> {
+        let state = match result {
+            Ok(Some(result)) => ItemState::Done {
+                result: serde_json::to_value(result).with_path(&self.path)?,
+            },
+            Ok(None) => ItemState::Skipped,
+            Err(error) => ItemState::Failed {
+                error: error.to_string(),
+            },
+        };
+        self.items.insert(name, state);
+        self.save()
+    }
+
+    /// How the item ended in an earlier run, or `None` when it has to be
+    /// processed again.
+    fn finished<R: DeserializeOwned>(&self, name: &str) -> Option<Option<R>> {
+        match self.items.get(name) {
+            Some(ItemState::Done { result }) => {
+                serde_json::from_value(result.clone()).ok().map(Some)
+            }
+            Some(ItemState::Skipped) => Some(None),
+            _ => None,
+        }
+    }
+
+    /// Whether the item is left out because the earlier run never got to it.
+    fn is_left_out(&self, name: &str) -> bool {
+        self.failed_only && !self.items.contains_key(name)
+    }
+}
+
+/// How the items of a batch ended.
+struct Outcome<R> {
+    total: usize,
+    /// The results of the items that succeeded, now or in an earlier run, in
+    /// the order the items were given.
+    results: Vec<R>,
+    /// The items that turned out to have nothing to process, such as a file
+    /// that isn't an image.
+    skipped: usize,
+    /// The names of the items that failed, in the order the items were given.
+    failed: Vec<(String, Error)>,
+    /// How many of the items were already finished in an earlier run.
+    resumed: usize,
+    /// How many of the items the earlier run never got to were left out.
+    left_out: usize,
+}
+
+impl<R> Outcome<R> {
+    fn headline(&self, what: &str) -> String {
+        let mut headline = format!("{} of {} {what} succeeded", self.results.len(), self.total);
+        if self.skipped > 0 {
+            headline.push_str(&format!(", {} skipped", self.skipped));
+        }
+        headline
+    }
+
+    fn print(&self, what: &str, run_dir: &Path) {
+        println!("{}\n", self.headline(what).green().bold());
+        if self.resumed > 0 {
+            println!(
+                "{}\n",
+                format!(
+                    "{} of them were already done in an earlier run",
+                    self.resumed
+                )
+                .yellow()
+                .bold()
+            );
+        }
+        if self.left_out > 0 {
+            println!(
+                "{}\n",
+                format!(
+                    "{} {what} that the earlier run never got to are left out",
+                    self.left_out
+                )
+                .yellow()
+                .bold()
+            );
+        }
+        if !self.failed.is_empty() {
+            println!(
+                "{}",
+                format!("{} {what} failed:", self.failed.len()).red().bold()
+            );
+            for (name, error) in &self.failed {
+                println!("  {}: {}", name.red().bold(), error.to_string().red());
+            }
+            println!(
+                "\n{} '{}'\n",
+                "They are retried by resuming the run in".yellow().bold(),
+                run_dir.display().to_string().yellow().bold()
+            );
+        }
+    }
+}
+
+impl Batch {
+    /// Processes every item with `work`, showing how many are done, failed
+    /// and remaining, and prints a summary at the end.
+    ///
+    /// An item that fails doesn't stop the others; it is listed in the summary
+    /// under its `name`, which is also what the item is known by in the
+    /// checkpoint. The results of the items that succeeded, now or in an
+    /// earlier run, are returned in the order the items were given.
+    pub async fn run<T, R, N, F, Fut>(
+        &self,
+        what: &str,
+        checkpoint: &mut Checkpoint,
+        items: Vec<T>,
+        name: N,
+        work: F,
+    ) -> Result<Vec<R>>
+    where
+        N: Fn(&T) -> String,
+        F: Fn(T) -> Fut,
+        Fut: Future<Output = Result<R>>,
+        R: Serialize + DeserializeOwned,
+    {
+        self.run_or_skip(what, checkpoint, items, name, |item| {
+            let processed = work(item);
+            async move { processed.await.map(Some) }
+        })
+        .await
+    }
+
+    /// Like [`Batch::run`], for the items whose `work` can find nothing to
+    /// process and return `None`, which are counted as skipped rather than as
+    /// succeeded.
+    pub async fn run_or_skip<T, R, N, F, Fut>(
+        &self,
+        what: &str,
+        checkpoint: &mut Checkpoint,
+        items: Vec<T>,
+        name: N,
+        work: F,
+    ) -> Result<Vec<R>>
+    where
+        N: Fn(&T) -> String,
+        F: Fn(T) -> Fut,
+        Fut: Future<Output = Result<Option<R>>>,
+        R: Serialize + DeserializeOwned,
+    {
+        let outcome = self.process(what, checkpoint, items, name, work).await?;
+        let run_dir = checkpoint.path.parent().unwrap_or(Path::new("."));
+        outcome.print(what, run_dir);
+        Ok(outcome.results)
+    }
+
+    async fn process<T, R, N, F, Fut>(
+        &self,
+        what: &str,
+        checkpoint: &mut Checkpoint,
+        items: Vec<T>,
+        name: N,
+        work: F,
+    ) -> Result<Outcome<R>>
+    where
+        N: Fn(&T) -> String,
+        F: Fn(T) -> Fut,
+        Fut: Future<Output = Result<Option<R>>>,
+        R: Serialize + DeserializeOwned,
+    {
+        let total = items.len();
+        let mut succeeded = Vec::new();
+        let mut skipped = 0;
+        let mut left_out = 0;
+        let mut pending = Vec::new();
+        for (index, item) in items.into_iter().enumerate() {
+            let item_name = name(&item);
+            match checkpoint.finished(&item_name) {
+                Some(Some(result)) => succeeded.push((index, result)),
+                Some(None) => skipped += 1,
+                None if checkpoint.is_left_out(&item_name) => left_out += 1,
+                None => pending.push((index, item_name, item)),
+            }
+        }
+        let resumed = succeeded.len() + skipped;
+        let remaining = pending.len();
+
+        let bar = ProgressBar::new(remaining as u64);
+        if let Ok(style) = ProgressStyle::with_template(
+            "{bar:30.green/white} {pos}/{len} {prefix} [{elapsed}] {msg}",
+        ) {
+            bar.set_style(style);
+        }
+        bar.set_prefix(what.to_string());
+        let mut finished = stream::iter(pending)
+            .map(|(index, name, item)| {
+                let processed = work(item);
+                async move { (index, name, processed.await) }
+            })
+            .buffer_unordered(self.concurrency.max(1));
+
+        let mut failed = Vec::new();
+        while let Some((index, name, result)) = finished.next().await {
+            checkpoint.record(name.clone(), &result)?;
+            match result {
+                Ok(Some(result)) => succeeded.push((index, result)),
+                Ok(None) => skipped += 1,
+                Err(error) => failed.push((index, name, error)),
+            }
+            bar.inc(1);
+            let done = succeeded.len() + skipped - resumed;
+            bar.set_message(format!(
+                "{done} done, {} failed, {} remaining",
+                failed.len(),
+                remaining - done - failed.len()
+            ));
+        }
+        bar.finish_and_clear();
+        succeeded.sort_by_key(|(index, _)| *index);
+        failed.sort_by_key(|(index, _, _)| *index);
+        Ok(Outcome {
+            total,
+            results: succeeded.into_iter().map(|(_, result)| result).collect(),
+            skipped,
+            failed: failed
+                .into_iter()
+                .map(|(_, name, error)| (name, error))
+                .collect(),
+            resumed,
+            left_out,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    /// Sleeps for longer the earlier the item is, so that the items finish in
+    /// the reverse of the order they were given in.
+    async fn reversed(item: u64, count: u64) {
+        tokio::time::sleep(Duration::from_millis((count - item) * 10)).await;
+    }
+
+    /// Succeeds for the items divisible by 3, skips the ones that leave 1 and
+    /// fails the others.
+    async fn mixed(item: u64) -> Result<Option<u64>> {
+        reversed(item, 6).await;
+        match item % 3 {
+            0 => Ok(Some(item)),
+            1 => Ok(None),
+            _ => Err(Error::Service(format!("no access to {item}"))),
+        }
+    }
+
+    fn name(item: &u64) -> String {
+        format!("file {item}")
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn the_results_keep_the_order_of_the_items() {
+        let run_dir = TempDir::new().unwrap();
+        let mut checkpoint = Checkpoint::open(run_dir.path(), false).unwrap();
+        let batch = Batch { concurrency: 8 };
+
+        let outcome = batch
+            .process(
+                "item(s)",
+                &mut checkpoint,
+                (0..5).collect(),
+                name,
+                |item| async move {
+                    reversed(item, 5).await;
+                    Ok(Some(item * 10))
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(outcome.results, [0, 10, 20, 30, 40]);
+        assert_eq!(outcome.headline("item(s)"), "5 of 5 item(s) succeeded");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn no_more_items_than_the_concurrency_run_at_once() {
+        let run_dir = TempDir::new().unwrap();
+        let mut checkpoint = Checkpoint::open(run_dir.path(), false).unwrap();
+        let (running, most) = (Cell::new(0), Cell::new(0));
+        let batch = Batch { concurrency: 3 };
+
+        let outcome = batch
+            .process(
+                "item(s)",
+                &mut checkpoint,
+                (0..10).collect(),
+                name,
+                |item| {
+                    let (running, most) = (&running, &most);
+                    async move {
+                        running.set(running.get() + 1);
+                        most.set(most.get().max(running.get()));
+                        reversed(item, 10).await;
+                        running.set(running.get() - 1);
+                        Ok(Some(item))
+                    }
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(most.get(), 3);
+        assert_eq!(outcome.results.len(), 10);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn failed_and_skipped_items_are_counted_apart() {
+        let run_dir = TempDir::new().unwrap();
+        let mut checkpoint = Checkpoint::open(run_dir.path(), false).unwrap();
+        let batch = Batch::default();
+
+        let outcome = batch
+            .process("file(s)", &mut checkpoint, (0..6).collect(), name, mixed)
+            .await
+            .unwrap();
+
+        assert_eq!(outcome.results, [0, 3]);
+        assert_eq!(outcome.skipped, 2);
+        let failed: Vec<_> = outcome
+            .failed
+            .iter()
+            .map(|(name, error)| format!("{name}: {error}"))
+            .collect();
+        assert_eq!(
+            failed,
+            [
+                "file 2: The service call failed: no access to 2",
+                "file 5: The service call failed: no access to 5"
+            ]
+        );
+        assert_eq!(
+            outcome.headline("file(s)"),
+            "2 of 6 file(s) succeeded, 2 skipped"
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn the_checkpoint_is_read_back_as_it_was_saved() {
+        let run_dir = TempDir::new().unwrap();
+        let mut checkpoint = Checkpoint::open(run_dir.path(), false).unwrap();
+        Batch::default()
+            .process("file(s)", &mut checkpoint, (0..3).collect(), name, mixed)
+            .await
+            .unwrap();
+
+        let checkpoint = Checkpoint::open(run_dir.path(), false).unwrap();
+
+        assert_eq!(checkpoint.finished::<u64>("file 0"), Some(Some(0)));
+        assert_eq!(checkpoint.finished::<u64>("file 1"), Some(None));
+        assert_eq!(checkpoint.finished::<u64>("file 2"), None);
+        assert!(matches!(
+            checkpoint.items.get("file 2"),
+            Some(ItemState::Failed { error }) if error == "The service call failed: no access to 2"
+        ));
+        assert!(!run_dir.path().join("checkpoint.json.partial").exists());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn the_finished_items_are_not_processed_again() {
+        let run_dir = TempDir::new().unwrap();
+        let mut checkpoint = Checkpoint::open(run_dir.path(), false).unwrap();
+        Batch::default()
+            .process("file(s)", &mut checkpoint, (0..6).collect(), name, mixed)
+            .await
+            .unwrap();
+
+        let processed = RefCell::new(Vec::new());
+        let mut checkpoint = Checkpoint::open(run_dir.path(), false).unwrap();
+        let outcome = Batch::default()
+            .process("file(s)", &mut checkpoint, (0..8).collect(), name, |item| {
+                processed.borrow_mut().push(item);
+                async move { Ok(Some(item)) }
+            })
+            .await
+            .unwrap();
+
+        processed.borrow_mut().sort();
+        assert_eq!(*processed.borrow(), [2, 5, 6, 7]);
+        assert_eq!(outcome.results, [0, 2, 3, 5, 6, 7]);
+        assert_eq!(outcome.skipped, 2);
+        assert_eq!(outcome.resumed, 4);
+        assert!(outcome.failed.is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn only_the_failed_items_are_retried_when_asked_to() {
+        let run_dir = TempDir::new().unwrap();
+        let mut checkpoint = Checkpoint::open(run_dir.path(), false).unwrap();
+        Batch::default()
+            .process("file(s)", &mut checkpoint, (0..6).collect(), name, mixed)
+            .await
+            .unwrap();
+
+        let processed = RefCell::new(Vec::new());
+        let mut checkpoint = Checkpoint::open(run_dir.path(), true).unwrap();
+        let outcome = Batch::default()
+            .process("file(s)", &mut checkpoint, (0..8).collect(), name, |item| {
+                processed.borrow_mut().push(item);
+                async move { Ok(Some(item)) }
+            })
+            .await
+            .unwrap();
+
+        processed.borrow_mut().sort();
+        assert_eq!(*processed.borrow(), [2, 5]);
+        assert_eq!(outcome.results, [0, 2, 3, 5]);
+        assert_eq!(outcome.left_out, 2);
+    }
+}
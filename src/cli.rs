@@ -0,0 +1,1078 @@
+use aws_apis::CredentInitialize;
+use clap::{Args, Parser, Subcommand};
+use glob::Pattern;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::batch::{Batch, Resume, DEFAULT_CONCURRENCY};
+use crate::commands::{
+    jobs, polly, rekognition, rekognition::CelebrityVisuals, rekognition::Redaction,
+    rekognition::Source, run_with, transcribe, translate, Clients, Settings,
+};
+use crate::config::Profile;
+use crate::error::{Error, Result};
+use crate::output::{ResultFormat, ResultOutput};
+use crate::preprocess::{ConvertTo, Preprocess, REKOGNITION_MAX_BYTES};
+use crate::speech_marks::SPEECH_MARK_TYPES;
+use crate::wait::Wait;
+use crate::walk::{parse_extensions, parse_pattern, Walk};
+use crate::{load_credentials_from_env_vars, load_profile_credentials};
+
+/// Non-interactive interface to the operations available in the menus.
+///
+/// Running the binary without a subcommand opens the interactive menu instead.
+#[derive(Parser)]
+#[command(name = "dlearningclient", version, about)]
+pub struct Cli {
+    /// Load the credentials from the AWS_ACCESS_KEY_ID, AWS_SECRET_ACCESS_KEY and
+    /// AWS_DEFAULT_REGION environment variables (or a .env file) instead of the AWS configuration file
+    #[arg(long, global = true)]
+    pub env_vars: bool,
+    /// Answer every service call from the fixture files in this directory instead of AWS
+    #[arg(long, global = true, value_name = "DIR")]
+    pub fixtures: Option<String>,
+    /// Profile of the configuration file to use instead of its default profile
+    #[arg(long, global = true, value_name = "NAME")]
+    pub profile: Option<String>,
+    /// Wait for started and fetched jobs to complete, polling their status, and process their results
+    #[arg(long, global = true)]
+    pub wait: bool,
+    /// Seconds to keep waiting for a job before giving up
+    #[arg(
+        long,
+        global = true,
+        value_name = "SECS",
+        default_value_t = 600,
+        requires = "wait"
+    )]
+    pub wait_timeout: u64,
+    /// Also write the results of the analyses in this structured format
+    #[arg(long, global = true, value_enum)]
+    pub format: Option<ResultFormat>,
+    /// File the structured results are written to, '-' for the standard output
+    #[arg(long, global = true, value_name = "PATH", requires = "format")]
+    pub output: Option<PathBuf>,
+    /// Directory under which every run writes its files, in a timestamped directory of its own
+    #[arg(long, global = true, value_name = "DIR")]
+    pub output_dir: Option<PathBuf>,
+    /// Also walk the subdirectories of the local directories, keeping their
+    /// relative paths in the keys of the uploaded objects
+    #[arg(long, global = true)]
+    pub recursive: bool,
+    /// Only pick the files of the local directories whose relative path matches this glob, such as 'faces/*.jpg' or '**/*.png'
+    #[arg(long, global = true, value_name = "GLOB", value_parser = glob_pattern)]
+    pub include: Vec<Pattern>,
+    /// Skip the files and subdirectories of the local directories whose relative path matches this glob
+    #[arg(long, global = true, value_name = "GLOB", value_parser = glob_pattern)]
+    pub exclude: Vec<Pattern>,
+    /// Only pick the files of the local directories with these extensions, such as 'jpg,png'
+    #[arg(
+        long = "extension",
+        global = true,
+        value_name = "EXT",
+        value_delimiter = ','
+    )]
+    pub extensions: Vec<String>,
+    /// How many images the batch operations process at the same time
+    #[arg(
+        long,
+        global = true,
+        value_name = "N",
+        default_value_t = DEFAULT_CONCURRENCY as u16,
+        value_parser = clap::value_parser!(u16).range(1..=64)
+    )]
+    pub concurrency: u16,
+    /// Continue the batch run that wrote to this directory, skipping the images it already processed
+    #[arg(long, global = true, value_name = "RUN_DIR")]
+    pub resume: Option<PathBuf>,
+    /// Only process the images the resumed run failed on again
+    #[arg(long, global = true, requires = "resume")]
+    pub failed_only: bool,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Amazon Polly operations
+    #[command(subcommand)]
+    Polly(PollyCommand),
+    /// Amazon Rekognition operations
+    #[command(subcommand)]
+    Rekognition(RekognitionCommand),
+    /// Amazon Translate operations
+    #[command(subcommand)]
+    Translate(TranslateCommand),
+    /// Amazon Transcribe operations
+    #[command(subcommand)]
+    Transcribe(TranscribeCommand),
+    /// List the asynchronous jobs started from this machine, the most recent first
+    Jobs,
+}
+
+#[derive(Subcommand)]
+pub enum PollyCommand {
+    /// Start the speech synthesis task
+    Synthesize {
+        /// Speech generation engine: 'standard' or 'neural' [default: polly_engine of the profile]
+        #[arg(long)]
+        engine: Option<String>,
+        /// Voice ID used for the audio generation [default: polly_voice of the profile]
+        #[arg(long)]
+        voice: Option<String>,
+        /// Language code of the audio, the language of the voice when it's not given
+        #[arg(long)]
+        language: Option<String>,
+        /// Text format of the content: 'ssml' or 'text'
+        #[arg(long, default_value = "ssml", value_parser = ["ssml", "text"])]
+        text_type: String,
+        /// Path of the text file to synthesize
+        #[arg(long)]
+        text_file: String,
+        /// Output format: 'json', 'mp3', 'ogg_vorbis' or 'pcm'
+        #[arg(long, default_value = "mp3", value_parser = ["json", "mp3", "ogg_vorbis", "pcm"])]
+        audio_format: String,
+        /// Amazon S3 bucket to which the output file will be saved [default: bucket of the profile]
+        #[arg(long)]
+        bucket: Option<String>,
+        /// Pronunciation lexicons to apply, separated by commas, the earlier ones taking precedence
+        #[arg(long = "lexicon", value_name = "NAME", value_delimiter = ',')]
+        lexicons: Vec<String>,
+        /// Speech mark types to write, separated by commas: instead of the audio with the
+        /// 'json' format, and by a second task next to it otherwise
+        #[arg(long, value_name = "TYPES", value_delimiter = ',', value_parser = SPEECH_MARK_TYPES)]
+        speech_marks: Vec<String>,
+    },
+    /// Synthesize a short text right away and write the audio to a local file
+    SynthesizeToFile {
+        /// Speech generation engine: 'standard' or 'neural' [default: polly_engine of the profile]
+        #[arg(long)]
+        engine: Option<String>,
+        /// Voice ID used for the audio generation [default: polly_voice of the profile]
+        #[arg(long)]
+        voice: Option<String>,
+        /// Language code of the audio, the language of the voice when it's not given
+        #[arg(long)]
+        language: Option<String>,
+        /// Text format of the content: 'ssml' or 'text', which is split into parts when it's too long
+        #[arg(long, default_value = "text", value_parser = ["ssml", "text"])]
+        text_type: String,
+        /// Path of the text file to synthesize
+        #[arg(long)]
+        text_file: String,
+        /// Audio format: 'mp3', 'ogg_vorbis' or 'pcm'
+        #[arg(long, default_value = "mp3", value_parser = ["mp3", "ogg_vorbis", "pcm"])]
+        audio_format: String,
+        /// File the audio is written to, instead of the run directory
+        #[arg(long, value_name = "PATH")]
+        file: Option<PathBuf>,
+        /// Pronunciation lexicons to apply, separated by commas, the earlier ones taking precedence
+        #[arg(long = "lexicon", value_name = "NAME", value_delimiter = ',')]
+        lexicons: Vec<String>,
+        /// Speech mark types to write next to the audio, separated by commas, along with
+        /// WebVTT and SRT subtitles of the sentence and word marks
+        #[arg(long, value_name = "TYPES", value_delimiter = ',', value_parser = SPEECH_MARK_TYPES)]
+        speech_marks: Vec<String>,
+    },
+    /// Write WebVTT and SRT subtitles of a speech marks file
+    Subtitles {
+        /// Path of the speech marks file, in JSON Lines
+        #[arg(long)]
+        marks_file: String,
+        /// Audio synthesized with the marks, which the last subtitle ends with
+        #[arg(long)]
+        audio_file: Option<String>,
+    },
+    /// Get the speech synthesis results
+    GetSynthesis {
+        /// Task ID returned when the synthesis task was started
+        #[arg(long)]
+        task_id: String,
+    },
+    /// List all speech synthesis tasks
+    ListTasks,
+    /// Generate all voices audio in MP3
+    GenerateAllVoices {
+        /// Speech generation engine: 'standard' or 'neural' [default: polly_engine of the profile]
+        #[arg(long)]
+        engine: Option<String>,
+        /// Language code of the audio
+        #[arg(long)]
+        language: String,
+        /// Text format of the content: 'ssml' or 'text'
+        #[arg(long, default_value = "ssml", value_parser = ["ssml", "text"])]
+        text_type: String,
+        /// Path to the text file
+        #[arg(long)]
+        text_file: String,
+        /// Directory prefix created in the output directory for the generated audio
+        #[arg(long)]
+        prefix: String,
+        /// Pronunciation lexicons to apply, separated by commas, the earlier ones taking precedence
+        #[arg(long = "lexicon", value_name = "NAME", value_delimiter = ',')]
+        lexicons: Vec<String>,
+    },
+    /// Synthesize a sample per voice and write an HTML page to compare them on
+    SampleVoices {
+        /// Speech generation engine whose voices are sampled
+        #[arg(long)]
+        engine: String,
+        /// Language code of the voices, instead of every language
+        #[arg(long)]
+        language: Option<String>,
+        /// Text format of the content: 'ssml' or 'text'
+        #[arg(long, default_value = "text", value_parser = ["ssml", "text"])]
+        text_type: String,
+        /// Path of the sample text file
+        #[arg(long)]
+        text_file: String,
+    },
+    /// Check a local PLS lexicon file and store it in Amazon Polly
+    PutLexicon {
+        /// Name to store the lexicon under: 1 to 20 letters and digits
+        #[arg(long)]
+        name: String,
+        /// Path to the PLS lexicon file
+        #[arg(long)]
+        lexicon_file: String,
+    },
+    /// List the pronunciation lexicons stored in Amazon Polly
+    Lexicons,
+    /// Download a stored pronunciation lexicon
+    GetLexicon {
+        /// Name of the lexicon
+        #[arg(long)]
+        name: String,
+        /// File the lexicon is written to, instead of the run directory
+        #[arg(long, value_name = "PATH")]
+        file: Option<PathBuf>,
+    },
+    /// Delete a stored pronunciation lexicon
+    DeleteLexicon {
+        /// Name of the lexicon
+        #[arg(long)]
+        name: String,
+    },
+    /// Check an SSML file for an engine without synthesizing it
+    ValidateSsml {
+        /// Speech generation engine: 'standard' or 'neural' [default: polly_engine of the profile]
+        #[arg(long)]
+        engine: Option<String>,
+        /// Path to the SSML text file
+        #[arg(long)]
+        text_file: String,
+    },
+    /// Obtain voice information from Amazon Polly
+    Voices,
+}
+
+#[derive(Subcommand)]
+pub enum RekognitionCommand {
+    /// Recognize a celebrity from a local image or an S3 object
+    RecognizeCelebrity {
+        /// Local path of the celebrity image
+        #[arg(long, conflicts_with_all = ["bucket", "key"], required_unless_present = "key")]
+        image: Option<String>,
+        /// Bucket where the celebrity image is stored [default: bucket of the profile]
+        #[arg(long, requires = "key")]
+        bucket: Option<String>,
+        /// Key of the celebrity image within the bucket
+        #[arg(long)]
+        key: Option<String>,
+        /// Also write copies of the images with a box and a name on every face
+        #[arg(long)]
+        annotate: bool,
+        /// Also save every face as an image of its own, listed in an index.json
+        #[arg(long)]
+        crop_faces: bool,
+    },
+    /// Upload images to an S3 bucket
+    UploadImages {
+        /// Bucket the images are uploaded to [default: bucket of the profile]
+        #[arg(long)]
+        bucket: Option<String>,
+        /// Local directory where the images are stored [default: local_prefix of the profile]
+        #[arg(long)]
+        dir: Option<String>,
+        /// Bucket path prefix under which the images will be saved [default: bucket_prefix of the profile]
+        #[arg(long)]
+        prefix: Option<String>,
+        #[command(flatten)]
+        preprocess: PreprocessArgs,
+    },
+    /// Recognize multiple celebrities from a local directory or an S3 prefix
+    RecognizeCelebrities {
+        /// Local directory where the celebrity images are stored
+        #[arg(long, conflicts_with_all = ["bucket", "prefix"], required_unless_present = "prefix")]
+        dir: Option<String>,
+        /// Bucket where the images are stored [default: bucket of the profile]
+        #[arg(long, requires = "prefix")]
+        bucket: Option<String>,
+        /// Bucket path prefix under which the celebrity images are stored
+        #[arg(long)]
+        prefix: Option<String>,
+        /// Also write copies of the images with a box and a name on every face
+        #[arg(long)]
+        annotate: bool,
+        /// Also save every face as an image of its own, listed in an index.json
+        #[arg(long)]
+        crop_faces: bool,
+    },
+    /// Face detection for a local image or directory, a single key or every key under a prefix
+    DetectFaces {
+        /// Local image, or directory of images, sent without uploading it to S3
+        #[arg(long, conflicts_with_all = ["bucket", "key", "prefix"], required_unless_present_any = ["key", "prefix"])]
+        image: Option<String>,
+        /// Bucket where the images are stored [default: bucket of the profile]
+        #[arg(long)]
+        bucket: Option<String>,
+        #[arg(long, conflicts_with = "prefix")]
+        key: Option<String>,
+        #[arg(long)]
+        prefix: Option<String>,
+    },
+    /// Text detection for a local image or directory, or a single key
+    DetectText {
+        /// Local image, or directory of images, sent without uploading it to S3
+        #[arg(long, conflicts_with_all = ["bucket", "key"], required_unless_present = "key")]
+        image: Option<String>,
+        /// Bucket where the image is stored [default: bucket of the profile]
+        #[arg(long, requires = "key")]
+        bucket: Option<String>,
+        #[arg(long)]
+        key: Option<String>,
+        /// Also write copies of the images with the detected lines and words drawn on them
+        #[arg(long)]
+        overlay: bool,
+    },
+    /// Upload modified images to an S3 bucket
+    UploadModifiedImages {
+        /// Bucket the images are uploaded to [default: bucket of the profile]
+        #[arg(long)]
+        bucket: Option<String>,
+        /// Local directory where the images are stored [default: local_prefix of the profile]
+        #[arg(long)]
+        dir: Option<String>,
+        /// Bucket path prefix under which the images will be saved [default: bucket_prefix of the profile]
+        #[arg(long)]
+        prefix: Option<String>,
+        #[command(flatten)]
+        preprocess: PreprocessArgs,
+    },
+    /// Write images with facial details obtained from Rekognition's 'DetectFaces' feature
+    AnnotateFaces {
+        /// Local image, or directory of images, sent without uploading it to S3
+        #[arg(long, conflicts_with_all = ["bucket", "prefix"], required_unless_present = "prefix")]
+        image: Option<String>,
+        /// Bucket where the images are stored [default: bucket of the profile]
+        #[arg(long, requires = "prefix")]
+        bucket: Option<String>,
+        /// Bucket path prefix under which the images are stored
+        #[arg(long)]
+        prefix: Option<String>,
+        /// Also mark the facial landmarks as points on the images
+        #[arg(long)]
+        landmarks: bool,
+    },
+    /// Blur, pixelate or box every face found in a local image or directory, or under a prefix
+    RedactFaces {
+        /// Local image, or directory of images, sent without uploading it to S3
+        #[arg(long, conflicts_with_all = ["bucket", "prefix"], required_unless_present = "prefix")]
+        image: Option<String>,
+        /// Bucket where the images are stored [default: bucket of the profile]
+        #[arg(long, requires = "prefix")]
+        bucket: Option<String>,
+        /// Bucket path prefix under which the images are stored
+        #[arg(long)]
+        prefix: Option<String>,
+        /// How the faces are hidden
+        #[arg(long, value_enum, default_value = "blur")]
+        redaction: Redaction,
+        /// Also upload the redacted images to this bucket
+        #[arg(long, requires = "upload_prefix")]
+        upload_bucket: Option<String>,
+        /// Bucket path prefix under which the redacted images are uploaded
+        #[arg(long, requires = "upload_bucket")]
+        upload_prefix: Option<String>,
+    },
+    /// Start a face detection task
+    StartFaceDetection {
+        /// Bucket where the face video is stored [default: bucket of the profile]
+        #[arg(long)]
+        bucket: Option<String>,
+        /// Key of the face video within the bucket
+        #[arg(long)]
+        key: String,
+    },
+    /// Get face detection results
+    GetFaceDetection {
+        #[arg(long)]
+        job_id: String,
+    },
+    /// Start a text detection task
+    StartTextDetection {
+        /// Bucket where the text video is stored [default: bucket of the profile]
+        #[arg(long)]
+        bucket: Option<String>,
+        /// Key of the text video within the bucket
+        #[arg(long)]
+        key: String,
+    },
+    /// Get text detection results
+    GetTextDetection {
+        #[arg(long)]
+        job_id: String,
+    },
+}
+
+/// The preprocessing steps of the image uploads.
+#[derive(Args)]
+pub struct PreprocessArgs {
+    /// Scale down the images whose width or height is larger, keeping the aspect ratio
+    /// [default: 800 for upload-modified-images]
+    #[arg(long, value_name = "PIXELS")]
+    max_dimension: Option<u32>,
+    /// Turn the images upright according to their EXIF orientation
+    /// [always on for upload-modified-images]
+    #[arg(long)]
+    auto_rotate: bool,
+    /// Convert the images to this format instead of keeping JPEG and PNG as they are
+    #[arg(long, value_enum)]
+    convert_to: Option<ConvertTo>,
+    /// JPEG quality, from 1 to 100
+    #[arg(long, default_value_t = 90, value_parser = clap::value_parser!(u8).range(1..=100))]
+    quality: u8,
+    /// Largest size in bytes an uploaded image may have; larger ones are scaled down
+    #[arg(long, value_name = "BYTES", default_value_t = REKOGNITION_MAX_BYTES)]
+    max_bytes: u64,
+}
+
+impl PreprocessArgs {
+    /// Applies the arguments on top of the defaults of the operation.
+    fn over(self, defaults: Preprocess) -> Preprocess {
+        Preprocess {
+            max_dimension: self.max_dimension.or(defaults.max_dimension),
+            auto_rotate: self.auto_rotate || defaults.auto_rotate,
+            convert_to: self.convert_to,
+            quality: self.quality,
+            max_bytes: self.max_bytes,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+pub enum TranslateCommand {
+    /// Get language info
+    Languages,
+    /// Translate text
+    Text {
+        /// Path to the plain text file to translate
+        #[arg(long)]
+        text_file: String,
+        /// Target language code [default: translate_target of the profile]
+        #[arg(long)]
+        target: Option<String>,
+    },
+    /// Translate document
+    Document {
+        /// Document type: 'Plain', 'Word' or 'Html'
+        #[arg(long)]
+        document_type: String,
+        /// Path to the document file
+        #[arg(long)]
+        document: String,
+        /// Target language code [default: translate_target of the profile]
+        #[arg(long)]
+        target: Option<String>,
+    },
+    /// Start text translation job
+    StartJob {
+        #[arg(long)]
+        job_name: String,
+        /// Document type: 'Plain', 'Word', 'Html', 'Ppt', 'Xlsx' or 'Xlf'
+        #[arg(long)]
+        document_type: String,
+        /// Input S3 URI, for example s3://bucket/folder
+        #[arg(long)]
+        input_uri: String,
+        /// Target language codes, up to 10 [default: translate_target of the profile]
+        #[arg(long = "target", num_args = 1..=10)]
+        targets: Vec<String>,
+        /// Output S3 URI, for example s3://bucket/new_folder
+        #[arg(long)]
+        output_uri: String,
+        /// Data access role ARN granting Amazon Translate read access to the input
+        #[arg(long)]
+        role_arn: String,
+    },
+    /// Describe text translation job
+    DescribeJob {
+        #[arg(long)]
+        job_id: String,
+    },
+    /// List text translation jobs
+    ListJobs,
+}
+
+#[derive(Subcommand)]
+pub enum TranscribeCommand {
+    /// Start transcription job
+    Start {
+        /// Output bucket where the task's output is stored [default: bucket of the profile]
+        #[arg(long)]
+        bucket: Option<String>,
+        /// S3 URL of the audio content, for example s3://bucket/audio.mp3
+        #[arg(long)]
+        media_uri: String,
+        /// Media format: mp3, mp4, wav, flac, ogg, amr or webm
+        #[arg(long)]
+        media_format: String,
+        #[arg(long)]
+        job_name: String,
+    },
+    /// Get transcription job
+    Get {
+        #[arg(long)]
+        job_name: String,
+    },
+    /// Transcription status
+    Status {
+        #[arg(long)]
+        job_name: String,
+    },
+    /// Download transcription results
+    Download {
+        /// Bucket whose transcription outputs are downloaded [default: bucket of the profile]
+        #[arg(long)]
+        bucket: Option<String>,
+    },
+    /// Retrieve the transcript from a JSON file
+    Transcript {
+        #[arg(long)]
+        json_file: String,
+    },
+}
+
+impl Cli {
+    pub fn settings(&self) -> Settings {
+        Settings {
+            wait: self.wait.then(|| Wait {
+                timeout: Duration::from_secs(self.wait_timeout),
+            }),
+            results: self.format.map(|format| ResultOutput {
+                format,
+                path: self.output.clone(),
+            }),
+            output_dir: self.output_dir.clone(),
+            walk: Walk {
+                recursive: self.recursive,
+                include: self.include.clone(),
+                exclude: self.exclude.clone(),
+                extensions: parse_extensions(&self.extensions.join(",")),
+            },
+            batch: Batch {
+                concurrency: usize::from(self.concurrency),
+            },
+            resume: self.resume.clone().map(|run_dir| Resume {
+                run_dir,
+                failed_only: self.failed_only,
+            }),
+        }
+    }
+}
+
+fn glob_pattern(pattern: &str) -> std::result::Result<Pattern, String> {
+    parse_pattern(pattern).map_err(|error| error.to_string())
+}
+
+pub async fn run(
+    command: Command,
+    env_vars: bool,
+    fixtures: Option<&str>,
+    profile: Profile,
+    settings: Settings,
+) {
+    let mut credential = CredentInitialize::default();
+    if fixtures.is_some() || matches!(command, Command::Jobs) {
+        // The fake backend and the job ledger never talk to AWS, so no credentials are needed
+    } else {
+        let loaded = match env_vars {
+            true => load_credentials_from_env_vars(&mut credential, profile.region.as_deref()),
+            false => load_profile_credentials(&mut credential, &profile).await,
+        };
+        if let Err(error) = loaded {
+            error.report();
+            std::process::exit(1);
+        }
+    }
+    let clients = Clients::build(&credential, fixtures, profile, settings);
+    let result = match command {
+        Command::Polly(command) => run_polly(command, &clients).await,
+        Command::Rekognition(command) => run_rekognition(command, &clients).await,
+        Command::Translate(command) => run_translate(command, &clients).await,
+        Command::Transcribe(command) => run_transcribe(command, &clients).await,
+        Command::Jobs => run_with(jobs::ListJobs { kind: None }, &clients).await,
+    };
+    credential.empty();
+    if let Err(error) = result {
+        error.report();
+        std::process::exit(1);
+    }
+}
+
+async fn run_polly(command: PollyCommand, clients: &Clients) -> Result<()> {
+    match command {
+        PollyCommand::Synthesize {
+            engine,
+            voice,
+            language,
+            text_type,
+            text_file,
+            audio_format,
+            bucket,
+            lexicons,
+            speech_marks,
+        } => {
+            let operation = polly::StartSpeechSynthesis {
+                engine_name: or_profile(
+                    engine,
+                    &clients.profile.polly_engine,
+                    "engine",
+                    "polly_engine",
+                )?,
+                voice_id: or_profile(voice, &clients.profile.polly_voice, "voice", "polly_voice")?,
+                language_code: language,
+                text_type,
+                text_path: text_file,
+                output_format: audio_format,
+                bucket_name: or_profile(bucket, &clients.profile.bucket, "bucket", "bucket")?,
+                lexicon_names: lexicons,
+                speech_mark_types: speech_marks,
+            };
+            run_with(operation, clients).await
+        }
+        PollyCommand::SynthesizeToFile {
+            engine,
+            voice,
+            language,
+            text_type,
+            text_file,
+            audio_format,
+            file,
+            lexicons,
+            speech_marks,
+        } => {
+            let operation = polly::SynthesizeToFile {
+                engine_name: or_profile(
+                    engine,
+                    &clients.profile.polly_engine,
+                    "engine",
+                    "polly_engine",
+                )?,
+                voice_id: or_profile(voice, &clients.profile.polly_voice, "voice", "polly_voice")?,
+                language_code: language,
+                text_type,
+                text_path: text_file,
+                output_format: audio_format,
+                output_path: file,
+                lexicon_names: lexicons,
+                speech_mark_types: speech_marks,
+            };
+            run_with(operation, clients).await
+        }
+        PollyCommand::Subtitles {
+            marks_file,
+            audio_file,
+        } => {
+            let operation = polly::ConvertSpeechMarks {
+                marks_path: marks_file,
+                audio_path: audio_file,
+            };
+            run_with(operation, clients).await
+        }
+        PollyCommand::GetSynthesis { task_id } => {
+            run_with(polly::GetSpeechSynthesisResult { task_id }, clients).await
+        }
+        PollyCommand::ListTasks => run_with(polly::ListSpeechSynthesisTasks, clients).await,
+        PollyCommand::GenerateAllVoices {
+            engine,
+            language,
+            text_type,
+            text_file,
+            prefix,
+            lexicons,
+        } => {
+            let operation = polly::GenerateAllVoices {
+                engine_name: or_profile(
+                    engine,
+                    &clients.profile.polly_engine,
+                    "engine",
+                    "polly_engine",
+                )?,
+                language_code: language,
+                text_type,
+                text_path: text_file,
+                path_prefix: prefix,
+                lexicon_names: lexicons,
+            };
+            run_with(operation, clients).await
+        }
+        PollyCommand::SampleVoices {
+            engine,
+            language,
+            text_type,
+            text_file,
+        } => {
+            let operation = polly::SampleVoices {
+                engine_name: engine,
+                language_code: language,
+                text_type,
+                text_path: text_file,
+            };
+            run_with(operation, clients).await
+        }
+        PollyCommand::PutLexicon { name, lexicon_file } => {
+            let operation = polly::PutLexicon {
+                name,
+                lexicon_path: lexicon_file,
+            };
+            run_with(operation, clients).await
+        }
+        PollyCommand::Lexicons => run_with(polly::ListLexicons, clients).await,
+        PollyCommand::GetLexicon { name, file } => {
+            let operation = polly::GetLexicon {
+                name,
+                output_path: file,
+            };
+            run_with(operation, clients).await
+        }
+        PollyCommand::DeleteLexicon { name } => {
+            run_with(polly::DeleteLexicon { name }, clients).await
+        }
+        PollyCommand::ValidateSsml { engine, text_file } => {
+            let operation = polly::ValidateSsml {
+                engine_name: or_profile(
+                    engine,
+                    &clients.profile.polly_engine,
+                    "engine",
+                    "polly_engine",
+                )?,
+                text_path: text_file,
+            };
+            run_with(operation, clients).await
+        }
+        PollyCommand::Voices => run_with(polly::DescribeVoices, clients).await,
+    }
+}
+
+/// The value of a flag, or the setting of the active profile when it's not given.
+fn or_profile(
+    value: Option<String>,
+    default: &Option<String>,
+    flag: &'static str,
+    setting: &'static str,
+) -> Result<String> {
+    value
+        .or_else(|| default.clone())
+        .ok_or(Error::MissingArgument { flag, setting })
+}
+
+/// Builds the image source out of the mutually exclusive local and S3 arguments,
+/// the bucket of the profile holding the key when no bucket is given.
+fn source(
+    local: Option<String>,
+    bucket: Option<String>,
+    key: Option<String>,
+    profile: &Profile,
+) -> Result<Source> {
+    match (local, key) {
+        (Some(local), _) => Ok(Source::Local(local)),
+        (None, Some(key)) => Ok(Source::S3 {
+            bucket: or_profile(bucket, &profile.bucket, "bucket", "bucket")?,
+            key,
+        }),
+        _ => unreachable!("clap enforces either a local path or a key"),
+    }
+}
+
+async fn run_rekognition(command: RekognitionCommand, clients: &Clients) -> Result<()> {
+    match command {
+        RekognitionCommand::RecognizeCelebrity {
+            image,
+            bucket,
+            key,
+            annotate,
+            crop_faces,
+        } => {
+            let operation = rekognition::RecognizeCelebrity {
+                source: source(image, bucket, key, &clients.profile)?,
+                visuals: CelebrityVisuals {
+                    annotate,
+                    crop_faces,
+                },
+            };
+            run_with(operation, clients).await
+        }
+        RekognitionCommand::UploadImages {
+            bucket,
+            dir,
+            prefix,
+            preprocess,
+        } => {
+            let operation = rekognition::UploadImages {
+                bucket_name: or_profile(bucket, &clients.profile.bucket, "bucket", "bucket")?,
+                local_path_prefix: or_profile(
+                    dir,
+                    &clients.profile.local_prefix,
+                    "dir",
+                    "local_prefix",
+                )?,
+                bucket_path_prefix: or_profile(
+                    prefix,
+                    &clients.profile.bucket_prefix,
+                    "prefix",
+                    "bucket_prefix",
+                )?,
+                preprocess: preprocess.over(Preprocess::default()),
+            };
+            run_with(operation, clients).await
+        }
+        RekognitionCommand::RecognizeCelebrities {
+            dir,
+            bucket,
+            prefix,
+            annotate,
+            crop_faces,
+        } => {
+            let operation = rekognition::RecognizeCelebrities {
+                source: source(dir, bucket, prefix, &clients.profile)?,
+                visuals: CelebrityVisuals {
+                    annotate,
+                    crop_faces,
+                },
+            };
+            run_with(operation, clients).await
+        }
+        RekognitionCommand::DetectFaces {
+            image,
+            bucket,
+            key,
+            prefix,
+        } => {
+            let images = match (image, key, prefix) {
+                (Some(image), _, _) => Source::local_images(&image, &clients.settings.walk)?,
+                (None, key, prefix) => {
+                    let bucket = or_profile(bucket, &clients.profile.bucket, "bucket", "bucket")?;
+                    let keys = match (key, prefix) {
+                        (Some(key), _) => vec![key],
+                        (None, Some(prefix)) => {
+                            clients
+                                .s3
+                                .list_objects_given_prefix(&bucket, &prefix)
+                                .await?
+                        }
+                        (None, None) => {
+                            unreachable!("clap enforces either --image, --key or --prefix")
+                        }
+                    };
+                    keys.into_iter()
+                        .map(|key| Source::S3 {
+                            bucket: bucket.clone(),
+                            key,
+                        })
+                        .collect()
+                }
+            };
+            let operation = rekognition::DetectFaces { images };
+            run_with(operation, clients).await
+        }
+        RekognitionCommand::DetectText {
+            image,
+            bucket,
+            key,
+            overlay,
+        } => {
+            let images = match source(image, bucket, key, &clients.profile)? {
+                Source::Local(path) => Source::local_images(&path, &clients.settings.walk)?,
+                object => vec![object],
+            };
+            let operation = rekognition::DetectText { images, overlay };
+            run_with(operation, clients).await
+        }
+        RekognitionCommand::UploadModifiedImages {
+            bucket,
+            dir,
+            prefix,
+            preprocess,
+        } => {
+            let operation = rekognition::UploadModifiedImages {
+                bucket_name: or_profile(bucket, &clients.profile.bucket, "bucket", "bucket")?,
+                local_path_prefix: or_profile(
+                    dir,
+                    &clients.profile.local_prefix,
+                    "dir",
+                    "local_prefix",
+                )?,
+                bucket_path_prefix: or_profile(
+                    prefix,
+                    &clients.profile.bucket_prefix,
+                    "prefix",
+                    "bucket_prefix",
+                )?,
+                preprocess: preprocess
+                    .over(rekognition::UploadModifiedImages::default_preprocess()),
+            };
+            run_with(operation, clients).await
+        }
+        RekognitionCommand::AnnotateFaces {
+            image,
+            bucket,
+            prefix,
+            landmarks,
+        } => {
+            let operation = rekognition::WriteFaceDetailsImages {
+                source: source(image, bucket, prefix, &clients.profile)?,
+                draw_landmarks: landmarks,
+            };
+            run_with(operation, clients).await
+        }
+        RekognitionCommand::RedactFaces {
+            image,
+            bucket,
+            prefix,
+            redaction,
+            upload_bucket,
+            upload_prefix,
+        } => {
+            let operation = rekognition::RedactFaces {
+                source: source(image, bucket, prefix, &clients.profile)?,
+                redaction,
+                upload_to: upload_bucket.zip(upload_prefix),
+            };
+            run_with(operation, clients).await
+        }
+        RekognitionCommand::StartFaceDetection { bucket, key } => {
+            let operation = rekognition::StartFaceDetection {
+                bucket_name: or_profile(bucket, &clients.profile.bucket, "bucket", "bucket")?,
+                key,
+            };
+            run_with(operation, clients).await
+        }
+        RekognitionCommand::GetFaceDetection { job_id } => {
+            run_with(rekognition::GetFaceDetection { job_id }, clients).await
+        }
+        RekognitionCommand::StartTextDetection { bucket, key } => {
+            let operation = rekognition::StartTextDetection {
+                bucket_name: or_profile(bucket, &clients.profile.bucket, "bucket", "bucket")?,
+                key,
+            };
+            run_with(operation, clients).await
+        }
+        RekognitionCommand::GetTextDetection { job_id } => {
+            run_with(rekognition::GetTextDetection { job_id }, clients).await
+        }
+    }
+}
+
+async fn run_translate(command: TranslateCommand, clients: &Clients) -> Result<()> {
+    match command {
+        TranslateCommand::Languages => run_with(translate::LanguageInfo, clients).await,
+        TranslateCommand::Text { text_file, target } => {
+            let operation = translate::TranslateText {
+                text_path: text_file,
+                target_lang_code: or_profile(
+                    target,
+                    &clients.profile.translate_target,
+                    "target",
+                    "translate_target",
+                )?,
+            };
+            run_with(operation, clients).await
+        }
+        TranslateCommand::Document {
+            document_type,
+            document,
+            target,
+        } => {
+            let operation = translate::TranslateDocument {
+                document_type,
+                document_path: document,
+                target_lang_code: or_profile(
+                    target,
+                    &clients.profile.translate_target,
+                    "target",
+                    "translate_target",
+                )?,
+            };
+            run_with(operation, clients).await
+        }
+        TranslateCommand::StartJob {
+            job_name,
+            document_type,
+            input_uri,
+            targets,
+            output_uri,
+            role_arn,
+        } => {
+            let targets = match targets.is_empty() {
+                true => vec![or_profile(
+                    None,
+                    &clients.profile.translate_target,
+                    "target",
+                    "translate_target",
+                )?],
+                false => targets,
+            };
+            let operation = translate::StartTextTranslationJob {
+                job_name,
+                document_type,
+                input_s3_uri: input_uri,
+                target_lang_codes: targets,
+                output_s3_uri: output_uri,
+                role_arn,
+            };
+            run_with(operation, clients).await
+        }
+        TranslateCommand::DescribeJob { job_id } => {
+            run_with(translate::DescribeTextTranslationJob { job_id }, clients).await
+        }
+        TranslateCommand::ListJobs => run_with(translate::ListTextTranslationJobs, clients).await,
+    }
+}
+
+async fn run_transcribe(command: TranscribeCommand, clients: &Clients) -> Result<()> {
+    match command {
+        TranscribeCommand::Start {
+            bucket,
+            media_uri,
+            media_format,
+            job_name,
+        } => {
+            let operation = transcribe::StartTranscriptionJob {
+                bucket_name: or_profile(bucket, &clients.profile.bucket, "bucket", "bucket")?,
+                key_audio_name: media_uri,
+                media_format,
+                job_name,
+            };
+            run_with(operation, clients).await
+        }
+        TranscribeCommand::Get { job_name } => {
+            run_with(transcribe::GetTranscriptionJob { job_name }, clients).await
+        }
+        TranscribeCommand::Status { job_name } => {
+            run_with(transcribe::TranscriptionStatus { job_name }, clients).await
+        }
+        TranscribeCommand::Download { bucket } => {
+            let operation = transcribe::DownloadTranscriptionResults {
+                bucket_name: or_profile(bucket, &clients.profile.bucket, "bucket", "bucket")?,
+            };
+            run_with(operation, clients).await
+        }
+        TranscribeCommand::Transcript { json_file } => {
+            let operation = transcribe::ExtractTranscript {
+                json_path: json_file,
+            };
+            run_with(operation, clients).await
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use async_trait::async_trait;
+use colored::Colorize;
+use inquire::{Select, Text};
+
+use super::{Clients, Operation};
+use crate::error::Result;
+use crate::ledger::{JobKind, JobRecord, Ledger};
+use crate::output::Results;
+
+/// Offers the recorded jobs of a kind to pick from, falling back to the given
+/// prompt when there are none or when the user wants to enter another ID.
+pub fn pick_job(kind: JobKind, prompt: Text) -> Result<String> {
+    let ledger = Ledger::load()?;
+    let jobs = ledger.jobs(Some(kind));
+    if jobs.is_empty() {
+        return Ok(prompt.prompt()?);
+    }
+    let mut options: Vec<String> = jobs.iter().map(|job| job.to_string()).collect();
+    options.push("Enter another ID\n".into());
+    let choice = Select::new(prompt.message, options)
+        .with_page_size(10)
+        .with_help_message("These jobs were started from this machine, the most recent first")
+        .raw_prompt()?;
+    match jobs.get(choice.index) {
+        Some(job) => {
+            println!("{}\n", format!("Using the job '{}'", job.id).green().bold());
+            Ok(job.id.clone())
+        }
+        None => Ok(prompt.prompt()?),
+    }
+}
+
+pub struct ListJobs {
+    pub kind: Option<JobKind>,
+}
+
+#[async_trait(?Send)]
+impl Operation for ListJobs {
+    type Output = Vec<JobRecord>;
+
+    async fn gather(_: &Clients) -> Result<Option<Self>> {
+        Ok(Some(Self { kind: None }))
+    }
+
+    async fn execute(&self, _: &Clients) -> Result<Vec<JobRecord>> {
+        let ledger = Ledger::load()?;
+        Ok(ledger.jobs(self.kind).into_iter().cloned().collect())
+    }
+
+    fn results(&self, jobs: &Vec<JobRecord>) -> Result<Option<Results>> {
+        Results::new("jobs", jobs).map(Some)
+    }
+
+    fn render(&self, jobs: Vec<JobRecord>) -> Result<()> {
+        if jobs.is_empty() {
+            println!("{}\n", "No jobs have been started yet".yellow().bold());
+            return Ok(());
+        }
+        for job in jobs {
+            println!("{}: {}", job.kind.to_string().green().bold(), job);
+        }
+        println!();
+        Ok(())
+    }
+}
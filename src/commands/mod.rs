@@ -0,0 +1,212 @@
+use async_trait::async_trait;
+use aws_apis::CredentInitialize;
+use inquire::Text;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+use crate::batch::{Batch, Checkpoint, Resume};
+use crate::config::Profile;
+use crate::error::{Error, Result};
+use crate::output::{ResultOutput, Results};
+use crate::services::{
+    aws::{AwsPolly, AwsRekognition, AwsS3, AwsTranscribe, AwsTranslate},
+    fake::FakeBackend,
+    PollyService, RekognitionService, S3Service, TranscribeService, TranslateService,
+};
+use crate::wait::Wait;
+use crate::walk::Walk;
+use crate::workspace::{self, DEFAULT_OUTPUT_ROOT};
+
+pub mod jobs;
+pub mod polly;
+pub mod rekognition;
+pub mod transcribe;
+pub mod translate;
+
+/// The choices of the session that outlive rebuilding the clients.
+#[derive(Clone, Default)]
+pub struct Settings {
+    /// Set when the results of asynchronous jobs are waited for.
+    pub wait: Option<Wait>,
+    /// Set when the results are also written in a structured format.
+    pub results: Option<ResultOutput>,
+    /// Overrides the output root of the profile.
+    pub output_dir: Option<PathBuf>,
+    /// Which files of the local directories the batch operations pick.
+    pub walk: Walk,
+    pub batch: Batch,
+    /// Set when the batch operation continues an earlier run.
+    pub resume: Option<Resume>,
+}
+
+/// The service clients every operation is executed against.
+///
+/// They are rebuilt as a whole whenever the credentials or the profile change.
+pub struct Clients {
+    /// The profile the clients were built for, whose defaults pre-fill the prompts.
+    pub profile: Profile,
+    pub settings: Settings,
+    pub s3: Box<dyn S3Service>,
+    pub polly: Box<dyn PollyService>,
+    pub rekognition: Box<dyn RekognitionService>,
+    pub transcribe: Box<dyn TranscribeService>,
+    pub translate: Box<dyn TranslateService>,
+}
+
+impl Clients {
+    /// Builds the AWS clients, or the fake backend reading from `fixtures` when it is given.
+    pub fn build(
+        credential: &CredentInitialize,
+        fixtures: Option<&str>,
+        profile: Profile,
+        settings: Settings,
+    ) -> Self {
+        if let Some(fixtures) = fixtures {
+            let fake = FakeBackend::new(fixtures);
+            return Self {
+                profile,
+                settings,
+                s3: Box::new(fake.clone()),
+                polly: Box::new(fake.clone()),
+                rekognition: Box::new(fake.clone()),
+                transcribe: Box::new(fake.clone()),
+                translate: Box::new(fake),
+            };
+        }
+        let sdk_config = credential.build();
+        Self {
+            profile,
+            settings,
+            s3: Box::new(AwsS3::build(&sdk_config)),
+            polly: Box::new(AwsPolly::build(&sdk_config)),
+            rekognition: Box::new(AwsRekognition::build(&sdk_config)),
+            transcribe: Box::new(AwsTranscribe::build(&sdk_config)),
+            translate: Box::new(AwsTranslate::build(&sdk_config)),
+        }
+    }
+
+    /// Creates the directory a run of the named operation writes its files to,
+    /// under the output root of the command line, the profile or the default one.
+    ///
+    /// When an earlier run is resumed, its directory is returned instead,
+    /// provided it's a run of the same operation.
+    pub fn run_dir(&self, name: &str) -> Result<PathBuf> {
+        if let Some(resume) = &self.settings.resume {
+            let is_run_of = resume
+                .run_dir
+                .file_name()
+                .is_some_and(|dir| dir.to_string_lossy().starts_with(&format!("{name}-")));
+            if !is_run_of || !resume.run_dir.is_dir() {
+                return Err(Error::NotResumable {
+                    path: resume.run_dir.clone(),
+                    operation: name.to_string(),
+                });
+            }
+            return workspace::absolute(&resume.run_dir);
+        }
+        let root = self
+            .settings
+            .output_dir
+            .as_deref()
+            .or(self.profile.output_dir.as_deref())
+            .unwrap_or(Path::new(DEFAULT_OUTPUT_ROOT));
+        workspace::run_dir(root, name)
+    }
+
+    /// The checkpoint of the batch run writing to `run_dir`.
+    pub fn checkpoint(&self, run_dir: &Path) -> Result<Checkpoint> {
+        let failed_only = self
+            .settings
+            .resume
+            .as_ref()
+            .is_some_and(|resume| resume.failed_only);
+        Checkpoint::open(run_dir, failed_only)
+    }
+
+    /// Fetches the state of a job, polling until its status leaves the `pending`
+    /// ones when the results are waited for.
+    pub async fn fetch_job<T, F, Fut, S>(
+        &self,
+        job: &str,
+        pending: &[&str],
+        status_of: S,
+        fetch: F,
+    ) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+        S: Fn(&T) -> Option<&str>,
+    {
+        match &self.settings.wait {
+            Some(wait) => wait.until_done(job, pending, status_of, fetch).await,
+            None => {
+                let mut fetch = fetch;
+                fetch().await
+            }
+        }
+    }
+}
+
+/// Pre-fills a prompt with a default of the active profile, when it has one.
+pub trait ProfileDefault<'a> {
+    fn with_profile_default(self, default: &'a Option<String>) -> Self;
+}
+
+impl<'a> ProfileDefault<'a> for Text<'a> {
+    fn with_profile_default(self, default: &'a Option<String>) -> Self {
+        match default {
+            Some(default) => self.with_initial_value(default),
+            None => self,
+        }
+    }
+}
+
+/// A single entry of the menus.
+///
+/// The value of the implementing type holds the inputs of the operation, so the
+/// interactive menu builds it through [`Operation::gather`] while the CLI builds
+/// it straight from the command line arguments.
+#[async_trait(?Send)]
+pub trait Operation: Sized {
+    type Output;
+
+    /// Prompts for the inputs of the operation, returning `None` if they are not usable.
+    async fn gather(clients: &Clients) -> Result<Option<Self>>;
+
+    async fn execute(&self, clients: &Clients) -> Result<Self::Output>;
+
+    /// Operations that print everything while executing have nothing left to render.
+    fn render(&self, _output: Self::Output) -> Result<()> {
+        Ok(())
+    }
+
+    /// The structured records of the output, for operations that produce any.
+    fn results(&self, _output: &Self::Output) -> Result<Option<Results>> {
+        Ok(None)
+    }
+}
+
+/// Runs an operation from the interactive menu.
+///
+/// Errors are reported rather than returned so the user stays in the submenu.
+pub async fn run<T: Operation>(clients: &Clients) {
+    let result = match T::gather(clients).await {
+        Ok(Some(operation)) => run_with(operation, clients).await,
+        Ok(None) => Ok(()),
+        Err(error) => Err(error),
+    };
+    if let Err(error) = result {
+        error.report();
+    }
+}
+
+/// Runs an operation whose inputs are already known.
+pub async fn run_with<T: Operation>(operation: T, clients: &Clients) -> Result<()> {
+    let output = operation.execute(clients).await?;
+    if let Some(result_output) = &clients.settings.results {
+        if let Some(results) = operation.results(&output)? {
+            results.write(result_output)?;
+        }
+    }
+    operation.render(output)
+}
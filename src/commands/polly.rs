@@ -0,0 +1,1595 @@
+use async_trait::async_trait;
+use colored::Colorize;
+use inquire::{Confirm, Select, Text};
+use std::fs::{create_dir_all, read, read_to_string, write, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use super::{jobs::pick_job, run_with, Clients, Operation, ProfileDefault};
+use crate::error::{Error, Result, WithPath};
+use crate::gallery::{self, VoiceSample};
+use crate::ledger::{JobKind, Ledger};
+use crate::output::Results;
+use crate::pdf;
+use crate::pls::{self, Summary};
+use crate::services::{Lexicon, SynthesisTask, Voice};
+use crate::speech_marks::{self, SpeechMark, SPEECH_MARK_TYPES};
+use crate::ssml::{self, Engine};
+
+/// The text types of the content to synthesize.
+const TEXT_TYPES: [&str; 2] = ["text", "ssml"];
+
+/// The formats a synthesis task writes, where 'json' is the speech marks.
+const OUTPUT_FORMATS: [&str; 4] = ["mp3", "ogg_vorbis", "pcm", "json"];
+
+/// Offers the choices, given as their values and labels, starting at the
+/// default one, and returns the value of the picked one.
+fn pick(
+    message: &str,
+    choices: Vec<(String, String)>,
+    default: Option<&str>,
+    help_message: &str,
+) -> Result<String> {
+    let cursor = starting_cursor(&choices, default);
+    let labels = choices.iter().map(|(_, label)| label.clone()).collect();
+    let choice = Select::new(message, labels)
+        .with_starting_cursor(cursor)
+        .with_page_size(10)
+        .with_help_message(help_message)
+        .raw_prompt()?;
+    Ok(choices[choice.index].0.clone())
+}
+
+/// Where the default is among the choices, or the first choice when it
+/// isn't one of them.
+fn starting_cursor(choices: &[(String, String)], default: Option<&str>) -> usize {
+    default
+        .and_then(|default| choices.iter().position(|(value, _)| value == default))
+        .unwrap_or_default()
+}
+
+/// Offers a fixed list of values, labelled as they are.
+fn pick_value(message: &str, values: &[&str], help_message: &str) -> Result<String> {
+    let choices = values
+        .iter()
+        .map(|value| (value.to_string(), value.to_string()))
+        .collect();
+    pick(message, choices, None, help_message)
+}
+
+fn supports(voice: &Voice, engine_name: &str) -> bool {
+    voice
+        .supported_engines
+        .iter()
+        .any(|engine| engine == engine_name)
+}
+
+/// The engines the voices support.
+fn engine_choices(voices: &[Voice]) -> Vec<(String, String)> {
+    let mut engines: Vec<&String> = voices
+        .iter()
+        .flat_map(|voice| &voice.supported_engines)
+        .collect();
+    engines.sort();
+    engines.dedup();
+    engines
+        .into_iter()
+        .map(|engine| (engine.clone(), engine.clone()))
+        .collect()
+}
+
+/// Asks for one of the engines the voices support.
+fn prompt_engine(clients: &Clients, voices: &[Voice], message: &str) -> Result<String> {
+    pick(
+        message,
+        engine_choices(voices),
+        clients.profile.polly_engine.as_deref(),
+        "Only the engines of the voices available in the region are listed",
+    )
+}
+
+/// The languages of the voices supporting the engine, labelled with their names.
+fn language_choices(voices: &[Voice], engine_name: &str) -> Vec<(String, String)> {
+    let mut languages: Vec<(String, String)> = voices
+        .iter()
+        .filter(|voice| supports(voice, engine_name))
+        .map(|voice| {
+            let label = format!("{} ({})", voice.language_code, voice.language_name);
+            (voice.language_code.clone(), label)
+        })
+        .collect();
+    languages.sort();
+    languages.dedup();
+    languages
+}
+
+/// Asks for one of the languages of the voices supporting the engine.
+fn prompt_language(voices: &[Voice], engine_name: &str) -> Result<String> {
+    pick(
+        "Select the audio language\n",
+        language_choices(voices, engine_name),
+        None,
+        &format!("Only the languages with '{engine_name}' voices are listed"),
+    )
+}
+
+/// The voices supporting the engine and speaking the language, labelled with
+/// their genders.
+fn voice_choices(
+    voices: &[Voice],
+    engine_name: &str,
+    language_code: &str,
+) -> Vec<(String, String)> {
+    let mut voice_ids: Vec<(String, String)> = voices
+        .iter()
+        .filter(|voice| supports(voice, engine_name) && voice.language_code == language_code)
+        .map(|voice| {
+            let label = format!("{} ({})", voice.voice_id, voice.gender);
+            (voice.voice_id.clone(), label)
+        })
+        .collect();
+    voice_ids.sort();
+    voice_ids
+}
+
+/// Asks for the engine, then for one of its languages and for one of the
+/// voices speaking it, returning `None` when Polly has no voices.
+async fn prompt_voice(clients: &Clients) -> Result<Option<(String, String, String)>> {
+    let voices = clients.polly.describe_voices().await?;
+    if voices.is_empty() {
+        println!("{}\n", "No voices are available to pick from".red().bold());
+        return Ok(None);
+    }
+    let engine_name = prompt_engine(
+        clients,
+        &voices,
+        "Select the speech generation engine name\n",
+    )?;
+    let language_code = prompt_language(&voices, &engine_name)?;
+    let voice_id = pick(
+        "Select the voice for audio generation\n",
+        voice_choices(&voices, &engine_name, &language_code),
+        clients.profile.polly_voice.as_deref(),
+        &format!("Only the '{engine_name}' voices speaking {language_code} are listed"),
+    )?;
+    Ok(Some((engine_name, voice_id, language_code)))
+}
+
+/// Asks for the lexicons applied to the speech, offering the stored ones.
+async fn prompt_lexicon_names(clients: &Clients) -> Result<Vec<String>> {
+    let lexicons: Vec<String> = clients
+        .polly
+        .list_lexicons()
+        .await?
+        .into_iter()
+        .map(|lexicon| lexicon.name)
+        .collect();
+    if lexicons.is_empty() {
+        return Ok(Vec::new());
+    }
+    let available_lexicons = format!("Available lexicons:\n{}\n", lexicons.join(" | "));
+    let lexicon_names = Text::new(
+        "Names of the pronunciation lexicons to apply, separated by commas\n",
+    )
+    .with_placeholder(&available_lexicons)
+    .with_formatter(&|str| format!(".....{str}.....\n"))
+    .with_help_message(
+        "Leave it empty to apply none; at most 5 are applied, the earlier ones taking precedence",
+    )
+    .prompt()?;
+    pls::parse_names(&lexicon_names)
+}
+
+/// Asks for the types of the speech marks to write along with the audio.
+fn prompt_speech_mark_types(help_message: &str) -> Result<Vec<String>> {
+    let speech_mark_types = Text::new("Types of the speech marks to write, separated by commas\n")
+        .with_placeholder(&SPEECH_MARK_TYPES.join(" | "))
+        .with_formatter(&|str| format!(".....{str}.....\n"))
+        .with_help_message(help_message)
+        .prompt()?;
+    speech_marks::parse_types(&speech_mark_types)
+}
+
+pub struct StartSpeechSynthesis {
+    pub engine_name: String,
+    pub voice_id: String,
+    /// The language of the voice is spoken when it's not set.
+    pub language_code: Option<String>,
+    pub text_type: String,
+    pub text_path: String,
+    pub output_format: String,
+    pub bucket_name: String,
+    pub lexicon_names: Vec<String>,
+    /// Written instead of the audio with the 'json' format, and by a second
+    /// task next to the audio otherwise.
+    pub speech_mark_types: Vec<String>,
+}
+
+impl StartSpeechSynthesis {
+    /// Starts a task writing the speech in the format and records it.
+    async fn start(
+        &self,
+        clients: &Clients,
+        text: &str,
+        output_format: &str,
+        speech_mark_types: &[String],
+    ) -> Result<String> {
+        let task_id = clients
+            .polly
+            .start_speech_synthesis_task(
+                &self.engine_name,
+                &self.voice_id,
+                self.language_code.as_deref(),
+                &self.text_type,
+                text,
+                output_format,
+                &self.bucket_name,
+                &self.lexicon_names,
+                speech_mark_types,
+            )
+            .await?;
+        let speech_mark_types = speech_mark_types.join(",");
+        let mut inputs = vec![
+            ("engine", self.engine_name.as_str()),
+            ("voice", &self.voice_id),
+            ("text", &self.text_path),
+            ("format", output_format),
+            ("bucket", &self.bucket_name),
+        ];
+        if let Some(language_code) = &self.language_code {
+            inputs.push(("language", language_code));
+        }
+        if !speech_mark_types.is_empty() {
+            inputs.push(("speech_marks", &speech_mark_types));
+        }
+        Ledger::record(
+            JobKind::SpeechSynthesis,
+            &task_id,
+            &inputs,
+            Some("scheduled"),
+        )?;
+        Ok(task_id)
+    }
+}
+
+#[async_trait(?Send)]
+impl Operation for StartSpeechSynthesis {
+    /// The task ID, unless the task was waited for and its results are already shown.
+    type Output = Option<String>;
+
+    async fn gather(clients: &Clients) -> Result<Option<Self>> {
+        let Some((engine_name, voice_id, language_code)) = prompt_voice(clients).await? else {
+            return Ok(None);
+        };
+        let text_type = pick_value(
+            "Please provide the text format of the content for which you would like to synthesize audio\n",
+            &TEXT_TYPES,
+            "SSML is checked for the engine before it's sent",
+        )?;
+        let text_path = Text::new(
+            "Please specify the path of the text file for which you would like audio generation\n",
+        )
+        .with_placeholder(
+            "The format of the text content is determined by the preceding selections\n",
+        )
+        .with_help_message("Click here https://tinyurl.com/ynjmpur3 to Learn more")
+        .with_formatter(&|str| format!(".....{str}.....\n"))
+        .prompt()?;
+        let output_format = pick_value(
+            "Please select the output format for the generated speech content\n",
+            &OUTPUT_FORMATS,
+            "'json' writes the speech marks instead of the audio",
+        )?;
+        let speech_mark_types = prompt_speech_mark_types(
+            "'json' writes only the speech marks; with an audio format they are written by a second task, and leaving it empty writes none",
+        )?;
+        let available_buckets = format!(
+            "Available Buckets in your account:\n{:#?}\n",
+            clients.s3.get_buckets().await?
+        );
+        let bucket_name = Text::new("Amazon S3 bucket name to which the output file will be saved\n")
+            .with_placeholder(&available_buckets)
+            .with_formatter(&|str| format!(".....{str}.....\n"))
+            .with_help_message("The chosen bucket name should be available in different regions to enable multi region access")
+            .with_profile_default(&clients.profile.bucket)
+            .prompt()?;
+        let lexicon_names = prompt_lexicon_names(clients).await?;
+        match (text_path.is_empty(), bucket_name.is_empty()) {
+            (false, false) => Ok(Some(Self {
+                engine_name,
+                voice_id,
+                language_code: Some(language_code),
+                text_type,
+                text_path,
+                output_format,
+                bucket_name,
+                lexicon_names,
+                speech_mark_types,
+            })),
+            _ => {
+                println!("{}\n", "Fields can't be left empty".red().bold());
+                Ok(None)
+            }
+        }
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<Option<String>> {
+        pls::check_names(&self.lexicon_names)?;
+        speech_marks::check_types(&self.speech_mark_types)?;
+        let only_speech_marks = self.output_format == "json";
+        if only_speech_marks && self.speech_mark_types.is_empty() {
+            return Err(Error::MissingSpeechMarkTypes);
+        }
+        let mut speech_text_data = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&self.text_path)
+            .with_path(&self.text_path)?;
+        let mut text_to_generate_speech = String::new();
+        speech_text_data
+            .read_to_string(&mut text_to_generate_speech)
+            .with_path(&self.text_path)?;
+        let text_to_generate_speech =
+            ssml::prepare(&text_to_generate_speech, &self.text_type, &self.engine_name)?;
+
+        let speech_mark_types: &[String] = match only_speech_marks {
+            true => &self.speech_mark_types,
+            false => &[],
+        };
+        let task_id = self
+            .start(
+                clients,
+                &text_to_generate_speech,
+                &self.output_format,
+                speech_mark_types,
+            )
+            .await?;
+        println!("Task ID: {}", task_id.green().bold());
+        // The audio and its speech marks are written by separate tasks.
+        let speech_marks_task_id = match only_speech_marks || self.speech_mark_types.is_empty() {
+            true => None,
+            false => {
+                let task_id = self
+                    .start(
+                        clients,
+                        &text_to_generate_speech,
+                        "json",
+                        &self.speech_mark_types,
+                    )
+                    .await?;
+                println!("Speech marks task ID: {}", task_id.green().bold());
+                Some(task_id)
+            }
+        };
+        if clients.settings.wait.is_none() {
+            return Ok(Some(task_id));
+        }
+        run_with(GetSpeechSynthesisResult { task_id }, clients).await?;
+        if let Some(task_id) = speech_marks_task_id {
+            run_with(GetSpeechSynthesisResult { task_id }, clients).await?;
+        }
+        Ok(None)
+    }
+
+    fn render(&self, task_id: Option<String>) -> Result<()> {
+        if task_id.is_some() {
+            println!("{}\n","The task is recorded, so 'Get speech synthesis results' offers it without typing the ID".yellow().bold());
+        }
+        Ok(())
+    }
+}
+
+/// The most characters a single 'SynthesizeSpeech' request is billed for,
+/// which the longer texts are split by.
+const MAX_REQUEST_CHARACTERS: usize = 3000;
+
+/// The most characters a single 'SynthesizeSpeech' request can have, SSML
+/// tags included.
+const MAX_REQUEST_TOTAL_CHARACTERS: usize = 6000;
+
+/// The formats the speech is written to a local file in.
+const AUDIO_FORMATS: [&str; 3] = ["mp3", "ogg_vorbis", "pcm"];
+
+/// Splits the text into parts of at most `limit` characters, at the end of a
+/// sentence when there is one, then at a space, and anywhere otherwise.
+///
+/// Each part comes with the byte offset it starts at within `text`.
+fn split_text(text: &str, limit: usize) -> Vec<(usize, &str)> {
+    let mut parts = Vec::new();
+    let mut offset = text.len() - text.trim_start().len();
+    let mut rest = text.trim();
+    while !rest.is_empty() {
+        let Some((end, _)) = rest.char_indices().nth(limit) else {
+            parts.push((offset, rest));
+            break;
+        };
+        let window = &rest[..end];
+        let cut = window
+            .rfind(['.', '!', '?', '\n'])
+            .map(|index| index + 1)
+            .or_else(|| window.rfind(char::is_whitespace))
+            .filter(|cut| *cut > 0)
+            .unwrap_or(end);
+        let part = rest[..cut].trim_end();
+        if !part.is_empty() {
+            parts.push((offset, part));
+        }
+        let next = rest[cut..].trim_start();
+        offset += rest.len() - next.len();
+        rest = next;
+    }
+    parts
+}
+
+/// Writes the speech marks as subtitles next to `base_path`, as '.vtt' and
+/// '.srt' files, returning their paths.
+///
+/// Nothing is written without sentence or word marks, which the cues are made of.
+fn write_subtitles(base_path: &Path, marks: &[SpeechMark], length: u64) -> Result<Vec<PathBuf>> {
+    let cues = speech_marks::cues(marks, length);
+    if cues.is_empty() {
+        return Ok(Vec::new());
+    }
+    let webvtt_path = base_path.with_extension("vtt");
+    write(&webvtt_path, speech_marks::to_webvtt(&cues)).with_path(&webvtt_path)?;
+    let srt_path = base_path.with_extension("srt");
+    write(&srt_path, speech_marks::to_srt(&cues)).with_path(&srt_path)?;
+    Ok(vec![webvtt_path, srt_path])
+}
+
+/// Synthesizes a short text right away, without a task or a bucket, and
+/// writes the audio to a local file.
+pub struct SynthesizeToFile {
+    pub engine_name: String,
+    pub voice_id: String,
+    /// The language of the voice is spoken when it's not set.
+    pub language_code: Option<String>,
+    pub text_type: String,
+    pub text_path: String,
+    /// One of [`AUDIO_FORMATS`].
+    pub output_format: String,
+    /// Written into the run directory when not set.
+    pub output_path: Option<PathBuf>,
+    pub lexicon_names: Vec<String>,
+    /// Written next to the audio along with the subtitles made of them.
+    pub speech_mark_types: Vec<String>,
+}
+
+#[async_trait(?Send)]
+impl Operation for SynthesizeToFile {
+    /// The file the audio is written to.
+    type Output = PathBuf;
+
+    async fn gather(clients: &Clients) -> Result<Option<Self>> {
+        let Some((engine_name, voice_id, language_code)) = prompt_voice(clients).await? else {
+            return Ok(None);
+        };
+        let text_type = pick_value(
+            "Please provide the text format of the content for which you would like to synthesize audio\n",
+            &TEXT_TYPES,
+            "Texts longer than 3000 characters are synthesized in parts, which SSML is split into at its paragraphs, sentences and breaks",
+        )?;
+        let text_path = Text::new(
+            "Please specify the path of the text file for which you would like audio generation\n",
+        )
+        .with_placeholder(
+            "The format of the text content is determined by the preceding selections\n",
+        )
+        .with_formatter(&|str| format!(".....{str}.....\n"))
+        .prompt()?;
+        let output_format = pick_value(
+            "Please select the format of the audio file\n",
+            &AUDIO_FORMATS,
+            "'pcm' is raw 16-bit mono audio without a header",
+        )?;
+        let lexicon_names = prompt_lexicon_names(clients).await?;
+        let speech_mark_types = prompt_speech_mark_types(
+            "Sentence and word marks are also written as WebVTT and SRT subtitles; leave it empty to write none",
+        )?;
+        match text_path.is_empty() {
+            false => Ok(Some(Self {
+                engine_name,
+                voice_id,
+                language_code: Some(language_code),
+                text_type,
+                text_path,
+                output_format,
+                output_path: None,
+                lexicon_names,
+                speech_mark_types,
+            })),
+            true => {
+                println!("{}\n", "Fields can't be left empty".red().bold());
+                Ok(None)
+            }
+        }
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<PathBuf> {
+        pls::check_names(&self.lexicon_names)?;
+        speech_marks::check_types(&self.speech_mark_types)?;
+        let text = read_to_string(&self.text_path).with_path(&self.text_path)?;
+        let text = ssml::prepare(&text, &self.text_type, &self.engine_name)?;
+        let parts = if self.text_type == "ssml" {
+            ssml::split(&text, MAX_REQUEST_CHARACTERS, MAX_REQUEST_TOTAL_CHARACTERS)?
+        } else {
+            split_text(&text, MAX_REQUEST_CHARACTERS)
+                .into_iter()
+                .map(|(start, part)| ssml::Part {
+                    text: part.to_string(),
+                    opening: 0,
+                    start,
+                })
+                .collect()
+        };
+        let mut audio = Vec::new();
+        let mut marks = Vec::new();
+        // Where the next part starts within the audio, in milliseconds.
+        let mut length = 0;
+        for part in &parts {
+            let part_audio = clients
+                .polly
+                .synthesize_speech(
+                    &self.engine_name,
+                    &self.voice_id,
+                    self.language_code.as_deref(),
+                    &self.text_type,
+                    &part.text,
+                    &self.output_format,
+                    &self.lexicon_names,
+                    &[],
+                )
+                .await?;
+            if !self.speech_mark_types.is_empty() {
+                let json_lines = clients
+                    .polly
+                    .synthesize_speech(
+                        &self.engine_name,
+                        &self.voice_id,
+                        self.language_code.as_deref(),
+                        &self.text_type,
+                        &part.text,
+                        "json",
+                        &self.lexicon_names,
+                        &self.speech_mark_types,
+                    )
+                    .await?;
+                let mut part_marks = speech_marks::parse(&String::from_utf8_lossy(&json_lines))?;
+                // The marks of a part are timed from its own audio and
+                // placed within its own text, after the tags it's opened with.
+                let part_length =
+                    speech_marks::length_of(&self.output_format, &part_audio, &part_marks);
+                for mark in &mut part_marks {
+                    mark.time += length;
+                    mark.start = mark
+                        .start
+                        .map(|start| start.saturating_sub(part.opening) + part.start);
+                    mark.end = mark
+                        .end
+                        .map(|end| end.saturating_sub(part.opening) + part.start);
+                }
+                marks.extend(part_marks);
+                length += part_length;
+            }
+            // MP3 frames and raw PCM samples play back to back as they are,
+            // and consecutive Ogg streams make up a chained Ogg file.
+            audio.extend(part_audio);
+        }
+        let output_path = match &self.output_path {
+            Some(path) => path.clone(),
+            None => {
+                let extension = match self.output_format.as_str() {
+                    "ogg_vorbis" => "ogg",
+                    format => format,
+                };
+                clients
+                    .run_dir("speech")?
+                    .join(format!("speech.{extension}"))
+            }
+        };
+        write(&output_path, audio).with_path(&output_path)?;
+        if !self.speech_mark_types.is_empty() {
+            let marks_path = output_path.with_extension("marks.jsonl");
+            write(&marks_path, speech_marks::to_json_lines(&marks)?).with_path(&marks_path)?;
+            for path in
+                [marks_path]
+                    .into_iter()
+                    .chain(write_subtitles(&output_path, &marks, length)?)
+            {
+                println!(
+                    "{} '{}'",
+                    "Written".green().bold(),
+                    path.display().to_string().green().bold()
+                );
+            }
+            println!();
+        }
+        if parts.len() > 1 {
+            println!(
+                "{}\n",
+                format!("The text was synthesized in {} parts", parts.len())
+                    .yellow()
+                    .bold()
+            );
+        }
+        Ok(output_path)
+    }
+
+    fn render(&self, output_path: PathBuf) -> Result<()> {
+        println!(
+            "{} '{}'\n",
+            "The speech is written to".green().bold(),
+            output_path.display().to_string().green().bold()
+        );
+        Ok(())
+    }
+}
+
+pub struct GenerateAllVoices {
+    pub engine_name: String,
+    pub language_code: String,
+    pub text_type: String,
+    pub text_path: String,
+    pub path_prefix: String,
+    pub lexicon_names: Vec<String>,
+}
+
+#[async_trait(?Send)]
+impl Operation for GenerateAllVoices {
+    type Output = ();
+
+    async fn gather(clients: &Clients) -> Result<Option<Self>> {
+        let voices = clients.polly.describe_voices().await?;
+        if voices.is_empty() {
+            println!("{}\n", "No voices are available to pick from".red().bold());
+            return Ok(None);
+        }
+        let engine_name = prompt_engine(
+            clients,
+            &voices,
+            "Select the engine name for generating all the voices using this engine\n",
+        )?;
+        let language_code = prompt_language(&voices, &engine_name)?;
+        let voice_counts = voice_choices(&voices, &engine_name, &language_code).len();
+        let text_type = pick_value(
+            "Please provide the text format of the content for which you would like to generate the voices\n",
+            &TEXT_TYPES,
+            "SSML is checked for the engine before it's sent",
+        )?;
+        let placeholder_info = format!(
+            "A total of '{voice_counts}' voices will be generated for the text you provide"
+        );
+        let text_path = Text::new("Please specify the path to the text file\n")
+            .with_placeholder(&placeholder_info)
+            .with_help_message(
+                "Click here https://tinyurl.com/bdf5uhce to download the sample SSML text file",
+            )
+            .with_formatter(&|input| format!("Received Text Path Is: '{input}'"))
+            .prompt()?;
+        let path_prefix = Text::new("Enter the path prefix under which you want to save the content in the output directory\n")
+            .with_placeholder("For example, 'neural/' or 'standard/ \n")
+            .with_formatter(&|input| format!("Received Path Prefix Is: {input}\n"))
+            .with_help_message("The directory is created within a new timestamped directory of the output root on each run")
+            .prompt()?;
+        let lexicon_names = prompt_lexicon_names(clients).await?;
+        match (text_path.is_empty(), path_prefix.is_empty()) {
+            (false, false) => Ok(Some(Self {
+                engine_name,
+                language_code,
+                text_type,
+                text_path,
+                path_prefix,
+                lexicon_names,
+            })),
+            _ => {
+                println!("{}\n", "Fields should not be left empty".red().bold());
+                Ok(None)
+            }
+        }
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<()> {
+        pls::check_names(&self.lexicon_names)?;
+        let voices_dir = clients.run_dir("voices")?.join(&self.path_prefix);
+        create_dir_all(&voices_dir).with_path(&voices_dir)?;
+        let path_prefix = format!("{}/", voices_dir.display());
+        let mut read_data = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&self.text_path)
+            .with_path(&self.text_path)?;
+        let mut text_data = String::new();
+        read_data
+            .read_to_string(&mut text_data)
+            .with_path(&self.text_path)?;
+        let text_data = ssml::prepare(&text_data, &self.text_type, &self.engine_name)?;
+        clients
+            .polly
+            .generate_all_available_voices_in_mp3(
+                &self.text_type,
+                &text_data,
+                &self.language_code,
+                &self.engine_name,
+                &path_prefix,
+                &self.lexicon_names,
+            )
+            .await?;
+        println!(
+            "{} '{}'\n",
+            "The voices are saved in".green().bold(),
+            voices_dir.display().to_string().green().bold()
+        );
+        Ok(())
+    }
+}
+
+/// Synthesizes a sample of the text with every voice of the engine, and
+/// writes an HTML page to compare them on.
+pub struct SampleVoices {
+    pub engine_name: String,
+    /// Every language is sampled when not set.
+    pub language_code: Option<String>,
+    pub text_type: String,
+    pub text_path: String,
+}
+
+#[async_trait(?Send)]
+impl Operation for SampleVoices {
+    type Output = Vec<VoiceSample>;
+
+    async fn gather(clients: &Clients) -> Result<Option<Self>> {
+        let voices = clients.polly.describe_voices().await?;
+        if voices.is_empty() {
+            println!("{}\n", "No voices are available to pick from".red().bold());
+            return Ok(None);
+        }
+        let engine_name = prompt_engine(
+            clients,
+            &voices,
+            "Select the engine whose voices are sampled\n",
+        )?;
+        let mut languages = vec![(String::new(), "Every language".to_string())];
+        languages.extend(language_choices(&voices, &engine_name));
+        let language_code = pick(
+            "Select the language of the voices to sample\n",
+            languages,
+            None,
+            "The voices of other languages read the text with their own accent",
+        )?;
+        let text_type = pick_value(
+            "Please provide the text format of the sample text\n",
+            &TEXT_TYPES,
+            "SSML is checked for the engine before it's sent",
+        )?;
+        let text_path = Text::new("Please specify the path of the sample text file\n")
+            .with_placeholder(
+                "Every voice reads the whole text, which is at most 3000 characters\n",
+            )
+            .with_formatter(&|str| format!(".....{str}.....\n"))
+            .prompt()?;
+        match text_path.is_empty() {
+            false => Ok(Some(Self {
+                engine_name,
+                language_code: (!language_code.is_empty()).then_some(language_code),
+                text_type,
+                text_path,
+            })),
+            true => {
+                println!("{}\n", "The text file can't be left empty".red().bold());
+                Ok(None)
+            }
+        }
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<Vec<VoiceSample>> {
+        let sample_text = read_to_string(&self.text_path).with_path(&self.text_path)?;
+        let text = ssml::prepare(&sample_text, &self.text_type, &self.engine_name)?;
+        // Each voice reads the whole text in a single request.
+        let billed = match self.text_type.as_str() {
+            "ssml" => ssml::billed_characters(&text)?,
+            _ => text.chars().count(),
+        };
+        for (characters, limit) in [
+            (billed, MAX_REQUEST_CHARACTERS),
+            (text.chars().count(), MAX_REQUEST_TOTAL_CHARACTERS),
+        ] {
+            if characters > limit {
+                return Err(Error::TextTooLong { characters, limit });
+            }
+        }
+        let mut voices: Vec<Voice> = clients
+            .polly
+            .describe_voices()
+            .await?
+            .into_iter()
+            .filter(|voice| {
+                supports(voice, &self.engine_name)
+                    && self
+                        .language_code
+                        .as_ref()
+                        .map_or(true, |language_code| &voice.language_code == language_code)
+            })
+            .collect();
+        if voices.is_empty() {
+            println!(
+                "{}\n",
+                format!("There are no '{}' voices to sample", self.engine_name)
+                    .yellow()
+                    .bold()
+            );
+            return Ok(Vec::new());
+        }
+        voices.sort_by(|a, b| {
+            (&a.language_code, &a.voice_name).cmp(&(&b.language_code, &b.voice_name))
+        });
+        let run_dir = clients.run_dir("voice_samples")?;
+        let mut checkpoint = clients.checkpoint(&run_dir)?;
+        let text = &text;
+        let run_dir = &run_dir;
+        let samples = clients
+            .settings
+            .batch
+            .run(
+                "voice(s)",
+                &mut checkpoint,
+                voices,
+                |voice| voice.voice_id.clone(),
+                |voice| async move {
+                    let audio = clients
+                        .polly
+                        .synthesize_speech(
+                            &self.engine_name,
+                            &voice.voice_id,
+                            Some(&voice.language_code),
+                            &self.text_type,
+                            text,
+                            "mp3",
+                            &[],
+                            &[],
+                        )
+                        .await?;
+                    let file = format!("{}.mp3", voice.voice_id);
+                    let audio_path = run_dir.join(&file);
+                    write(&audio_path, &audio).with_path(&audio_path)?;
+                    Ok(VoiceSample {
+                        voice_id: voice.voice_id,
+                        voice_name: voice.voice_name,
+                        gender: voice.gender,
+                        language_code: voice.language_code,
+                        language_name: voice.language_name,
+                        engine: self.engine_name.clone(),
+                        file,
+                        size: audio.len() as u64,
+                    })
+                },
+            )
+            .await?;
+        let page_path = run_dir.join("index.html");
+        write(&page_path, gallery::to_html(&sample_text, &samples)).with_path(&page_path)?;
+        println!(
+            "{} '{}'\n",
+            "Open the gallery of the samples at".green().bold(),
+            page_path.display().to_string().green().bold()
+        );
+        Ok(samples)
+    }
+
+    fn results(&self, samples: &Vec<VoiceSample>) -> Result<Option<Results>> {
+        Results::new("voice_samples", samples).map(Some)
+    }
+}
+
+/// Checks an SSML file for an engine without sending it.
+pub struct ValidateSsml {
+    pub engine_name: String,
+    pub text_path: String,
+}
+
+#[async_trait(?Send)]
+impl Operation for ValidateSsml {
+    /// Whether the file is a fragment that is sent wrapped in `<speak>`.
+    type Output = bool;
+
+    async fn gather(clients: &Clients) -> Result<Option<Self>> {
+        let engines = [Engine::Standard, Engine::Neural]
+            .iter()
+            .map(|engine| (engine.to_string(), engine.to_string()))
+            .collect();
+        let engine_name = pick(
+            "Select the speech generation engine the SSML is meant for\n",
+            engines,
+            clients.profile.polly_engine.as_deref(),
+            "The tags and attributes that only the other engine reads are reported",
+        )?;
+        let text_path = Text::new("Please specify the path to the SSML text file\n")
+            .with_placeholder(
+                "A text without <speak> around it is checked as a part of a document\n",
+            )
+            .with_formatter(&|str| format!(".....{str}.....\n"))
+            .prompt()?;
+        match text_path.is_empty() {
+            false => Ok(Some(Self {
+                engine_name,
+                text_path,
+            })),
+            true => {
+                println!("{}\n", "Fields can't be left empty".red().bold());
+                Ok(None)
+            }
+        }
+    }
+
+    async fn execute(&self, _: &Clients) -> Result<bool> {
+        let text = read_to_string(&self.text_path).with_path(&self.text_path)?;
+        let document = ssml::prepare(&text, "ssml", &self.engine_name)?;
+        Ok(document != text)
+    }
+
+    fn render(&self, wrapped: bool) -> Result<()> {
+        println!(
+            "{}\n",
+            format!(
+                "'{}' is valid SSML for the '{}' engine",
+                self.text_path, self.engine_name
+            )
+            .green()
+            .bold()
+        );
+        if wrapped {
+            println!(
+                "{}\n",
+                "It has no <speak> around it, which is added when it's synthesized"
+                    .yellow()
+                    .bold()
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Writes the subtitles of a speech marks file, such as the one a 'json'
+/// synthesis task stores in the bucket.
+pub struct ConvertSpeechMarks {
+    pub marks_path: String,
+    /// The audio the marks were synthesized with, which the last cue ends
+    /// with; it's estimated from the last mark otherwise.
+    pub audio_path: Option<String>,
+}
+
+#[async_trait(?Send)]
+impl Operation for ConvertSpeechMarks {
+    /// The subtitle files.
+    type Output = Vec<PathBuf>;
+
+    async fn gather(_: &Clients) -> Result<Option<Self>> {
+        let marks_path = Text::new("Please specify the path of the speech marks file\n")
+            .with_placeholder(
+                "The JSON Lines of the sentence or word marks, downloaded from the bucket\n",
+            )
+            .with_formatter(&|str| format!(".....{str}.....\n"))
+            .prompt()?;
+        let audio_path =
+            Text::new("Please specify the path of the audio synthesized with the marks\n")
+                .with_placeholder("An 'mp3', 'ogg' or 'pcm' file\n")
+                .with_formatter(&|str| format!(".....{str}.....\n"))
+                .with_help_message(
+                    "Leave it empty to end the last subtitle a second after its mark",
+                )
+                .prompt()?;
+        match marks_path.is_empty() {
+            false => Ok(Some(Self {
+                marks_path,
+                audio_path: (!audio_path.is_empty()).then_some(audio_path),
+            })),
+            true => {
+                println!(
+                    "{}\n",
+                    "The speech marks file can't be left empty".red().bold()
+                );
+                Ok(None)
+            }
+        }
+    }
+
+    async fn execute(&self, _: &Clients) -> Result<Vec<PathBuf>> {
+        let json_lines = read_to_string(&self.marks_path).with_path(&self.marks_path)?;
+        let marks = speech_marks::parse(&json_lines)?;
+        let audio_length = match &self.audio_path {
+            Some(audio_path) => {
+                let audio = read(audio_path).with_path(audio_path)?;
+                let format = match Path::new(audio_path)
+                    .extension()
+                    .and_then(|extension| extension.to_str())
+                {
+                    Some("ogg") => "ogg_vorbis",
+                    Some(extension) => extension,
+                    None => "",
+                };
+                speech_marks::audio_milliseconds(format, &audio)
+            }
+            None => None,
+        };
+        let length = audio_length.unwrap_or_else(|| speech_marks::estimated_length(&marks));
+        write_subtitles(Path::new(&self.marks_path), &marks, length)
+    }
+
+    fn render(&self, subtitle_paths: Vec<PathBuf>) -> Result<()> {
+        if subtitle_paths.is_empty() {
+            println!(
+                "{}\n",
+                "There are no sentence or word marks to make subtitles of"
+                    .red()
+                    .bold()
+            );
+        }
+        for path in subtitle_paths {
+            println!(
+                "{} '{}'",
+                "Written".green().bold(),
+                path.display().to_string().green().bold()
+            );
+        }
+        println!();
+        Ok(())
+    }
+}
+
+/// Asks for the name of a stored lexicon, offering the stored ones.
+async fn prompt_lexicon_name(clients: &Clients, message: &str) -> Result<String> {
+    let lexicons: Vec<String> = clients
+        .polly
+        .list_lexicons()
+        .await?
+        .into_iter()
+        .map(|lexicon| lexicon.name)
+        .collect();
+    let available_lexicons = format!("Available lexicons:\n{}\n", lexicons.join(" | "));
+    Ok(Text::new(message)
+        .with_placeholder(&available_lexicons)
+        .with_formatter(&|str| format!(".....{str}.....\n"))
+        .prompt()?)
+}
+
+fn print_lexicon(lexicon: &Lexicon) {
+    println!(
+        "Name: {}\nLanguage Code: {}\nAlphabet: {}\nLexemes: {}\nSize: {} characters\n",
+        lexicon.name.green().bold(),
+        lexicon
+            .language_code
+            .as_deref()
+            .unwrap_or_default()
+            .green()
+            .bold(),
+        lexicon
+            .alphabet
+            .as_deref()
+            .unwrap_or_default()
+            .green()
+            .bold(),
+        lexicon.lexemes_count.to_string().green().bold(),
+        lexicon.size.to_string().green().bold()
+    );
+}
+
+/// Checks a local PLS file and stores it in Polly under a name.
+pub struct PutLexicon {
+    pub name: String,
+    pub lexicon_path: String,
+}
+
+#[async_trait(?Send)]
+impl Operation for PutLexicon {
+    type Output = Summary;
+
+    async fn gather(_: &Clients) -> Result<Option<Self>> {
+        let name = Text::new("Name to store the pronunciation lexicon under\n")
+            .with_placeholder("1 to 20 letters and digits, such as 'ProductNames'\n")
+            .with_formatter(&|str| format!(".....{str}.....\n"))
+            .with_help_message("A lexicon with the same name is replaced")
+            .prompt()?;
+        let lexicon_path = Text::new("Please specify the path to the PLS lexicon file\n")
+            .with_placeholder("The lexicon is checked locally before it's stored\n")
+            .with_formatter(&|str| format!(".....{str}.....\n"))
+            .with_help_message("A W3C PLS document with a <lexeme> for every word, giving its <phoneme> or <alias>")
+            .prompt()?;
+        match (name.is_empty(), lexicon_path.is_empty()) {
+            (false, false) => Ok(Some(Self { name, lexicon_path })),
+            _ => {
+                println!("{}\n", "Fields can't be left empty".red().bold());
+                Ok(None)
+            }
+        }
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<Summary> {
+        pls::check_name(&self.name)?;
+        let content = read_to_string(&self.lexicon_path).with_path(&self.lexicon_path)?;
+        let summary = pls::validate(&content)?;
+        clients.polly.put_lexicon(&self.name, &content).await?;
+        Ok(summary)
+    }
+
+    fn render(&self, summary: Summary) -> Result<()> {
+        println!(
+            "{}\n",
+            format!(
+                "The lexicon '{}' with {} lexemes for '{}' ({}) is stored",
+                self.name, summary.lexemes, summary.language_code, summary.alphabet
+            )
+            .green()
+            .bold()
+        );
+        Ok(())
+    }
+}
+
+pub struct ListLexicons;
+
+#[async_trait(?Send)]
+impl Operation for ListLexicons {
+    type Output = Vec<Lexicon>;
+
+    async fn gather(_: &Clients) -> Result<Option<Self>> {
+        Ok(Some(Self))
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<Vec<Lexicon>> {
+        clients.polly.list_lexicons().await
+    }
+
+    fn results(&self, lexicons: &Vec<Lexicon>) -> Result<Option<Results>> {
+        Results::new("lexicons", lexicons).map(Some)
+    }
+
+    fn render(&self, lexicons: Vec<Lexicon>) -> Result<()> {
+        if lexicons.is_empty() {
+            println!(
+                "{}\n",
+                "There are no lexicons stored in this region"
+                    .yellow()
+                    .bold()
+            );
+        }
+        lexicons.iter().for_each(print_lexicon);
+        Ok(())
+    }
+}
+
+/// Downloads a stored lexicon into a local PLS file.
+pub struct GetLexicon {
+    pub name: String,
+    /// Written into the run directory when not set.
+    pub output_path: Option<PathBuf>,
+}
+
+#[async_trait(?Send)]
+impl Operation for GetLexicon {
+    /// The lexicon and the file its document is written to.
+    type Output = (Lexicon, PathBuf);
+
+    async fn gather(clients: &Clients) -> Result<Option<Self>> {
+        let name = prompt_lexicon_name(clients, "Name of the lexicon to download\n").await?;
+        match name.is_empty() {
+            false => Ok(Some(Self {
+                name,
+                output_path: None,
+            })),
+            true => {
+                println!("{}\n", "Lexicon name can't be empty".red().bold());
+                Ok(None)
+            }
+        }
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<(Lexicon, PathBuf)> {
+        // The name is part of the path the lexicon is written to.
+        pls::check_name(&self.name)?;
+        let (lexicon, content) = clients.polly.get_lexicon(&self.name).await?;
+        let output_path = match &self.output_path {
+            Some(path) => path.clone(),
+            None => clients
+                .run_dir("lexicon")?
+                .join(format!("{}.pls", self.name)),
+        };
+        write(&output_path, content).with_path(&output_path)?;
+        Ok((lexicon, output_path))
+    }
+
+    fn results(&self, (lexicon, _): &(Lexicon, PathBuf)) -> Result<Option<Results>> {
+        Results::new("lexicon", std::slice::from_ref(lexicon)).map(Some)
+    }
+
+    fn render(&self, (lexicon, output_path): (Lexicon, PathBuf)) -> Result<()> {
+        print_lexicon(&lexicon);
+        println!(
+            "{} '{}'\n",
+            "The lexicon is written to".green().bold(),
+            output_path.display().to_string().green().bold()
+        );
+        Ok(())
+    }
+}
+
+pub struct DeleteLexicon {
+    pub name: String,
+}
+
+#[async_trait(?Send)]
+impl Operation for DeleteLexicon {
+    type Output = ();
+
+    async fn gather(clients: &Clients) -> Result<Option<Self>> {
+        let name = prompt_lexicon_name(clients, "Name of the lexicon to delete\n").await?;
+        if name.is_empty() {
+            println!("{}\n", "Lexicon name can't be empty".red().bold());
+            return Ok(None);
+        }
+        let confirmed = Confirm::new(&format!(
+            "Are you sure you want to delete the lexicon '{name}'?\n"
+        ))
+        .with_placeholder("Type 'Yes' to delete it, or 'No' to keep it\n")
+        .with_formatter(&|input| format!("Received Response Is: {input}\n"))
+        .with_help_message("The speech synthesized afterwards no longer applies it")
+        .with_default(false)
+        .prompt()?;
+        match confirmed {
+            true => Ok(Some(Self { name })),
+            false => Ok(None),
+        }
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<()> {
+        pls::check_name(&self.name)?;
+        clients.polly.delete_lexicon(&self.name).await?;
+        println!(
+            "{}\n",
+            format!("The lexicon '{}' is deleted", self.name)
+                .green()
+                .bold()
+        );
+        Ok(())
+    }
+}
+
+pub struct GetSpeechSynthesisResult {
+    pub task_id: String,
+}
+
+#[async_trait(?Send)]
+impl Operation for GetSpeechSynthesisResult {
+    type Output = Option<SynthesisTask>;
+
+    async fn gather(_: &Clients) -> Result<Option<Self>> {
+        let task_id = pick_job(
+            JobKind::SpeechSynthesis,
+            Text::new("To obtain speech results, enter the task ID\n")
+                .with_placeholder("Task ID was generated when calling the StartSpeechSynthesisTask REST API or\nwhen you chose the 'Start the speech synthesis task' option\n")
+                .with_formatter(&|str| format!(".....{str}.....\n")),
+        )?;
+        match task_id.is_empty() {
+            false => Ok(Some(Self { task_id })),
+            true => {
+                println!("{}\n", "Task ID can't be empty".red().bold());
+                Ok(None)
+            }
+        }
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<Option<SynthesisTask>> {
+        let task = clients
+            .fetch_job(
+                &self.task_id,
+                &["scheduled", "inProgress"],
+                |task: &Option<SynthesisTask>| {
+                    task.as_ref().and_then(|task| task.status.as_deref())
+                },
+                || clients.polly.get_speech_synthesis_result(&self.task_id),
+            )
+            .await?;
+        if let Some(task) = &task {
+            Ledger::update_status(
+                JobKind::SpeechSynthesis,
+                &self.task_id,
+                task.status.as_deref(),
+            )?;
+            if let (Some("completed"), Some(uri)) = (task.status.as_deref(), &task.output_uri) {
+                let uri_path = clients.run_dir("synthesis_result")?.join("audio_uri.txt");
+                let uri_data = format!("URL for the synthesized audio: {uri}\n");
+                write(&uri_path, uri_data).with_path(&uri_path)?;
+                println!(
+                    "{} '{}'\n",
+                    "The URL is written to".green().bold(),
+                    uri_path.display().to_string().green().bold()
+                );
+            }
+        }
+        Ok(task)
+    }
+
+    fn results(&self, task: &Option<SynthesisTask>) -> Result<Option<Results>> {
+        Results::new("synthesis_task", task.as_slice()).map(Some)
+    }
+
+    fn render(&self, output: Option<SynthesisTask>) -> Result<()> {
+        let Some(task) = output else {
+            return Ok(());
+        };
+        match task.status.as_deref().unwrap_or("No Status Is Available") {
+            "scheduled" => {
+                println!("{}\n","Current task status is 'scheduled,' so please try again after some time.\nThe result is only available when the status is 'completed'".yellow().bold());
+            }
+            "inProgress" => {
+                println!("{}\n","Current task status is 'in progress,' so please try again after some time.\nThe result is only available when the status is 'completed'".yellow().bold());
+            }
+            "completed" => {
+                if let (
+                    Some(status),
+                    Some(engine),
+                    Some(uri),
+                    Some(format),
+                    Some(text),
+                    Some(voice),
+                    Some(code),
+                ) = (
+                    &task.status,
+                    &task.engine,
+                    &task.output_uri,
+                    &task.output_format,
+                    &task.text_type,
+                    &task.voice_id,
+                    &task.language_code,
+                ) {
+                    let colored_status = status.green().bold();
+                    let colored_engine = engine.green().bold();
+                    let colored_uri = uri.green().bold();
+                    let colored_format = format.green().bold();
+                    let colored_type = text.green().bold();
+                    let colored_voiceid = voice.green().bold();
+                    let colored_code = code.green().bold();
+                    println!("Task Status: {colored_status}");
+                    println!("Engine Name: {colored_engine}");
+                    println!("Output Format of the synthesized audio: {colored_format}");
+                    println!("Voice ID of the synthesized audio: {colored_voiceid}");
+                    println!("Text type of synthesized audio: {colored_type}");
+                    println!("Language Code for the synthesized audio: {colored_code}");
+                    println!("URL for the synthesized audio: {colored_uri}\n");
+                    println!("{}","The bucket can't be accessed right away; you have to make it public or only accessible in the web console".yellow().bold());
+                    println!("{}\n","Alternatively, you can make the object accessible using the 'Modify Object Visibility' option in the S3 menu or download them using the 'Download Object from bucket' option".yellow().bold());
+                }
+            }
+            "failed" => {
+                println!("{}", "The task has failed".red().bold());
+                println!("{}", "Reason, if any".yellow().bold());
+                println!(
+                    "{}\n",
+                    task.status_reason
+                        .as_deref()
+                        .unwrap_or("No reason is available")
+                );
+            }
+            _ => println!("Shoudn't reach"),
+        }
+        Ok(())
+    }
+}
+
+pub struct ListSpeechSynthesisTasks;
+
+#[async_trait(?Send)]
+impl Operation for ListSpeechSynthesisTasks {
+    type Output = Vec<SynthesisTask>;
+
+    async fn gather(_: &Clients) -> Result<Option<Self>> {
+        Ok(Some(Self))
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<Vec<SynthesisTask>> {
+        clients.polly.list_speech_synthesis_tasks().await
+    }
+
+    fn render(&self, tasks: Vec<SynthesisTask>) -> Result<()> {
+        if tasks.is_empty() {
+            println!(
+                "{}\n",
+                "No speech synthesis tasks were found".yellow().bold()
+            );
+        }
+        for task in tasks {
+            println!(
+                "Task ID: {}\nTask Status: {}\n",
+                task.task_id.unwrap_or_default().green().bold(),
+                task.status.unwrap_or_default().green().bold()
+            );
+        }
+        Ok(())
+    }
+}
+
+pub struct DescribeVoices;
+
+#[async_trait(?Send)]
+impl Operation for DescribeVoices {
+    type Output = Vec<Voice>;
+
+    async fn gather(_: &Clients) -> Result<Option<Self>> {
+        Ok(Some(Self))
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<Vec<Voice>> {
+        let voices = clients.polly.describe_voices().await?;
+        let run_dir = clients.run_dir("voices_info")?;
+        let info_path = run_dir.join("voices_info.txt");
+        let mut file = OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .read(true)
+            .write(true)
+            .open(&info_path)
+            .with_path(&info_path)?;
+        let headers = vec![
+            "Gender of Voice",
+            "Voice ID",
+            "Language Code",
+            "Language Name",
+            "Voice Name",
+            "Supported Engine",
+        ];
+        let mut rows = Vec::new();
+        for voice in &voices {
+            let engines = voice.supported_engines.concat();
+            let data = format!("Gender:           {}\nVoiceId:          {}\nLanguageCode:     {}\nLanguage Name:    {}\nVoice Name:       {}\nSupported Engine: {}\n\n",
+                voice.gender,
+                voice.voice_id,
+                voice.language_code,
+                voice.language_name,
+                voice.voice_name,
+                engines
+            );
+            file.write_all(data.as_bytes()).with_path(&info_path)?;
+            rows.push(vec![
+                voice.gender.clone(),
+                voice.voice_id.clone(),
+                voice.language_code.clone(),
+                voice.language_name.clone(),
+                voice.voice_name.clone(),
+                engines,
+            ]);
+        }
+        pdf::write_table(
+            &run_dir.join("voices_info.pdf"),
+            "Voices of Amazon Polly",
+            &headers,
+            &rows,
+        )?;
+        let colored_dir = run_dir.display().to_string().green().bold();
+        let msg = format!("There is a lot more information available, so it only displays the first three pieces of voice information.\n\nAll the voice information is saved to {colored_dir} as 'voices_info.txt' instead of cluttering the command-line window");
+        println!("{}\n", msg);
+        Ok(voices)
+    }
+
+    fn results(&self, voices: &Vec<Voice>) -> Result<Option<Results>> {
+        Results::new("voices", voices).map(Some)
+    }
+
+    fn render(&self, voices: Vec<Voice>) -> Result<()> {
+        voices.iter().take(3).for_each(|voice| {
+            println!(
+                "Gender: {}\nVoiceId: {}\nLanguageCode: {}\nLanguage Name: {}\nVoice Name: {}",
+                voice.gender.green().bold(),
+                voice.voice_id.green().bold(),
+                voice.language_code.green().bold(),
+                voice.language_name.green().bold(),
+                voice.voice_name.green().bold()
+            );
+            voice.supported_engines.iter().for_each(|engine| {
+                println!("Supported Engine: {}\n", engine.green().bold());
+            });
+        });
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_text_within_the_limit_is_one_part() {
+        assert_eq!(split_text("  Hello there.\n", 20), [(2, "Hello there.")]);
+    }
+
+    #[test]
+    fn a_long_text_is_split_at_the_end_of_its_sentences() {
+        let text = "One two. Three four! Five six?";
+
+        assert_eq!(
+            split_text(text, 12),
+            [(0, "One two."), (9, "Three four!"), (21, "Five six?")]
+        );
+    }
+
+    #[test]
+    fn a_sentence_too_long_for_a_part_is_split_at_a_space_or_anywhere() {
+        assert_eq!(split_text("héllo wörld", 8), [(0, "héllo"), (7, "wörld")]);
+        assert_eq!(
+            split_text("abcdefgh", 3),
+            [(0, "abc"), (3, "def"), (6, "gh")]
+        );
+    }
+
+    #[test]
+    fn the_offsets_point_at_the_parts_within_the_text() {
+        let text = "A first sentence.   A second one.\n\nAnd a third, the longest of all.";
+
+        for (offset, part) in split_text(text, 20) {
+            assert!(part.chars().count() <= 20, "{part}");
+            assert_eq!(&text[offset..offset + part.len()], part);
+        }
+    }
+
+    fn voice(voice_id: &str, language_code: &str, engines: &[&str]) -> Voice {
+        Voice {
+            gender: "Female".to_string(),
+            voice_id: voice_id.to_string(),
+            language_code: language_code.to_string(),
+            language_name: format!("{language_code} name"),
+            voice_name: voice_id.to_string(),
+            supported_engines: engines.iter().map(|engine| engine.to_string()).collect(),
+        }
+    }
+
+    fn values(choices: Vec<(String, String)>) -> Vec<String> {
+        choices.into_iter().map(|(value, _)| value).collect()
+    }
+
+    fn voices() -> Vec<Voice> {
+        vec![
+            voice("Matthew", "en-US", &["standard", "neural"]),
+            voice("Lea", "fr-FR", &["standard"]),
+            voice("Joanna", "en-US", &["neural"]),
+            voice("Ruth", "en-US", &["long-form"]),
+        ]
+    }
+
+    #[test]
+    fn the_engines_are_the_ones_of_the_voices_listed_once() {
+        assert_eq!(
+            values(engine_choices(&voices())),
+            ["long-form", "neural", "standard"]
+        );
+    }
+
+    #[test]
+    fn only_the_languages_spoken_with_the_engine_are_listed() {
+        assert_eq!(values(language_choices(&voices(), "neural")), ["en-US"]);
+        assert_eq!(
+            language_choices(&voices(), "standard"),
+            [
+                ("en-US".to_string(), "en-US (en-US name)".to_string()),
+                ("fr-FR".to_string(), "fr-FR (fr-FR name)".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn only_the_voices_of_the_engine_speaking_the_language_are_listed() {
+        assert_eq!(
+            values(voice_choices(&voices(), "neural", "en-US")),
+            ["Joanna", "Matthew"]
+        );
+        assert_eq!(
+            voice_choices(&voices(), "standard", "fr-FR"),
+            [("Lea".to_string(), "Lea (Female)".to_string())]
+        );
+        assert!(voice_choices(&voices(), "long-form", "fr-FR").is_empty());
+    }
+
+    #[test]
+    fn the_default_is_picked_only_when_it_is_listed() {
+        let choices = voice_choices(&voices(), "neural", "en-US");
+
+        assert_eq!(starting_cursor(&choices, Some("Matthew")), 1);
+        assert_eq!(starting_cursor(&choices, Some("Lea")), 0);
+        assert_eq!(starting_cursor(&choices, None), 0);
+    }
+}
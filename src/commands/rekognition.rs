@@ -0,0 +1,2483 @@
+use async_trait::async_trait;
+use clap::ValueEnum;
+use colored::Colorize;
+use image::imageops::{replace, FilterType};
+use image::{self, DynamicImage, GenericImageView, Rgba};
+use image_compressor::FolderCompressor;
+use imageproc::drawing::{
+    draw_filled_circle_mut, draw_filled_rect_mut, draw_hollow_rect_mut, draw_line_segment_mut,
+    draw_text_mut, text_size,
+};
+use imageproc::rect::Rect;
+use inquire::{Confirm, Select, Text};
+use rusttype::{Font, Scale};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::{create_dir, create_dir_all, read_dir, remove_dir, rename, write, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use super::{jobs::pick_job, run_with, Clients, Operation, ProfileDefault};
+use crate::batch::blocking;
+use crate::error::{Error, Result, WithPath};
+use crate::keys::{key_for, local_name, LocalNames};
+use crate::ledger::{JobKind, Ledger};
+use crate::output::{ImageRecord, Results};
+use crate::pdf::{self, Section};
+use crate::preprocess::{ConvertTo, Preprocess};
+use crate::services::{
+    BoundingBox, CelebrityFace, DetectionJob, FaceFlag, FaceSummary, TextSummary,
+};
+use crate::walk::Walk;
+use crate::workspace::scratch_dir;
+
+/// Where the images of an operation are read from.
+#[derive(Clone)]
+pub enum Source {
+    /// A local file or directory.
+    Local(String),
+    /// A key, or a path prefix, within a bucket.
+    S3 { bucket: String, key: String },
+}
+
+impl Source {
+    /// How the image is named in the structured results.
+    fn image_name(&self) -> String {
+        match self {
+            Self::Local(path) => path.clone(),
+            Self::S3 { bucket, key } => format!("{bucket}/{key}"),
+        }
+    }
+
+    /// The file name of the image, without the directories or the path prefix.
+    fn file_name(&self) -> String {
+        match self {
+            Self::Local(path) => local_name(path),
+            Self::S3 { key, .. } => local_name(key),
+        }
+    }
+
+    /// The path of the image relative to the directory or the path prefix it
+    /// was found under, such as 'faces/a.jpg', or its file name when `root` is
+    /// the image itself.
+    fn relative_to(&self, root: &Self) -> String {
+        let relative = match (self, root) {
+            (Self::Local(path), Self::Local(root)) => Path::new(path)
+                .strip_prefix(root)
+                .map(|relative| relative.to_string_lossy().replace('\\', "/"))
+                .unwrap_or_default(),
+            (Self::S3 { key, .. }, Self::S3 { key: prefix, .. }) => key
+                .strip_prefix(prefix.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            _ => String::new(),
+        };
+        match relative.is_empty() {
+            true => self.file_name(),
+            false => relative,
+        }
+    }
+
+    /// The local path, bucket and key arguments of the service calls.
+    fn location(&self) -> (Option<&str>, Option<&str>, Option<&str>) {
+        match self {
+            Self::Local(path) => (Some(path), None, None),
+            Self::S3 { bucket, key } => (None, Some(bucket), Some(key)),
+        }
+    }
+
+    /// The path of the image on the local disk, downloading it into `dir` as
+    /// `name` when it is stored in S3.
+    async fn local_copy(&self, clients: &Clients, dir: &Path, name: &str) -> Result<String> {
+        match self {
+            Self::Local(path) => Ok(path.clone()),
+            Self::S3 { bucket, key } => {
+                // Every object is downloaded into a directory of its own first,
+                // so that the keys with the same file name under different
+                // prefixes, downloaded at the same time, don't overwrite each other.
+                let object_dir = tempfile::Builder::new().tempdir_in(dir).with_path(dir)?;
+                let local_path_prefix = format!("{}/", object_dir.path().display());
+                clients
+                    .s3
+                    .download_content_from_bucket(bucket, key, &local_path_prefix)
+                    .await?;
+                let downloaded = object_dir.path().join(local_name(key));
+                let local_path = dir.join(name);
+                rename(&downloaded, &local_path).with_path(&downloaded)?;
+                Ok(local_path.display().to_string())
+            }
+        }
+    }
+
+    /// The images of a local directory or of a path prefix within a bucket.
+    async fn images(&self, clients: &Clients) -> Result<Vec<Self>> {
+        match self {
+            Self::Local(path) => Self::local_images(path, &clients.settings.walk),
+            Self::S3 { bucket, key } => Ok(clients
+                .s3
+                .list_objects_given_prefix(bucket, key)
+                .await?
+                .into_iter()
+                .map(|key| Self::S3 {
+                    bucket: bucket.clone(),
+                    key,
+                })
+                .collect()),
+        }
+    }
+
+    /// Pairs every image with the name its copies are written locally as,
+    /// which stays unique when images of different directories or path
+    /// prefixes share a file name.
+    fn with_local_names(images: Vec<Self>) -> Vec<(Self, String)> {
+        let mut local_names = LocalNames::default();
+        images
+            .into_iter()
+            .map(|image| {
+                let local_name = local_names.unique(&image.file_name());
+                (image, local_name)
+            })
+            .collect()
+    }
+
+    /// A local image, or the JPG and PNG images of a local directory that the
+    /// walk picks.
+    pub fn local_images(path: &str, walk: &Walk) -> Result<Vec<Self>> {
+        if !Path::new(path).is_dir() {
+            return Ok(vec![Self::Local(path.to_string())]);
+        }
+        Ok(walk
+            .files(Path::new(path), &["jpg", "jpeg", "png"])?
+            .into_iter()
+            .map(|file| Self::Local(file.path.display().to_string()))
+            .collect())
+    }
+}
+
+/// Asks whether the images are read from the local disk, and if so returns the
+/// local image or directory, which is sent as bytes without staging it in S3.
+fn prompt_local_path(clients: &Clients, what: &str) -> Result<Option<String>> {
+    let local = Confirm::new(&format!(
+        "Either you want to provide the local or S3 location for the {what}\n"
+    ))
+    .with_placeholder("Type 'Yes' to load from a local image or directory, or type 'No' to provide the S3 location\n")
+    .with_formatter(&|input| format!("Received Response Is: {input}\n"))
+    .with_help_message("Local images are sent directly to Rekognition and are never uploaded to a bucket")
+    .prompt()?;
+    if !local {
+        return Ok(None);
+    }
+    let path = Text::new("Please provide the path to the image, or to a directory of images, without quotation marks\n")
+        .with_placeholder("The images should be in '.JPG' or '.PNG' format; no other formats are supported.\n")
+        .with_formatter(&|input| format!("Received Path Is: {input}\n"))
+        .with_profile_default(&clients.profile.local_prefix)
+        .prompt()?;
+    Ok(Some(path))
+}
+
+/// The images written besides the recognized names.
+#[derive(Clone, Copy, Default)]
+pub struct CelebrityVisuals {
+    /// Copies of the images with a box and the name of every face drawn on them.
+    pub annotate: bool,
+    /// An image of every face, listed in an 'index.json' with the celebrity it shows.
+    pub crop_faces: bool,
+}
+
+impl CelebrityVisuals {
+    fn prompt() -> Result<Self> {
+        let annotate = Confirm::new("Do you also want copies of the images with the recognized names drawn on them?\n")
+            .with_placeholder("Type 'Yes' to box every face with the celebrity name, or 'Unrecognized', or 'No' to skip it\n")
+            .with_formatter(&|input| format!("Received Response Is: {input}\n"))
+            .with_default(false)
+            .prompt()?;
+        let crop_faces = Confirm::new("Do you also want every face saved as an image of its own?\n")
+            .with_placeholder("Type 'Yes' to crop the faces next to a JSON index of who they are, or 'No' to skip it\n")
+            .with_formatter(&|input| format!("Received Response Is: {input}\n"))
+            .with_default(false)
+            .prompt()?;
+        println!();
+        Ok(Self {
+            annotate,
+            crop_faces,
+        })
+    }
+
+    fn is_wanted(&self) -> bool {
+        self.annotate || self.crop_faces
+    }
+
+    /// Creates the directories of the wanted images within the run directory.
+    fn writer(&self, run_dir: &Path) -> Result<CelebrityImageWriter> {
+        let annotated_dir = run_dir.join("celebrity_images");
+        let crops_dir = run_dir.join("celebrity_faces");
+        for (wanted, dir) in [
+            (self.annotate, &annotated_dir),
+            (self.crop_faces, &crops_dir),
+        ] {
+            if wanted {
+                create_dir_all(dir).with_path(dir)?;
+            }
+        }
+        Ok(CelebrityImageWriter {
+            annotated_dir: self.annotate.then_some(annotated_dir),
+            crops_dir: self.crop_faces.then_some(crops_dir),
+            index: Vec::new(),
+            local_names: LocalNames::default(),
+            font: Font::try_from_bytes(include_bytes!("../assets/font.ttf"))
+                .expect("Error Getting Font Bytes"),
+        })
+    }
+}
+
+/// A cropped face, as listed in the 'index.json' next to the crops.
+#[derive(Serialize)]
+struct CelebrityCrop {
+    image: String,
+    /// The file name of the crop.
+    crop: String,
+    #[serde(flatten)]
+    face: CelebrityFace,
+}
+
+/// Writes the annotated copies and the face crops of the images, one image at a time.
+struct CelebrityImageWriter {
+    annotated_dir: Option<PathBuf>,
+    crops_dir: Option<PathBuf>,
+    index: Vec<CelebrityCrop>,
+    local_names: LocalNames,
+    font: Font<'static>,
+}
+
+impl CelebrityImageWriter {
+    /// Draws on and crops the local copy of an image the faces were recognized in.
+    fn add(&mut self, source: &Source, image_path: &Path, faces: &[CelebrityFace]) -> Result<()> {
+        if self.annotated_dir.is_none() && self.crops_dir.is_none() {
+            return Ok(());
+        }
+        let mut image = image::open(image_path).with_path(image_path)?;
+        let file_name = self.local_names.unique(&source.file_name());
+        if let Some(crops_dir) = &self.crops_dir {
+            let stem = Path::new(&file_name)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_else(|| file_name.clone());
+            for (index, face) in faces.iter().enumerate() {
+                let Some((left, top, width, height)) = face
+                    .bounding_box
+                    .and_then(|bounding_box| crop_area(&bounding_box, image.dimensions()))
+                else {
+                    continue;
+                };
+                let crop = format!("{stem}_face{}.png", index + 1);
+                let crop_path = crops_dir.join(&crop);
+                image
+                    .crop_imm(left, top, width, height)
+                    .save(&crop_path)
+                    .with_path(&crop_path)?;
+                self.index.push(CelebrityCrop {
+                    image: source.image_name(),
+                    crop,
+                    face: face.clone(),
+                });
+            }
+        }
+        if let Some(annotated_dir) = &self.annotated_dir {
+            for face in faces {
+                let Some(bounding_box) = face.bounding_box else {
+                    continue;
+                };
+                let (color, labels) = match (&face.name, face.match_confidence) {
+                    (Some(name), Some(confidence)) => (
+                        Rgba([0, 200, 0, 255]),
+                        vec![name.clone(), format!("Match: {confidence:.1}")],
+                    ),
+                    (Some(name), None) => (Rgba([0, 200, 0, 255]), vec![name.clone()]),
+                    (None, _) => (Rgba([255, 190, 0, 255]), vec!["Unrecognized".to_string()]),
+                };
+                let (rect, thickness) = draw_face_box(&mut image, &bounding_box, color);
+                draw_box_labels(&mut image, rect, thickness, &labels, color, &self.font);
+            }
+            let annotated_path = annotated_dir.join(&file_name);
+            image.save(&annotated_path).with_path(&annotated_path)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the index of the crops and tells where the images are.
+    fn finish(self) -> Result<()> {
+        if let Some(annotated_dir) = &self.annotated_dir {
+            println!(
+                "{} '{}'\n",
+                "The images with the recognized names drawn on them are written to"
+                    .green()
+                    .bold(),
+                annotated_dir.display().to_string().green().bold()
+            );
+        }
+        if let Some(crops_dir) = &self.crops_dir {
+            let index_path = crops_dir.join("index.json");
+            let json_data = serde_json::to_string_pretty(&self.index).with_path(&index_path)?;
+            write(&index_path, json_data).with_path(&index_path)?;
+            println!(
+                "{} '{}'\n",
+                "The cropped faces and their index are written to"
+                    .green()
+                    .bold(),
+                crops_dir.display().to_string().green().bold()
+            );
+        }
+        Ok(())
+    }
+}
+
+/// The part of the image within a box, in pixels as left, top, width and
+/// height, or `None` when the box lies outside of the image.
+fn crop_area(
+    bounding_box: &BoundingBox,
+    (image_width, image_height): (u32, u32),
+) -> Option<(u32, u32, u32, u32)> {
+    let to_pixels = |ratio: f32, size: u32| (ratio * size as f32).clamp(0.0, size as f32) as u32;
+    let left = to_pixels(bounding_box.left, image_width);
+    let top = to_pixels(bounding_box.top, image_height);
+    let right = to_pixels(bounding_box.left + bounding_box.width, image_width);
+    let bottom = to_pixels(bounding_box.top + bounding_box.height, image_height);
+    (right > left && bottom > top).then(|| (left, top, right - left, bottom - top))
+}
+
+pub struct RecognizeCelebrity {
+    pub source: Source,
+    pub visuals: CelebrityVisuals,
+}
+
+#[async_trait(?Send)]
+impl Operation for RecognizeCelebrity {
+    type Output = Vec<CelebrityFace>;
+
+    async fn gather(clients: &Clients) -> Result<Option<Self>> {
+        let Some(source) = Self::gather_source(clients).await? else {
+            return Ok(None);
+        };
+        let visuals = CelebrityVisuals::prompt()?;
+        Ok(Some(Self { source, visuals }))
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<Vec<CelebrityFace>> {
+        let (local_path, bucket_name, key) = self.source.location();
+        let faces = clients
+            .rekognition
+            .recognize_celebrities(local_path, bucket_name, key)
+            .await?;
+        if self.visuals.is_wanted() {
+            let mut writer = self
+                .visuals
+                .writer(&clients.run_dir("celebrity_visuals")?)?;
+            // The downloaded image is only needed to draw on and to crop.
+            let read_images = scratch_dir()?;
+            let image_path = self
+                .source
+                .local_copy(clients, read_images.path(), &self.source.file_name())
+                .await?;
+            writer.add(&self.source, Path::new(&image_path), &faces)?;
+            writer.finish()?;
+        }
+        Ok(faces)
+    }
+
+    fn results(&self, faces: &Vec<CelebrityFace>) -> Result<Option<Results>> {
+        let image = self.source.image_name();
+        let records: Vec<ImageRecord<&CelebrityFace>> = faces
+            .iter()
+            .map(|face| ImageRecord {
+                image: image.clone(),
+                result: face,
+            })
+            .collect();
+        Results::new("celebrities", &records).map(Some)
+    }
+
+    fn render(&self, faces: Vec<CelebrityFace>) -> Result<()> {
+        if faces.is_empty() {
+            println!("{}\n", "No faces were found in the image".yellow().bold());
+            return Ok(());
+        }
+        for face in faces {
+            match (&face.name, face.match_confidence) {
+                (Some(name), Some(confidence)) => println!(
+                    "Celebrity: {} with a match confidence of {}",
+                    name.green().bold(),
+                    confidence.to_string().green().bold()
+                ),
+                (Some(name), None) => println!("Celebrity: {}", name.green().bold()),
+                (None, _) => println!("{}", "Unrecognized face".yellow().bold()),
+            }
+            for url in &face.urls {
+                println!("More information: {}", url.green().bold());
+            }
+            if let Some(bounding_box) = face.bounding_box {
+                println!(
+                    "Bounding Box: Width: {}, Height: {}, Left: {}, Top: {}",
+                    bounding_box.width, bounding_box.height, bounding_box.left, bounding_box.top
+                );
+            }
+            println!();
+        }
+        Ok(())
+    }
+}
+
+impl RecognizeCelebrity {
+    /// Asks where the celebrity image is read from.
+    async fn gather_source(clients: &Clients) -> Result<Option<Source>> {
+        let local_or_s3 = Confirm::new(
+            "Either you want to provide the local or S3 location for the celebrity image\n",
+        )
+        .with_placeholder("Type 'Yes' to load from the local location, or type 'No' to provide the S3 object location\n")
+        .with_formatter(&|input| format!("Received Response Is: {input}\n"))
+        .with_help_message("Only the celebrity image should be used, and the results are based on AWS APIs")
+        .prompt()?;
+        match local_or_s3 {
+            true => {
+                let celebrity_image_path = Text::new(
+                    "Please provide the path to the celebrity image or paste it here without quotation marks\n",
+                )
+                .with_placeholder("The image should be in '.JPG' or '.PNG' format; no other formats are supported.\n")
+                .with_formatter(&|input| format!("Received Path Is: {input}\n"))
+                .prompt()?;
+                Ok(Some(Source::Local(celebrity_image_path)))
+            }
+            false => {
+                let get_buckets = clients.s3.get_buckets().await?;
+                let available_buckets =
+                    format!("Available buckets in your account:\n{:#?}\n", get_buckets);
+                let bucket_name = Text::new(
+                    "Please enter the bucket name where the celebrity images are stored\n",
+                )
+                .with_placeholder(&available_buckets)
+                .with_formatter(&|input| format!("Received Path Is: {input}\n"))
+                .with_help_message("Ensure that the bucket and the region are the same as where you are making the request")
+                .with_profile_default(&clients.profile.bucket)
+                .prompt()?;
+                if bucket_name.is_empty() {
+                    println!("{}\n", "Bucket name can't be empty".red().bold());
+                    return Ok(None);
+                }
+                let get_objects = clients.s3.retrieve_keys_in_a_bucket(&bucket_name).await?;
+                let available_objects = format!(
+                    "Available keys and path prefix in {bucket_name}\n{:#?}\n",
+                    get_objects
+                );
+                let bucket_key = Text::new(
+                    "Please provide the key path for the actual celebrity image\n",
+                )
+                .with_placeholder(&available_objects)
+                .with_help_message(
+                    "For example: 'celebrityimages/ar rahman.jpg' or 'robert downey jr.png'",
+                )
+                .with_formatter(&|input| format!("Received Bucket Key: {input}\n"))
+                .prompt()?;
+                match bucket_key.is_empty() {
+                    false => Ok(Some(Source::S3 {
+                        bucket: bucket_name,
+                        key: bucket_key,
+                    })),
+                    true => {
+                        println!("{}\n", "Bucket Key/object can't empty".red().bold());
+                        Ok(None)
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Asks which preprocessing steps the images go through before the upload,
+/// starting from `defaults`.
+fn prompt_preprocess(defaults: Preprocess) -> Result<Preprocess> {
+    let max_dimension = Text::new("Enter the largest width or height, in pixels, the images may have\n")
+        .with_placeholder("Larger images are scaled down keeping their aspect ratio; leave it empty to keep their size\n")
+        .with_formatter(&|input| format!("Received Maximum Dimension: {input}\n"))
+        .with_default(&defaults.max_dimension.map(|max| max.to_string()).unwrap_or_default())
+        .prompt()?;
+    let max_dimension = match max_dimension.trim() {
+        "" => None,
+        max_dimension => match max_dimension.parse::<u32>() {
+            Ok(max_dimension) if max_dimension > 0 => Some(max_dimension),
+            _ => {
+                println!("{}\n", "The maximum dimension should be a positive number of pixels, so the size is kept".yellow().bold());
+                None
+            }
+        },
+    };
+    let auto_rotate = Confirm::new(
+        "Do you want the images turned upright according to their EXIF orientation?\n",
+    )
+    .with_placeholder(
+        "Type 'Yes' to rotate the photos taken sideways, or 'No' to keep them as they are\n",
+    )
+    .with_formatter(&|input| format!("Received Response Is: {input}\n"))
+    .with_default(defaults.auto_rotate)
+    .prompt()?;
+    let formats = vec!["Keep the format\n", "JPEG\n", "PNG\n"];
+    let convert_to = match Select::new("Select the format the images are uploaded in\n", formats)
+        .with_help_message("Formats other than JPEG and PNG are always converted to PNG, since Rekognition reads neither")
+        .prompt()?
+    {
+        "JPEG\n" => Some(ConvertTo::Jpeg),
+        "PNG\n" => Some(ConvertTo::Png),
+        _ => None,
+    };
+    let quality = match convert_to {
+        Some(ConvertTo::Png) => defaults.quality,
+        _ => {
+            let quality = Text::new("Enter the JPEG quality, from 1 to 100\n")
+                .with_formatter(&|input| format!("Received Quality: {input}\n"))
+                .with_default(&defaults.quality.to_string())
+                .prompt()?;
+            match quality.trim().parse::<u8>() {
+                Ok(quality) if (1..=100).contains(&quality) => quality,
+                _ => {
+                    println!(
+                        "{}\n",
+                        format!(
+                            "The quality should be from 1 to 100, so {} is used",
+                            defaults.quality
+                        )
+                        .yellow()
+                        .bold()
+                    );
+                    defaults.quality
+                }
+            }
+        }
+    };
+    println!();
+    Ok(Preprocess {
+        max_dimension,
+        auto_rotate,
+        convert_to,
+        quality,
+        ..defaults
+    })
+}
+
+/// Preprocesses the images of a local directory that the walk picks and
+/// uploads them under the path prefix, followed by their path relative to the
+/// directory, skipping the files that aren't images.
+async fn upload_images(
+    clients: &Clients,
+    local_dir: &str,
+    bucket_name: &str,
+    bucket_path_prefix: &str,
+    preprocess: &Preprocess,
+    run_name: &str,
+) -> Result<()> {
+    // The run directory only keeps the checkpoint of the uploads.
+    let mut checkpoint = clients.checkpoint(&clients.run_dir(run_name)?)?;
+    // The processed copies are only kept until they are uploaded.
+    let processed_dir = scratch_dir()?;
+    // Every file is picked, as the ones that aren't images are skipped by the
+    // preprocessing anyway.
+    let processed_root = processed_dir.path();
+    let files = clients.settings.walk.files(Path::new(local_dir), &[])?;
+    let uploaded = clients
+        .settings
+        .batch
+        .run_or_skip(
+            "file(s)",
+            &mut checkpoint,
+            files,
+            |file| file.relative.clone(),
+            |file| async move {
+                // The copies keep the subdirectories, so that images of the
+                // same name in different directories don't overwrite each other.
+                let out_dir = processed_root.join(file.relative_dir());
+                let (path, preprocess) = (file.path.clone(), preprocess.clone());
+                let processed = blocking(move || {
+                    create_dir_all(&out_dir).with_path(&out_dir)?;
+                    preprocess.apply(&path, &out_dir)
+                })
+                .await?;
+                let Some(upload_path) = processed else {
+                    return Ok(None);
+                };
+                let file_name = upload_path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                let key_name = key_for(
+                    bucket_path_prefix,
+                    &format!("{}{file_name}", file.relative_dir()),
+                );
+                clients
+                    .s3
+                    .upload_content_to_a_bucket(
+                        bucket_name,
+                        &upload_path.display().to_string(),
+                        &key_name,
+                    )
+                    .await?;
+                Ok(Some(()))
+            },
+        )
+        .await?
+        .len();
+    println!(
+        "{}\n",
+        format!("{uploaded} image(s) are uploaded under '{bucket_name}/{bucket_path_prefix}'")
+            .green()
+            .bold()
+    );
+    Ok(())
+}
+
+pub struct UploadImages {
+    pub bucket_name: String,
+    pub local_path_prefix: String,
+    pub bucket_path_prefix: String,
+    pub preprocess: Preprocess,
+}
+
+#[async_trait(?Send)]
+impl Operation for UploadImages {
+    type Output = ();
+
+    async fn gather(clients: &Clients) -> Result<Option<Self>> {
+        let get_buckets = clients.s3.get_buckets().await?;
+        let available_buckets = format!("Available buckets in your account:\n{:#?}\n", get_buckets);
+        let bucket_name = Text::new("Please enter the bucket name where you'd like to store images for the 'Recognize Multiple Celebrities' option\n")
+            .with_placeholder(&available_buckets)
+            .with_formatter(&|input| format!("Received Bucket Name: {input}\n"))
+            .with_help_message("Ensure that the chosen bucket and region match")
+            .with_profile_default(&clients.profile.bucket)
+            .prompt()?;
+        let local_path_prefix = Text::new("Provide the local path prefix under which all your celebrity JPG or PNG images are stored\n")
+            .with_placeholder(r#"Eg: 'CelebrityImages/', 'E:\CelebrityImages'\n"#)
+            .with_formatter(&|input| format!("Received Local Path Prefix: {input}\n"))
+            .with_help_message("These images should be in either '.jpg' or '.png' format")
+            .with_profile_default(&clients.profile.local_prefix)
+            .prompt()?;
+        if bucket_name.is_empty() || local_path_prefix.is_empty() {
+            println!(
+                "{}\n",
+                "Both bucket name and local directory path Can't Empty"
+                    .red()
+                    .bold()
+            );
+            return Ok(None);
+        }
+        let get_objects = clients.s3.retrieve_keys_in_a_bucket(&bucket_name).await?;
+        let available_objects = format!(
+            "Available keys and path prefix in {bucket_name}\n{:#?}\n",
+            get_objects
+        );
+        let bucket_path_prefix = Text::new(
+            "Select a prefix for the bucket under which all your uploaded images will be saved\n",
+        )
+        .with_placeholder(&available_objects)
+        .with_help_message("For example, you can use 'celebrityimages/' or 'images/'")
+        .with_formatter(&|input| format!("Received Bucket Path Prefix: {input}\n"))
+        .with_profile_default(&clients.profile.bucket_prefix)
+        .prompt()?;
+        if bucket_path_prefix.is_empty() {
+            println!("{}\n", "Bucket path prefix can't be empty".red().bold());
+            return Ok(None);
+        }
+        let preprocess = prompt_preprocess(Preprocess::default())?;
+        Ok(Some(Self {
+            bucket_name,
+            local_path_prefix,
+            bucket_path_prefix,
+            preprocess,
+        }))
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<()> {
+        upload_images(
+            clients,
+            &self.local_path_prefix,
+            &self.bucket_name,
+            &self.bucket_path_prefix,
+            &self.preprocess,
+            "uploads",
+        )
+        .await?;
+        println!("Please provide '{}' as the prefix for 'Recognize Multiple Celebrities'\nwhen asking for the bucket path key or prefix to retrieve images under this prefix\n",self.bucket_path_prefix.green().bold());
+        Ok(())
+    }
+}
+
+pub struct RecognizeCelebrities {
+    pub source: Source,
+    pub visuals: CelebrityVisuals,
+}
+
+#[async_trait(?Send)]
+impl Operation for RecognizeCelebrities {
+    type Output = Vec<ImageRecord<CelebrityFace>>;
+
+    async fn gather(clients: &Clients) -> Result<Option<Self>> {
+        let Some(source) = Self::gather_source(clients).await? else {
+            return Ok(None);
+        };
+        let visuals = CelebrityVisuals::prompt()?;
+        Ok(Some(Self { source, visuals }))
+    }
+
+    /// Recognizes every image once, for the PDF report, the annotated images,
+    /// the crops and the results alike.
+    async fn execute(&self, clients: &Clients) -> Result<Vec<ImageRecord<CelebrityFace>>> {
+        let run_dir = clients.run_dir("celebrities")?;
+        let mut writer = self.visuals.writer(&run_dir)?;
+        let mut celebrities = Vec::new();
+        let mut sections = Vec::new();
+        let images = match &self.source {
+            Source::Local(dir) => Source::local_images(dir, &clients.settings.walk)?
+                .into_iter()
+                .map(|source| {
+                    let image_path = PathBuf::from(source.image_name());
+                    (source, image_path)
+                })
+                .collect(),
+            Source::S3 { .. } => {
+                let downloaded_images = run_dir.join("DownloadedImages");
+                create_dir(&downloaded_images).with_path(&downloaded_images)?;
+                let mut images = Vec::new();
+                for (source, local_name) in
+                    Source::with_local_names(self.source.images(clients).await?)
+                {
+                    let image_path = source
+                        .local_copy(clients, &downloaded_images, &local_name)
+                        .await?;
+                    images.push((source, PathBuf::from(image_path)));
+                }
+                images
+            }
+        };
+        for (source, image_path) in images {
+            let (local_path, bucket_name, key) = source.location();
+            let faces = clients
+                .rekognition
+                .recognize_celebrities(local_path, bucket_name, key)
+                .await?;
+            writer.add(&source, &image_path, &faces)?;
+            let image = source.image_name();
+            sections.push(celebrity_section(&image, image_path, &faces));
+            celebrities.extend(faces.into_iter().map(|face| ImageRecord {
+                image: image.clone(),
+                result: face,
+            }));
+        }
+        let report_path = run_dir.join("celebrities.pdf");
+        pdf::write_sections(&report_path, "Recognized Celebrities", &sections)?;
+        writer.finish()?;
+        println!(
+            "{} '{}'\n",
+            "The celebrity report is written to".green().bold(),
+            report_path.display().to_string().green().bold()
+        );
+        Ok(celebrities)
+    }
+
+    fn results(&self, celebrities: &Vec<ImageRecord<CelebrityFace>>) -> Result<Option<Results>> {
+        Results::new("celebrities", celebrities).map(Some)
+    }
+}
+
+impl RecognizeCelebrities {
+    /// Asks where the celebrity images are read from.
+    async fn gather_source(clients: &Clients) -> Result<Option<Source>> {
+        let local_or_s3 = Confirm::new(
+            "Either you want to provide the local or S3 location for the celebrity Images\n",
+        )
+        .with_placeholder("Type 'Yes' to load from the local location, or type 'No' to provide the S3 object prefix\n")
+        .with_formatter(&|input| format!("Received Response Is: {input}\n"))
+        .with_help_message("Only the celebrity images should be used, and the results are based on AWS APIs")
+        .prompt()?;
+        match local_or_s3 {
+            true => {
+                let celebrity_images_dir = Text::new(
+                    "Please provide the directory where the celebrity images exist in JPG or PNG formats\n",
+                )
+                .with_placeholder(r#"Eg: 'Celebrity Images/' or 'E:\New folder\CelebrityImages'\n"#)
+                .with_help_message("The images should be in '.JPG' or '.PNG' format; no other formats are supported")
+                .with_formatter(&|input| format!("Received Path Is: {input}\n"))
+                .with_profile_default(&clients.profile.local_prefix)
+                .prompt()?;
+                match celebrity_images_dir.is_empty() {
+                    false => Ok(Some(Source::Local(celebrity_images_dir))),
+                    true => {
+                        println!(
+                            "{}\n",
+                            "The Field Celebrity Images Dir can't be empty".red().bold()
+                        );
+                        Ok(None)
+                    }
+                }
+            }
+            false => {
+                let get_buckets = clients.s3.get_buckets().await?;
+                let available_buckets =
+                    format!("Available buckets in your account:\n{:#?}\n", get_buckets);
+                let bucket_name =
+                    Text::new("Enter the bucket name where the celebrity images are stored\n")
+                        .with_placeholder(&available_buckets)
+                        .with_formatter(&|input| format!("Received Bucket Name: {input}\n"))
+                        .with_help_message("The bucket name is what you used to upload images using the 'Upload Images to an S3 Bucket' option")
+                        .with_profile_default(&clients.profile.bucket)
+                        .prompt()?;
+                if bucket_name.is_empty() {
+                    println!("{}\n", "Bucket Name can't be empty".red().bold());
+                    return Ok(None);
+                }
+                let get_objects = clients.s3.retrieve_keys_in_a_bucket(&bucket_name).await?;
+                let available_objects = format!(
+                    "Available keys and path prefix in {bucket_name}\n{:#?}\n",
+                    get_objects
+                );
+                let bucket_path_prefix = Text::new("Please enter the bucket path prefix under which the celebrity images can be retrieved\n")
+                    .with_placeholder(&available_objects)
+                    .with_help_message("For example, 'celebrityimages/' or something similar if that's how you named the prefix")
+                    .with_formatter(&|input| format!("Received Bucket Path Prefix: {input}\n"))
+                    .with_profile_default(&clients.profile.bucket_prefix)
+                    .prompt()?;
+                Ok(Some(Source::S3 {
+                    bucket: bucket_name,
+                    key: bucket_path_prefix,
+                }))
+            }
+        }
+    }
+}
+
+/// The page of the celebrity report that shows an image and the faces in it.
+fn celebrity_section(image: &str, image_path: PathBuf, faces: &[CelebrityFace]) -> Section {
+    let mut lines = Vec::new();
+    for face in faces {
+        match (&face.name, face.match_confidence) {
+            (Some(name), Some(confidence)) => {
+                lines.push(format!("{name}, with a match confidence of {confidence}"))
+            }
+            (Some(name), None) => lines.push(name.clone()),
+            _ => lines.push("An unrecognized face".to_string()),
+        }
+        lines.extend(face.urls.iter().map(|url| format!("    {url}")));
+    }
+    if faces.is_empty() {
+        lines.push("No faces were found".to_string());
+    }
+    Section {
+        heading: image.to_string(),
+        image: Some(image_path),
+        lines,
+    }
+}
+
+pub struct DetectFaces {
+    pub images: Vec<Source>,
+}
+
+#[async_trait(?Send)]
+impl Operation for DetectFaces {
+    type Output = Vec<ImageRecord<FaceSummary>>;
+
+    async fn gather(clients: &Clients) -> Result<Option<Self>> {
+        if let Some(path) = prompt_local_path(clients, "face image")? {
+            return match path.is_empty() {
+                false => Ok(Some(Self {
+                    images: Source::local_images(&path, &clients.settings.walk)?,
+                })),
+                true => {
+                    println!("{}\n", "The local path can't be empty".red().bold());
+                    Ok(None)
+                }
+            };
+        }
+        let get_buckets = clients.s3.get_buckets().await?;
+        let available_buckets = format!("Available buckets in your account:\n{:#?}\n", get_buckets);
+        let bucket_name = Text::new("Select the bucket name where the face image is stored\n")
+            .with_placeholder(&available_buckets)
+            .with_formatter(&|str| format!(".....{str}.....\n"))
+            .with_help_message("Ensure that the bucket and the region are the same as where you are making the request")
+            .with_profile_default(&clients.profile.bucket)
+            .prompt()?;
+        if bucket_name.is_empty() {
+            println!("{}\n", "Bucket name can't be empty".red().bold());
+            return Ok(None);
+        }
+        let get_objects = clients.s3.retrieve_keys_in_a_bucket(&bucket_name).await?;
+        let available_objects = format!("Available keys in {bucket_name}\n{:#?}\n", get_objects);
+        let object = Text::new("Please input the key or path of the face image within the chosen bucket or copy it from the placeholder information\n")
+            .with_placeholder(&available_objects)
+            .with_formatter(&|str| format!(".....{str}.....\n"))
+            .with_help_message("Don't put quotation marks around the key when pasting")
+            .prompt()?;
+        match object.is_empty() {
+            false => Ok(Some(Self {
+                images: vec![Source::S3 {
+                    bucket: bucket_name,
+                    key: object,
+                }],
+            })),
+            true => {
+                println!("{}\n", "key/object name can't be empty".red().bold());
+                Ok(None)
+            }
+        }
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<Vec<ImageRecord<FaceSummary>>> {
+        let run_dir = clients.run_dir("detected_faces")?;
+        let mut checkpoint = clients.checkpoint(&run_dir)?;
+        let detected = clients
+            .settings
+            .batch
+            .run(
+                "image(s)",
+                &mut checkpoint,
+                self.images.iter().collect(),
+                |source| source.image_name(),
+                |source| async move {
+                    let (local_path, bucket_name, key) = source.location();
+                    let detected = clients
+                        .rekognition
+                        .detect_faces(local_path, bucket_name, key)
+                        .await?;
+                    let image = source.image_name();
+                    Ok(detected
+                        .into_iter()
+                        .map(|face| ImageRecord {
+                            image: image.clone(),
+                            result: face,
+                        })
+                        .collect::<Vec<_>>())
+                },
+            )
+            .await?;
+        let faces: Vec<_> = detected.into_iter().flatten().collect();
+        let detail_path = run_dir.join("FaceDetail.txt");
+        let mut file = OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .read(true)
+            .write(true)
+            .open(&detail_path)
+            .with_path(&detail_path)?;
+        for ImageRecord { result: face, .. } in &faces {
+            let buf = format!("Gender: {} and Confidence Level: {}\nAge Range:\nLowest Prediction Age: {} and Highest Prediction Age: {}\nSmile: {} and Confidence Levle: {}\nBeard: {} and Confidence: {}\nBounding Box Details:\nWidth: {}, Height: {}, Left: {},Top: {}",
+                face.gender,
+                face.gender_confidence,
+                face.age_low,
+                face.age_high,
+                face.smile,
+                face.smile_confidence,
+                face.beard,
+                face.beard_confidence,
+                face.width,
+                face.height,
+                face.left,
+                face.top
+            );
+            file.write_all(buf.as_bytes()).with_path(&detail_path)?;
+            file.write_all(b"\n").with_path(&detail_path)?;
+            for detail in more_face_details(face) {
+                file.write_all(detail.as_bytes()).with_path(&detail_path)?;
+            }
+        }
+        println!(
+            "{} '{}'\n",
+            "The text file, containing the face details, has been successfully written to"
+                .green()
+                .bold(),
+            detail_path.display().to_string().green().bold()
+        );
+        Ok(faces)
+    }
+
+    fn results(&self, faces: &Vec<ImageRecord<FaceSummary>>) -> Result<Option<Results>> {
+        Results::new("faces", faces).map(Some)
+    }
+}
+
+pub struct DetectText {
+    pub images: Vec<Source>,
+    /// Also writes copies of the images with the polygon and the string of
+    /// every detected line and word drawn on them.
+    pub overlay: bool,
+}
+
+#[async_trait(?Send)]
+impl Operation for DetectText {
+    type Output = Vec<ImageRecord<TextSummary>>;
+
+    async fn gather(clients: &Clients) -> Result<Option<Self>> {
+        let images = match prompt_local_path(clients, "image with the text")? {
+            Some(path) if path.is_empty() => {
+                println!("{}\n", "The local path can't be empty".red().bold());
+                return Ok(None);
+            }
+            Some(path) => Source::local_images(&path, &clients.settings.walk)?,
+            None => match Self::gather_s3(clients).await? {
+                Some(image) => vec![image],
+                None => return Ok(None),
+            },
+        };
+        let overlay = Confirm::new("Do you also want the detected lines and words drawn on the images?\n")
+            .with_placeholder("Type 'Yes' to write copies of the images with the polygon and the string of every line and word, or 'No' to write only the text\n")
+            .with_formatter(&|input| format!("Received Response Is: {input}\n"))
+            .with_help_message("Images in S3 are downloaded to draw on them")
+            .with_default(false)
+            .prompt()?;
+        println!();
+        Ok(Some(Self { images, overlay }))
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<Vec<ImageRecord<TextSummary>>> {
+        let run_dir = clients.run_dir("texts")?;
+        let mut checkpoint = clients.checkpoint(&run_dir)?;
+        let overlays_dir = run_dir.join("text_overlays");
+        if self.overlay {
+            create_dir_all(&overlays_dir).with_path(&overlays_dir)?;
+        }
+        // The downloaded images are only needed to draw the texts on.
+        let read_images = scratch_dir()?;
+        let font = Font::try_from_bytes(include_bytes!("../assets/font.ttf"))
+            .expect("Error Getting Font Bytes");
+        let (read_images, font, overlays_dir) = (read_images.path(), &font, &overlays_dir);
+        let detected = clients
+            .settings
+            .batch
+            .run(
+                "image(s)",
+                &mut checkpoint,
+                Source::with_local_names(self.images.clone()),
+                |(source, _)| source.image_name(),
+                |(source, local_name)| async move {
+                    let (local_path, bucket_name, key) = source.location();
+                    let detected = clients
+                        .rekognition
+                        .detect_texts(local_path, bucket_name, key)
+                        .await?;
+                    if self.overlay {
+                        let read_image_path =
+                            source.local_copy(clients, read_images, &local_name).await?;
+                        let overlay_path = overlays_dir.join(&local_name);
+                        let (texts, font) = (detected.clone(), font.clone());
+                        blocking(move || {
+                            let mut image =
+                                image::open(&read_image_path).with_path(&read_image_path)?;
+                            for text in &texts {
+                                overlay_text(&mut image, text, &font);
+                            }
+                            image.save(&overlay_path).with_path(&overlay_path)
+                        })
+                        .await?;
+                    }
+                    let image = source.image_name();
+                    Ok(detected
+                        .into_iter()
+                        .map(|text| ImageRecord {
+                            image: image.clone(),
+                            result: text,
+                        })
+                        .collect::<Vec<_>>())
+                },
+            )
+            .await?;
+        let texts: Vec<_> = detected.into_iter().flatten().collect();
+        let texts_path = run_dir.join("Texts.txt");
+        let mut texts_only = Vec::new();
+        let mut file = OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .read(true)
+            .write(true)
+            .open(&texts_path)
+            .with_path(&texts_path)?;
+        for ImageRecord {
+            image,
+            result: detected,
+        } in &texts
+        {
+            let buf = format!(
+                "Image: {image}\nDetected Text: {}\nText Type: {}\nText Confidence: {}\n",
+                detected.text, detected.text_type, detected.confidence
+            );
+            file.write_all(buf.as_bytes()).with_path(&texts_path)?;
+            texts_only.push(detected.text.clone());
+        }
+        println!(
+            "{} '{}'\n",
+            "The text file, containing the text details, has been successfully written to"
+                .green()
+                .bold(),
+            texts_path.display().to_string().green().bold()
+        );
+        pdf::write_lines(&run_dir.join("Texts.pdf"), "Detected Texts", &texts_only)?;
+        if self.overlay {
+            println!(
+                "{} '{}'\n",
+                "The images with the detected texts drawn on them are written to"
+                    .green()
+                    .bold(),
+                overlays_dir.display().to_string().green().bold()
+            );
+        }
+        Ok(texts)
+    }
+
+    fn results(&self, texts: &Vec<ImageRecord<TextSummary>>) -> Result<Option<Results>> {
+        Results::new("texts", texts).map(Some)
+    }
+}
+
+impl DetectText {
+    /// Asks for the bucket and the key of the image.
+    async fn gather_s3(clients: &Clients) -> Result<Option<Source>> {
+        let get_buckets = clients.s3.get_buckets().await?;
+        let available_buckets = format!("Available buckets in your account:\n{:#?}\n", get_buckets);
+        let bucket_name = Text::new(
+            "Please select the bucket name where the image is stored, which contains the text within it\n",
+        )
+        .with_placeholder(&available_buckets)
+        .with_formatter(&|str| format!(".....{str}.....\n"))
+        .with_help_message("Ensure that the bucket and the region are the same as where you are making the request")
+        .with_profile_default(&clients.profile.bucket)
+        .prompt()?;
+        if bucket_name.is_empty() {
+            println!("{}\n", "Bucket name can't be empty".red().bold());
+            return Ok(None);
+        }
+        let get_objects = clients.s3.retrieve_keys_in_a_bucket(&bucket_name).await?;
+        let available_objects = format!("Available keys in {bucket_name}\n{:#?}\n", get_objects);
+        let object = Text::new("Input the key or path of the image from the chosen bucket, or copy it from the placeholder information\n")
+            .with_placeholder(&available_objects)
+            .with_formatter(&|str| format!(".....{str}.....\n"))
+            .with_help_message("Don't put quotation marks around the key when pasting")
+            .prompt()?;
+        match object.is_empty() {
+            false => Ok(Some(Source::S3 {
+                bucket: bucket_name,
+                key: object,
+            })),
+            true => {
+                println!("{}\n", "key/object name can't be empty".red().bold());
+                Ok(None)
+            }
+        }
+    }
+}
+
+pub struct UploadModifiedImages {
+    pub bucket_name: String,
+    pub local_path_prefix: String,
+    pub bucket_path_prefix: String,
+    pub preprocess: Preprocess,
+}
+
+impl UploadModifiedImages {
+    /// What the images go through unless asked otherwise, which keeps them
+    /// small enough for quick face detection.
+    pub fn default_preprocess() -> Preprocess {
+        Preprocess {
+            max_dimension: Some(800),
+            auto_rotate: true,
+            ..Preprocess::default()
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl Operation for UploadModifiedImages {
+    type Output = ();
+
+    async fn gather(clients: &Clients) -> Result<Option<Self>> {
+        let get_buckets = clients.s3.get_buckets().await?;
+        let available_buckets = format!("Available buckets in your account:\n{:#?}\n", get_buckets);
+        let bucket_name = Text::new("Please enter the bucket name where you'd like to store images for the Face Detection Tasks\n")
+            .with_placeholder(&available_buckets)
+            .with_formatter(&|input| format!("Received Bucket Name: {input}\n"))
+            .with_help_message("Ensure that the chosen bucket and region match")
+            .with_profile_default(&clients.profile.bucket)
+            .prompt()?;
+        let local_path_prefix = Text::new("Provide the local path prefix where your all images are stored\n")
+            .with_placeholder("Please Note that the images are resized and converted as you choose next, but the original images on your computer remain unchanged\n")
+            .with_formatter(&|input| format!("Received Local Path Prefix: {input}\n"))
+            .with_help_message("These images should be in either '.jpg' or '.png' format")
+            .with_profile_default(&clients.profile.local_prefix)
+            .prompt()?;
+        if bucket_name.is_empty() || local_path_prefix.is_empty() {
+            println!("{}\n", "No fields can be empty".red().bold());
+            return Ok(None);
+        }
+        let get_objects = clients.s3.retrieve_keys_in_a_bucket(&bucket_name).await?;
+        let available_objects = format!(
+            "Available keys and path prefix in {bucket_name}\n{:#?}\n",
+            get_objects
+        );
+        let bucket_path_prefix =
+            Text::new("Select a prefix for the bucket where the images will be saved\n")
+                .with_placeholder(&available_objects)
+                .with_help_message("For example, you can use 'face_images/' or 'images/'")
+                .with_formatter(&|input| format!("Received Bucket Path Prefix: {input}"))
+                .with_profile_default(&clients.profile.bucket_prefix)
+                .prompt()?;
+        let preprocess = prompt_preprocess(Self::default_preprocess())?;
+        Ok(Some(Self {
+            bucket_name,
+            local_path_prefix,
+            bucket_path_prefix,
+            preprocess,
+        }))
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<()> {
+        upload_images(
+            clients,
+            &self.local_path_prefix,
+            &self.bucket_name,
+            &self.bucket_path_prefix,
+            &self.preprocess,
+            "modified_uploads",
+        )
+        .await
+    }
+}
+
+pub struct WriteFaceDetailsImages {
+    /// A local image or directory, or a path prefix within a bucket.
+    pub source: Source,
+    /// Also marks the facial landmarks as points on the images.
+    pub draw_landmarks: bool,
+}
+
+impl WriteFaceDetailsImages {
+    /// Detects the faces of an image and draws them on a copy of it, returning
+    /// the name of the image with the lines of its details, and its faces.
+    async fn write_details_image(
+        &self,
+        clients: &Clients,
+        source: &Source,
+        image_name: String,
+        read_images: &Path,
+        face_details_images: &Path,
+        font: &Font<'static>,
+    ) -> Result<(String, Vec<String>, Vec<ImageRecord<FaceSummary>>)> {
+        let read_image_path = source.local_copy(clients, read_images, &image_name).await?;
+        let (local_path, bucket_name, key) = source.location();
+        let outputs = clients
+            .rekognition
+            .detect_faces(local_path, bucket_name, key)
+            .await?;
+        let draw_landmarks = self.draw_landmarks;
+        let (source, font) = (source.clone(), font.clone());
+        let modified_image_path_name = face_details_images.join(&image_name);
+        blocking(move || {
+            let mut image = image::open(&read_image_path).with_path(&read_image_path)?;
+            let mut lines = Vec::new();
+            let mut faces = Vec::new();
+            for (index, face) in outputs.into_iter().enumerate() {
+                let FaceSummary {
+                    gender,
+                    gender_confidence,
+                    age_low,
+                    age_high,
+                    smile,
+                    smile_confidence,
+                    beard,
+                    beard_confidence,
+                    width,
+                    height,
+                    left,
+                    top,
+                    ..
+                } = &face;
+                let details = vec![
+                    format!("Details of face {} in image: {image_name}\n", index + 1),
+                    format!("Gender: {gender}, with a confidence level of {gender_confidence}\n"),
+                    format!("Age Range: {age_low} to {age_high}\n"),
+                    format!("Beard: {beard}, with a confidence level of {beard_confidence}\n"),
+                    format!("Smile: {smile}, with a confidence level of {smile_confidence}\n"),
+                    format!(
+                        "Bounding Box Details: Width: {}, Height: {}, Left: {}, Top: {}\n",
+                        width, height, left, top
+                    ),
+                ];
+                lines.extend(details.into_iter().chain(more_face_details(&face)));
+                lines.push("\n".to_string());
+                annotate_face(&mut image, index, &face, &font, draw_landmarks);
+                faces.push(ImageRecord {
+                    image: source.image_name(),
+                    result: face,
+                });
+            }
+            image
+                .save(&modified_image_path_name)
+                .with_path(&modified_image_path_name)?;
+            Ok((image_name, lines, faces))
+        })
+        .await
+    }
+}
+
+#[async_trait(?Send)]
+impl Operation for WriteFaceDetailsImages {
+    type Output = Vec<ImageRecord<FaceSummary>>;
+
+    async fn gather(clients: &Clients) -> Result<Option<Self>> {
+        let source = match prompt_local_path(clients, "face images")? {
+            Some(path) if path.is_empty() => {
+                println!("{}\n", "The local path can't be empty".red().bold());
+                return Ok(None);
+            }
+            Some(path) => Source::Local(path),
+            None => match gather_image_prefix(clients).await? {
+                Some(source) => source,
+                None => return Ok(None),
+            },
+        };
+        let draw_landmarks =
+            Confirm::new("Do you also want the facial landmarks drawn as points on the images?\n")
+                .with_placeholder("Type 'Yes' to mark the eyes, nose, mouth and the other landmarks, or 'No' to draw only the boxes\n")
+                .with_formatter(&|input| format!("Received Response Is: {input}\n"))
+                .with_default(false)
+                .prompt()?;
+        println!();
+        Ok(Some(Self {
+            source,
+            draw_landmarks,
+        }))
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<Vec<ImageRecord<FaceSummary>>> {
+        let run_dir = clients.run_dir("face_images")?;
+        let mut checkpoint = clients.checkpoint(&run_dir)?;
+        let details_path = run_dir.join("Face_details.txt");
+        let mut file = OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .read(true)
+            .write(true)
+            .open(&details_path)
+            .with_path(&details_path)?;
+
+        let images = self.source.images(clients).await?;
+        let face_details_images = run_dir.join("face_details_images");
+        create_dir_all(&face_details_images).with_path(&face_details_images)?;
+        // The downloaded images are only needed to draw the details on.
+        let read_images = scratch_dir()?;
+        let read_images = read_images.path();
+
+        let font = Font::try_from_bytes(include_bytes!("../assets/font.ttf"))
+            .expect("Error Getting Font Bytes");
+        let font = &font;
+        let face_details_images = &face_details_images;
+
+        let details_images = clients
+            .settings
+            .batch
+            .run(
+                "image(s)",
+                &mut checkpoint,
+                Source::with_local_names(images),
+                |(source, _)| source.image_name(),
+                |(source, local_name)| async move {
+                    self.write_details_image(
+                        clients,
+                        &source,
+                        local_name,
+                        read_images,
+                        face_details_images,
+                        font,
+                    )
+                    .await
+                },
+            )
+            .await?;
+        // The details are written in the order of the images, whichever
+        // finished first.
+        let mut faces = Vec::new();
+        let mut face_lines: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (image_name, details, image_faces) in details_images {
+            for detail in &details {
+                file.write_all(detail.as_bytes()).with_path(&details_path)?;
+            }
+            face_lines.entry(image_name).or_default().extend(
+                details
+                    .iter()
+                    .map(|detail| detail.trim_end().to_string())
+                    .filter(|detail| !detail.is_empty()),
+            );
+            faces.extend(image_faces);
+        }
+
+        let compressed_images = run_dir.join("compressed_images");
+        create_dir_all(&compressed_images).with_path(&compressed_images)?;
+        let mut compressor = FolderCompressor::new(face_details_images, &compressed_images);
+        compressor.set_thread_count(8);
+        compressor.set_delelte_origin(true);
+        compressor
+            .compress()
+            .map_err(|error| Error::Compression(error.to_string()))?;
+        let compressed: Vec<PathBuf> = read_dir(&compressed_images)
+            .with_path(&compressed_images)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<_>>()
+            .with_path(&compressed_images)?;
+        // The compressor may change the extension, so the images are matched by stem.
+        let sections: Vec<Section> = face_lines
+            .into_iter()
+            .map(|(image_name, lines)| Section {
+                image: compressed
+                    .iter()
+                    .find(|path| path.file_stem() == Path::new(&image_name).file_stem())
+                    .cloned(),
+                heading: image_name,
+                lines,
+            })
+            .collect();
+        pdf::write_sections(&run_dir.join("Face_details.pdf"), "Face Details", &sections)?;
+        println!(
+            "{} '{}'\n",
+            "Face details and the images with them are written to"
+                .green()
+                .bold(),
+            run_dir.display().to_string().green().bold()
+        );
+        Ok(faces)
+    }
+
+    fn results(&self, faces: &Vec<ImageRecord<FaceSummary>>) -> Result<Option<Results>> {
+        Results::new("faces", faces).map(Some)
+    }
+}
+
+/// How the faces are hidden by [`RedactFaces`].
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Redaction {
+    /// A Gaussian blur strong enough to make the face unrecognizable
+    Blur,
+    /// Large square blocks of the average color
+    Pixelate,
+    /// A solid black box
+    Box,
+}
+
+impl Redaction {
+    /// Hides the region of a face, grown a little so the edges of the face are covered too.
+    fn apply(&self, image: &mut DynamicImage, bounding_box: &BoundingBox) {
+        let (image_width, image_height) = image.dimensions();
+        let grow_x = bounding_box.width * 0.1;
+        let grow_y = bounding_box.height * 0.1;
+        let left = ((bounding_box.left - grow_x).max(0.0) * image_width as f32) as u32;
+        let top = ((bounding_box.top - grow_y).max(0.0) * image_height as f32) as u32;
+        let right = ((bounding_box.left + bounding_box.width + grow_x).min(1.0)
+            * image_width as f32) as u32;
+        let bottom = ((bounding_box.top + bounding_box.height + grow_y).min(1.0)
+            * image_height as f32) as u32;
+        if right <= left || bottom <= top {
+            return;
+        }
+        let (width, height) = (right - left, bottom - top);
+        match self {
+            Self::Blur => {
+                let face = image.crop_imm(left, top, width, height);
+                let sigma = (width.max(height) as f32 / 8.0).max(4.0);
+                replace(image, &face.blur(sigma), left as i64, top as i64);
+            }
+            Self::Pixelate => {
+                let block = (width.max(height) / 12).max(4);
+                let face = image
+                    .crop_imm(left, top, width, height)
+                    .resize_exact(
+                        (width / block).max(1),
+                        (height / block).max(1),
+                        FilterType::Triangle,
+                    )
+                    .resize_exact(width, height, FilterType::Nearest);
+                replace(image, &face, left as i64, top as i64);
+            }
+            Self::Box => {
+                let rect = Rect::at(left as i32, top as i32).of_size(width, height);
+                draw_filled_rect_mut(image, rect, Rgba([0, 0, 0, 255]));
+            }
+        }
+    }
+}
+
+pub struct RedactFaces {
+    /// A local image or directory, or a path prefix within a bucket.
+    pub source: Source,
+    pub redaction: Redaction,
+    /// The bucket and the path prefix the redacted images are uploaded under,
+    /// followed by their path within the directory or the path prefix they
+    /// were found under, besides being written to the run directory.
+    pub upload_to: Option<(String, String)>,
+}
+
+#[async_trait(?Send)]
+impl Operation for RedactFaces {
+    type Output = Vec<ImageRecord<FaceSummary>>;
+
+    async fn gather(clients: &Clients) -> Result<Option<Self>> {
+        let source = match prompt_local_path(clients, "images to redact")? {
+            Some(path) if path.is_empty() => {
+                println!("{}\n", "The local path can't be empty".red().bold());
+                return Ok(None);
+            }
+            Some(path) => Source::Local(path),
+            None => match gather_image_prefix(clients).await? {
+                Some(source) => source,
+                None => return Ok(None),
+            },
+        };
+        let redactions = vec!["Blur\n", "Pixelate\n", "Solid box\n"];
+        let redaction = match Select::new("Select how the faces should be hidden\n", redactions)
+            .with_help_message(
+                "Every face found by 'DetectFaces' is hidden, whatever its confidence",
+            )
+            .prompt()?
+        {
+            "Blur\n" => Redaction::Blur,
+            "Pixelate\n" => Redaction::Pixelate,
+            _ => Redaction::Box,
+        };
+        let upload = Confirm::new("Do you also want the redacted images uploaded to a bucket?\n")
+            .with_placeholder("Type 'Yes' to upload them under a path prefix of your choice, or 'No' to only write them locally\n")
+            .with_formatter(&|input| format!("Received Response Is: {input}\n"))
+            .with_default(false)
+            .prompt()?;
+        let upload_to = match upload {
+            true => {
+                let get_buckets = clients.s3.get_buckets().await?;
+                let available_buckets =
+                    format!("Available buckets in your account:\n{:#?}\n", get_buckets);
+                let bucket_name = Text::new(
+                    "Please enter the bucket name where the redacted images should be uploaded\n",
+                )
+                .with_placeholder(&available_buckets)
+                .with_formatter(&|input| format!("Received Bucket Name: {input}\n"))
+                .with_profile_default(&clients.profile.bucket)
+                .prompt()?;
+                let bucket_path_prefix = Text::new(
+                    "Select a prefix for the bucket where the redacted images will be saved\n",
+                )
+                .with_help_message(
+                    "For example, you can use 'redacted/' so that the originals aren't overwritten",
+                )
+                .with_formatter(&|input| format!("Received Bucket Path Prefix: {input}\n"))
+                .prompt()?;
+                match (bucket_name.is_empty(), bucket_path_prefix.is_empty()) {
+                    (false, false) => Some((bucket_name, bucket_path_prefix)),
+                    _ => {
+                        println!("{}\n", "No fields can be empty".red().bold());
+                        return Ok(None);
+                    }
+                }
+            }
+            false => None,
+        };
+        println!();
+        Ok(Some(Self {
+            source,
+            redaction,
+            upload_to,
+        }))
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<Vec<ImageRecord<FaceSummary>>> {
+        let run_dir = clients.run_dir("redacted_faces")?;
+        let mut checkpoint = clients.checkpoint(&run_dir)?;
+        let images = self.source.images(clients).await?;
+        // The downloaded originals are only needed to redact them.
+        let read_images = scratch_dir()?;
+        let (read_images, run_dir) = (read_images.path(), &run_dir);
+        let redacted = clients
+            .settings
+            .batch
+            .run(
+                "image(s)",
+                &mut checkpoint,
+                Source::with_local_names(images),
+                |(source, _)| source.image_name(),
+                |(source, local_name)| async move {
+                    let (local_path, bucket_name, key) = source.location();
+                    let detected = clients
+                        .rekognition
+                        .detect_faces(local_path, bucket_name, key)
+                        .await?;
+                    let read_image_path =
+                        source.local_copy(clients, read_images, &local_name).await?;
+                    // The copies keep the subdirectories, so that images of the same
+                    // name in different directories don't overwrite each other.
+                    let relative_path = key_for("", &source.relative_to(&self.source));
+                    let redacted_path = run_dir.join(&relative_path);
+                    let redaction = self.redaction;
+                    let boxes: Vec<_> = detected
+                        .iter()
+                        .map(|face| BoundingBox {
+                            width: face.width,
+                            height: face.height,
+                            left: face.left,
+                            top: face.top,
+                        })
+                        .collect();
+                    let target = redacted_path.clone();
+                    blocking(move || {
+                        let mut image =
+                            image::open(&read_image_path).with_path(&read_image_path)?;
+                        for bounding_box in &boxes {
+                            redaction.apply(&mut image, bounding_box);
+                        }
+                        if let Some(parent) = target.parent() {
+                            create_dir_all(parent).with_path(parent)?;
+                        }
+                        image.save(&target).with_path(&target)
+                    })
+                    .await?;
+                    if let Some((bucket_name, bucket_path_prefix)) = &self.upload_to {
+                        clients
+                            .s3
+                            .upload_content_to_a_bucket(
+                                bucket_name,
+                                &redacted_path.display().to_string(),
+                                &key_for(bucket_path_prefix, &relative_path),
+                            )
+                            .await?;
+                    }
+                    let image_name = source.image_name();
+                    Ok(detected
+                        .into_iter()
+                        .map(|face| ImageRecord {
+                            image: image_name.clone(),
+                            result: face,
+                        })
+                        .collect::<Vec<_>>())
+                },
+            )
+            .await?;
+        let faces: Vec<_> = redacted.into_iter().flatten().collect();
+        println!(
+            "{} '{}'\n",
+            "The redacted images are written to".green().bold(),
+            run_dir.display().to_string().green().bold()
+        );
+        if let Some((bucket_name, bucket_path_prefix)) = &self.upload_to {
+            println!(
+                "{} '{}'\n",
+                "The redacted images are uploaded under".green().bold(),
+                format!("{bucket_name}/{bucket_path_prefix}").green().bold()
+            );
+        }
+        Ok(faces)
+    }
+
+    fn results(&self, faces: &Vec<ImageRecord<FaceSummary>>) -> Result<Option<Results>> {
+        Results::new("faces", faces).map(Some)
+    }
+}
+
+/// Asks for the bucket and the path prefix the images are stored under.
+async fn gather_image_prefix(clients: &Clients) -> Result<Option<Source>> {
+    let get_buckets = clients.s3.get_buckets().await?;
+    let available_buckets = format!("Available buckets in your account:\n{:#?}\n", get_buckets);
+    let bucket_name =
+        Text::new("Please enter the name of the bucket where the images are stored\n")
+            .with_placeholder(&available_buckets)
+            .with_formatter(&|input| format!("Received Bucket Name Is: {input}"))
+            .with_profile_default(&clients.profile.bucket)
+            .prompt()?;
+    if bucket_name.is_empty() {
+        println!(
+            "{}\n",
+            "Neither Bucket Name nor Bucket Path Prefix Can't be Empty"
+                .red()
+                .bold()
+        );
+        return Ok(None);
+    }
+    let get_objects = clients.s3.retrieve_keys_in_a_bucket(&bucket_name).await?;
+    let available_objects = format!(
+        "Available keys and path prefix in {bucket_name}\n{:#?}\n",
+        get_objects
+    );
+    let bucket_path_prefix =
+        Text::new("Enter the path prefix within the bucket where the images are stored\n")
+            .with_formatter(&|input| format!("Received Bucket Path Prefix Is: {input}"))
+            .with_placeholder(&available_objects)
+            .with_help_message(
+                "The images are written to a new timestamped directory of the output root",
+            )
+            .with_profile_default(&clients.profile.bucket_prefix)
+            .prompt()?;
+    Ok(Some(Source::S3 {
+        bucket: bucket_name,
+        key: bucket_path_prefix,
+    }))
+}
+
+/// Draws the polygon of a detected line or word, scaled to the image
+/// dimensions, with the detected string above a line and below a word.
+fn overlay_text(image: &mut DynamicImage, text: &TextSummary, font: &Font) {
+    if text.polygon.is_empty() {
+        return;
+    }
+    let (image_width, image_height) = image.dimensions();
+    let corners: Vec<(f32, f32)> = text
+        .polygon
+        .iter()
+        .map(|point| (point.x * image_width as f32, point.y * image_height as f32))
+        .collect();
+    let is_line = text.text_type == "LINE";
+    let color = match is_line {
+        true => Rgba([0, 90, 255, 255]),
+        false => Rgba([255, 0, 0, 255]),
+    };
+    let thickness = (image_width.min(image_height) / 400).max(2) as i32;
+    for (index, &start) in corners.iter().enumerate() {
+        let end = corners[(index + 1) % corners.len()];
+        for offset in 0..thickness {
+            let offset = offset as f32;
+            draw_line_segment_mut(
+                image,
+                (start.0 + offset, start.1 + offset),
+                (end.0 + offset, end.1 + offset),
+                color,
+            );
+        }
+    }
+
+    let top = corners
+        .iter()
+        .map(|corner| corner.1)
+        .fold(f32::MAX, f32::min);
+    let bottom = corners
+        .iter()
+        .map(|corner| corner.1)
+        .fold(f32::MIN, f32::max);
+    let left = corners
+        .iter()
+        .map(|corner| corner.0)
+        .fold(f32::MAX, f32::min);
+    let scale = Scale::uniform((image_height as f32 / 50.0).max(12.0));
+    let (_, label_height) = text_size(scale, font, &text.text);
+    let y = match is_line {
+        true => (top as i32 - label_height - thickness).max(0),
+        false => (bottom as i32 + thickness).min(image_height as i32 - label_height),
+    };
+    draw_text_mut(image, color, left as i32, y, scale, font, &text.text);
+}
+
+/// The colors the faces of an image are told apart with.
+const FACE_COLORS: [Rgba<u8>; 6] = [
+    Rgba([255, 0, 0, 255]),
+    Rgba([0, 200, 0, 255]),
+    Rgba([0, 90, 255, 255]),
+    Rgba([255, 190, 0, 255]),
+    Rgba([220, 0, 220, 255]),
+    Rgba([0, 200, 220, 255]),
+];
+
+/// The report lines of the attributes beyond gender, age, beard and smile.
+fn more_face_details(face: &FaceSummary) -> Vec<String> {
+    let flag = |name: &str, flag: &FaceFlag| {
+        format!(
+            "{name}: {}, with a confidence level of {}\n",
+            flag.value, flag.confidence
+        )
+    };
+    let emotions: Vec<String> = face
+        .emotions
+        .iter()
+        .map(|emotion| format!("{} ({})", emotion.kind, emotion.confidence))
+        .collect();
+    let landmarks: Vec<String> = face
+        .landmarks
+        .iter()
+        .map(|landmark| format!("{} ({}, {})", landmark.kind, landmark.x, landmark.y))
+        .collect();
+    vec![
+        format!("Face Confidence: {}\n", face.confidence),
+        flag("Mustache", &face.mustache),
+        flag("Eyeglasses", &face.eyeglasses),
+        flag("Sunglasses", &face.sunglasses),
+        flag("Eyes Open", &face.eyes_open),
+        flag("Mouth Open", &face.mouth_open),
+        format!("Emotions: {}\n", emotions.join(", ")),
+        format!(
+            "Pose: Roll: {}, Yaw: {}, Pitch: {}\n",
+            face.pose.roll, face.pose.yaw, face.pose.pitch
+        ),
+        format!(
+            "Quality: Brightness: {}, Sharpness: {}\n",
+            face.quality.brightness, face.quality.sharpness
+        ),
+        format!("Landmarks: {}\n", landmarks.join(", ")),
+    ]
+}
+
+/// Draws the bounding box of a face, scaled to the image dimensions, and labels
+/// its attributes next to the box in the color of the face. The landmarks are
+/// marked as points when asked for.
+fn annotate_face(
+    image: &mut DynamicImage,
+    index: usize,
+    face: &FaceSummary,
+    font: &Font,
+    draw_landmarks: bool,
+) {
+    let (image_width, image_height) = image.dimensions();
+    let color = FACE_COLORS[index % FACE_COLORS.len()];
+    let bounding_box = BoundingBox {
+        width: face.width,
+        height: face.height,
+        left: face.left,
+        top: face.top,
+    };
+    let (rect, thickness) = draw_face_box(image, &bounding_box, color);
+    if draw_landmarks {
+        let radius = thickness as i32 + 1;
+        for landmark in &face.landmarks {
+            let center = (
+                (landmark.x * image_width as f32) as i32,
+                (landmark.y * image_height as f32) as i32,
+            );
+            draw_filled_circle_mut(image, center, radius, color);
+        }
+    }
+    let labels = [
+        format!("Face {}", index + 1),
+        format!("Gender: {}", face.gender),
+        format!("Age: {}-{}", face.age_low, face.age_high),
+        format!("Beard: {}", face.beard),
+        format!("Smile: {}", face.smile),
+    ];
+    draw_box_labels(image, rect, thickness, &labels, color, font);
+}
+
+/// Draws a box, scaled from the ratios to the image dimensions, and returns it
+/// in pixels along with the thickness of its lines.
+fn draw_face_box(
+    image: &mut DynamicImage,
+    bounding_box: &BoundingBox,
+    color: Rgba<u8>,
+) -> (Rect, u32) {
+    let (image_width, image_height) = image.dimensions();
+    let left = (bounding_box.left.max(0.0) * image_width as f32) as i32;
+    let top = (bounding_box.top.max(0.0) * image_height as f32) as i32;
+    let width = ((bounding_box.width * image_width as f32) as u32).max(1);
+    let height = ((bounding_box.height * image_height as f32) as u32).max(1);
+    let thickness = (image_width.min(image_height) / 300).max(2);
+    for offset in 0..thickness {
+        let rect = Rect::at(left - offset as i32, top - offset as i32)
+            .of_size(width + 2 * offset, height + 2 * offset);
+        draw_hollow_rect_mut(image, rect, color);
+    }
+    (Rect::at(left, top).of_size(width, height), thickness)
+}
+
+/// Writes the labels one below the other, right of the box when they fit
+/// there and otherwise left of it.
+fn draw_box_labels(
+    image: &mut DynamicImage,
+    rect: Rect,
+    thickness: u32,
+    labels: &[String],
+    color: Rgba<u8>,
+    font: &Font,
+) {
+    let (image_width, image_height) = image.dimensions();
+    let scale = Scale::uniform((image_height as f32 / 40.0).max(14.0));
+    let line_height = scale.y as i32 + 4;
+    let label_width = labels
+        .iter()
+        .map(|label| text_size(scale, font, label).0)
+        .max()
+        .unwrap_or(0);
+    let margin = thickness as i32 + 4;
+    let right = rect.right() + margin;
+    let x = match right + label_width <= image_width as i32 {
+        true => right,
+        false => (rect.left() - margin - label_width).max(0),
+    };
+    let y = rect
+        .top()
+        .min(image_height as i32 - line_height * labels.len() as i32)
+        .max(0);
+    for (line, label) in labels.iter().enumerate() {
+        let line_y = y + line as i32 * line_height;
+        draw_text_mut(image, color, x, line_y, scale, font, label);
+    }
+}
+
+/// Prompts for the bucket and key of a stored video, shared by the start task options.
+async fn gather_video_location(
+    clients: &Clients,
+    content: &str,
+) -> Result<Option<(String, String)>> {
+    let get_buckets = clients.s3.get_buckets().await?;
+    let available_buckets = format!("Available buckets in your account:\n{:#?}\n", get_buckets);
+    let help_message = format!("S3 buckets are used to store {content} and videos");
+    let bucket_name = Text::new(&format!(
+        "Select the bucket name where the {content} video is stored\n"
+    ))
+    .with_placeholder(&available_buckets)
+    .with_formatter(&|str| format!(".....{str}.....\n"))
+    .with_help_message(&help_message)
+    .with_profile_default(&clients.profile.bucket)
+    .prompt()?;
+    if bucket_name.is_empty() {
+        println!("{}\n", "Bucket name can't be empty".red().bold());
+        return Ok(None);
+    }
+    let get_objects = clients.s3.retrieve_keys_in_a_bucket(&bucket_name).await?;
+    let available_objects = format!("Available keys in {bucket_name}\n{:#?}\n", get_objects);
+    let key_video_name = Text::new(&format!("Please input the key or path of the {content} video within the chosen bucket or copy it from the placeholder information\n"))
+        .with_placeholder(&available_objects)
+        .with_formatter(&|str| format!(".....{str}.....\n"))
+        .with_help_message("Don't put quotation marks around the key when pasting")
+        .prompt()?;
+    match key_video_name.is_empty() {
+        false => Ok(Some((bucket_name, key_video_name))),
+        true => {
+            println!("{}\n", "key/object name can't be empty".red().bold());
+            Ok(None)
+        }
+    }
+}
+
+pub struct StartFaceDetection {
+    pub bucket_name: String,
+    pub key: String,
+}
+
+#[async_trait(?Send)]
+impl Operation for StartFaceDetection {
+    /// The job ID, unless the job was waited for and its results are already written.
+    type Output = Option<String>;
+
+    async fn gather(clients: &Clients) -> Result<Option<Self>> {
+        let location = gather_video_location(clients, "face").await?;
+        Ok(location.map(|(bucket_name, key)| Self { bucket_name, key }))
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<Option<String>> {
+        let job_id = clients
+            .rekognition
+            .start_face_detection_task(&self.bucket_name, &self.key)
+            .await?;
+        Ledger::record(
+            JobKind::FaceDetection,
+            &job_id,
+            &[("bucket", &self.bucket_name), ("key", &self.key)],
+            Some("IN_PROGRESS"),
+        )?;
+        println!("Job ID: {}", job_id.green().bold());
+        if clients.settings.wait.is_none() {
+            return Ok(Some(job_id));
+        }
+        run_with(GetFaceDetection { job_id }, clients).await?;
+        Ok(None)
+    }
+
+    fn render(&self, job_id: Option<String>) -> Result<()> {
+        if job_id.is_some() {
+            render_recorded_job("Get face detection results");
+        }
+        Ok(())
+    }
+}
+
+pub struct StartTextDetection {
+    pub bucket_name: String,
+    pub key: String,
+}
+
+#[async_trait(?Send)]
+impl Operation for StartTextDetection {
+    /// The job ID, unless the job was waited for and its results are already written.
+    type Output = Option<String>;
+
+    async fn gather(clients: &Clients) -> Result<Option<Self>> {
+        let location = gather_video_location(clients, "text").await?;
+        Ok(location.map(|(bucket_name, key)| Self { bucket_name, key }))
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<Option<String>> {
+        let job_id = clients
+            .rekognition
+            .start_text_detection_task(&self.bucket_name, &self.key)
+            .await?;
+        Ledger::record(
+            JobKind::TextDetection,
+            &job_id,
+            &[("bucket", &self.bucket_name), ("key", &self.key)],
+            Some("IN_PROGRESS"),
+        )?;
+        println!("Job ID: {}", job_id.green().bold());
+        if clients.settings.wait.is_none() {
+            return Ok(Some(job_id));
+        }
+        run_with(GetTextDetection { job_id }, clients).await?;
+        Ok(None)
+    }
+
+    fn render(&self, job_id: Option<String>) -> Result<()> {
+        if job_id.is_some() {
+            render_recorded_job("Get text detection results");
+        }
+        Ok(())
+    }
+}
+
+fn render_recorded_job(get_option: &str) {
+    println!(
+        "{}\n",
+        format!("The job is recorded, so '{get_option}' offers it without typing the ID")
+            .yellow()
+            .bold()
+    );
+}
+
+/// Points to the run directory of a detection job once it has succeeded, and
+/// removes it otherwise since nothing is written to it until then.
+fn finish_detection_dir(run_dir: &Path, job: &DetectionJob) {
+    match job.status.as_deref() {
+        Some("SUCCEEDED") => println!(
+            "{} '{}'\n",
+            "The results of the job are written to".green().bold(),
+            run_dir.display().to_string().green().bold()
+        ),
+        // Only an empty directory is removed, so there is nothing to report.
+        _ => {
+            let _ = remove_dir(run_dir);
+        }
+    }
+}
+
+/// Reports the state of a face or text detection job, whose results were written
+/// to its run directory while executing once the job has succeeded.
+fn render_detection_job(job: &DetectionJob) {
+    if let Some(job_status) = &job.status {
+        match job_status.as_str() {
+            "IN_PROGRESS" => {
+                println!("The job status is currently marked as '{}' which means no output is generated until the status changes to '{}'","IN_PROGRESS".green().bold(),"SUCCEEDED".yellow().bold());
+                println!("{}\n","Please check back after some time to obtain the results of the face detection process".yellow().bold());
+            }
+            "SUCCEEDED" => {
+                println!(
+                    "It appears that the job status is now '{}', and the output processing has begun\n",
+                    "SUCCEEDED".green().bold()
+                );
+            }
+            "FAILED" => {
+                println!("It appears that the job status is '{}'. For some reason, the face detection task has failed","FAILED".green().bold());
+                println!(
+                    "{}\n",
+                    "Please try again by restarting the face detection process. Good luck!\n"
+                        .yellow()
+                        .bold()
+                );
+            }
+            _ => {}
+        }
+    }
+    if let Some(status_msg) = &job.status_message {
+        println!("Status Message is: {}\n", status_msg.green().bold());
+    }
+}
+
+pub struct GetFaceDetection {
+    pub job_id: String,
+}
+
+#[async_trait(?Send)]
+impl Operation for GetFaceDetection {
+    type Output = DetectionJob;
+
+    async fn gather(_: &Clients) -> Result<Option<Self>> {
+        let job_id = pick_job(
+            JobKind::FaceDetection,
+            Text::new(
+                "To obtain the results of the face detection task, please enter the job ID\n",
+            )
+            .with_placeholder(
+                "The job ID was generated when you initiated the start face detection task\n",
+            )
+            .with_formatter(&|str| format!("......{str}......\n")),
+        )?;
+        match job_id.is_empty() {
+            false => Ok(Some(Self { job_id })),
+            true => {
+                println!("{}\n", "Job ID can't be empty".red().bold());
+                Ok(None)
+            }
+        }
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<DetectionJob> {
+        let run_dir = clients.run_dir("face_detection")?;
+        let job = clients
+            .fetch_job(
+                &self.job_id,
+                &["IN_PROGRESS"],
+                |job: &DetectionJob| job.status.as_deref(),
+                || {
+                    clients
+                        .rekognition
+                        .get_face_detection_results(&self.job_id, &run_dir)
+                },
+            )
+            .await?;
+        finish_detection_dir(&run_dir, &job);
+        Ledger::update_status(JobKind::FaceDetection, &self.job_id, job.status.as_deref())?;
+        Ok(job)
+    }
+
+    fn results(&self, job: &DetectionJob) -> Result<Option<Results>> {
+        Results::new("detection_job", std::slice::from_ref(job)).map(Some)
+    }
+
+    fn render(&self, job: DetectionJob) -> Result<()> {
+        render_detection_job(&job);
+        Ok(())
+    }
+}
+
+pub struct GetTextDetection {
+    pub job_id: String,
+}
+
+#[async_trait(?Send)]
+impl Operation for GetTextDetection {
+    type Output = DetectionJob;
+
+    async fn gather(_: &Clients) -> Result<Option<Self>> {
+        let job_id = pick_job(
+            JobKind::TextDetection,
+            Text::new(
+                "To obtain the results of the text detection task, please enter the job ID\n",
+            )
+            .with_placeholder(
+                "The job ID was generated when you initiated the start text detection task\n",
+            )
+            .with_formatter(&|str| format!("......{str}......")),
+        )?;
+        match job_id.is_empty() {
+            false => Ok(Some(Self { job_id })),
+            true => {
+                println!("{}\n", "Job ID can't be empty".red().bold());
+                Ok(None)
+            }
+        }
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<DetectionJob> {
+        let run_dir = clients.run_dir("text_detection")?;
+        let job = clients
+            .fetch_job(
+                &self.job_id,
+                &["IN_PROGRESS"],
+                |job: &DetectionJob| job.status.as_deref(),
+                || {
+                    clients
+                        .rekognition
+                        .get_text_detection_results(&self.job_id, &run_dir)
+                },
+            )
+            .await?;
+        finish_detection_dir(&run_dir, &job);
+        Ledger::update_status(JobKind::TextDetection, &self.job_id, job.status.as_deref())?;
+        Ok(job)
+    }
+
+    fn results(&self, job: &DetectionJob) -> Result<Option<Results>> {
+        Results::new("detection_job", std::slice::from_ref(job)).map(Some)
+    }
+
+    fn render(&self, job: DetectionJob) -> Result<()> {
+        render_detection_job(&job);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::{ImageQuality, Landmark, Point, Pose};
+    use image::RgbaImage;
+    use tempfile::TempDir;
+
+    const BLACK: Rgba<u8> = Rgba([0, 0, 0, 255]);
+
+    fn font() -> Font<'static> {
+        Font::try_from_bytes(include_bytes!("../assets/font.ttf")).unwrap()
+    }
+
+    fn blank(width: u32, height: u32) -> DynamicImage {
+        DynamicImage::ImageRgba8(RgbaImage::from_pixel(width, height, BLACK))
+    }
+
+    fn face(left: f32, top: f32, width: f32, height: f32) -> FaceSummary {
+        FaceSummary {
+            gender: "Female".to_string(),
+            gender_confidence: 99.0,
+            age_low: 25,
+            age_high: 33,
+            smile: true,
+            smile_confidence: 90.0,
+            beard: false,
+            beard_confidence: 95.0,
+            width,
+            height,
+            left,
+            top,
+            confidence: 99.9,
+            mustache: FaceFlag::default(),
+            eyeglasses: FaceFlag::default(),
+            sunglasses: FaceFlag::default(),
+            eyes_open: FaceFlag::default(),
+            mouth_open: FaceFlag::default(),
+            emotions: Vec::new(),
+            pose: Pose::default(),
+            quality: ImageQuality::default(),
+            landmarks: vec![Landmark {
+                kind: "nose".to_string(),
+                x: left + width / 2.0,
+                y: top + height / 2.0,
+            }],
+        }
+    }
+
+    fn has_color(image: &DynamicImage, xs: std::ops::Range<u32>, color: Rgba<u8>) -> bool {
+        xs.flat_map(|x| (0..image.height()).map(move |y| (x, y)))
+            .any(|(x, y)| image.get_pixel(x, y) == color)
+    }
+
+    #[test]
+    fn every_face_gets_a_box_of_its_own_color_at_the_original_resolution() {
+        let mut image = blank(1000, 600);
+        let font = font();
+
+        annotate_face(&mut image, 0, &face(0.1, 0.25, 0.2, 0.5), &font, false);
+        annotate_face(&mut image, 1, &face(0.5, 0.25, 0.2, 0.5), &font, false);
+
+        assert_eq!(image.dimensions(), (1000, 600));
+        // The boxes are scaled to the image: left edges at x 100 and 500.
+        assert_eq!(image.get_pixel(100, 300), FACE_COLORS[0]);
+        assert_eq!(image.get_pixel(500, 300), FACE_COLORS[1]);
+        assert_eq!(image.get_pixel(299, 300), FACE_COLORS[0]);
+        // Only the outline is drawn, without the landmarks.
+        assert_eq!(image.get_pixel(200, 300), BLACK);
+    }
+
+    #[test]
+    fn the_landmarks_are_marked_when_asked_for() {
+        let mut image = blank(1000, 600);
+
+        annotate_face(&mut image, 0, &face(0.1, 0.25, 0.2, 0.5), &font(), true);
+
+        assert_eq!(image.get_pixel(200, 300), FACE_COLORS[0]);
+    }
+
+    #[test]
+    fn the_labels_go_left_of_a_box_at_the_right_edge() {
+        let mut image = blank(800, 400);
+
+        annotate_face(&mut image, 2, &face(0.8, 0.1, 0.2, 0.3), &font(), false);
+
+        assert_eq!(image.get_pixel(640, 100), FACE_COLORS[2]);
+        assert!(has_color(&image, 0..630, FACE_COLORS[2]));
+    }
+
+    #[test]
+    fn a_box_reaching_past_the_image_is_clipped() {
+        let mut image = blank(200, 100);
+
+        annotate_face(&mut image, 6, &face(-0.2, 0.9, 1.5, 0.5), &font(), true);
+
+        // The colors repeat once every color is used.
+        assert_eq!(image.get_pixel(0, 90), FACE_COLORS[0]);
+    }
+
+    fn text(text_type: &str, corners: &[(f32, f32)]) -> TextSummary {
+        TextSummary {
+            text: "Hello".to_string(),
+            text_type: text_type.to_string(),
+            confidence: 99.0,
+            polygon: corners.iter().map(|&(x, y)| Point { x, y }).collect(),
+        }
+    }
+
+    fn is_drawn(image: &DynamicImage, ys: std::ops::Range<u32>) -> bool {
+        ys.flat_map(|y| (0..image.width()).map(move |x| (x, y)))
+            .any(|(x, y)| image.get_pixel(x, y) != BLACK)
+    }
+
+    #[test]
+    fn lines_and_words_are_outlined_in_their_own_colors() {
+        let mut image = blank(1000, 500);
+        let corners = [(0.1, 0.5), (0.9, 0.5), (0.9, 0.6), (0.1, 0.6)];
+
+        overlay_text(&mut image, &text("LINE", &corners), &font());
+
+        // The polygon is scaled to the image, and the label goes above a line.
+        assert_eq!(image.get_pixel(500, 250), Rgba([0, 90, 255, 255]));
+        assert_eq!(image.get_pixel(500, 275), BLACK);
+        assert!(is_drawn(&image, 0..248));
+
+        let mut image = blank(1000, 500);
+        overlay_text(&mut image, &text("WORD", &corners), &font());
+
+        assert_eq!(image.get_pixel(500, 300), Rgba([255, 0, 0, 255]));
+        assert!(is_drawn(&image, 303..500));
+    }
+
+    #[test]
+    fn a_rotated_polygon_is_drawn_corner_to_corner() {
+        let mut image = blank(400, 400);
+        let corners = [(0.5, 0.1), (0.9, 0.5), (0.5, 0.9), (0.1, 0.5)];
+
+        overlay_text(&mut image, &text("WORD", &corners), &font());
+
+        assert_eq!(image.get_pixel(280, 120), Rgba([255, 0, 0, 255]));
+        // The bounding box corner is left alone.
+        assert_eq!(image.get_pixel(360, 40), BLACK);
+    }
+
+    #[test]
+    fn a_text_without_a_polygon_is_not_drawn() {
+        let mut image = blank(100, 100);
+
+        overlay_text(&mut image, &text("LINE", &[]), &font());
+
+        assert!(!has_color(&image, 0..100, Rgba([0, 90, 255, 255])));
+    }
+
+    fn bounding_box(left: f32, top: f32, width: f32, height: f32) -> BoundingBox {
+        BoundingBox {
+            width,
+            height,
+            left,
+            top,
+        }
+    }
+
+    #[test]
+    fn the_crop_area_is_clamped_to_the_image() {
+        assert_eq!(
+            crop_area(&bounding_box(0.25, 0.5, 0.5, 0.25), (200, 100)),
+            Some((50, 50, 100, 25))
+        );
+        // A box reaching past the image is cut at its edges.
+        assert_eq!(
+            crop_area(&bounding_box(-0.1, 0.8, 0.5, 0.5), (200, 100)),
+            Some((0, 80, 80, 20))
+        );
+        assert_eq!(
+            crop_area(&bounding_box(1.2, 0.5, 0.3, 0.3), (200, 100)),
+            None
+        );
+    }
+
+    #[test]
+    fn celebrities_are_annotated_and_cropped_with_an_index() {
+        let dir = TempDir::new().unwrap();
+        let image_path = dir.path().join("stars.png");
+        blank(200, 100).save(&image_path).unwrap();
+        let visuals = CelebrityVisuals {
+            annotate: true,
+            crop_faces: true,
+        };
+        let faces = [
+            CelebrityFace {
+                name: Some("Jane Doe".to_string()),
+                match_confidence: Some(99.5),
+                bounding_box: Some(bounding_box(0.1, 0.2, 0.2, 0.4)),
+                ..CelebrityFace::default()
+            },
+            CelebrityFace {
+                bounding_box: Some(bounding_box(0.9, 0.9, 0.5, 0.5)),
+                ..CelebrityFace::default()
+            },
+            CelebrityFace::default(),
+        ];
+        let source = Source::Local(image_path.display().to_string());
+
+        let mut writer = visuals.writer(dir.path()).unwrap();
+        writer.add(&source, &image_path, &faces).unwrap();
+        writer.finish().unwrap();
+
+        let annotated = image::open(dir.path().join("celebrity_images/stars.png")).unwrap();
+        assert_eq!(annotated.get_pixel(20, 40), Rgba([0, 200, 0, 255]));
+        assert_eq!(annotated.get_pixel(180, 90), Rgba([255, 190, 0, 255]));
+        let crop = image::open(dir.path().join("celebrity_faces/stars_face1.png")).unwrap();
+        assert_eq!(crop.dimensions(), (40, 40));
+        let crop = image::open(dir.path().join("celebrity_faces/stars_face2.png")).unwrap();
+        assert_eq!(crop.dimensions(), (20, 10));
+        let index: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(dir.path().join("celebrity_faces/index.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(index.as_array().unwrap().len(), 2);
+        assert_eq!(index[0]["crop"], "stars_face1.png");
+        assert_eq!(index[0]["name"], "Jane Doe");
+    }
+
+    const WHITE: Rgba<u8> = Rgba([255, 255, 255, 255]);
+
+    /// Alternating black and white pixels, which any redaction evens out.
+    fn checkered(width: u32, height: u32) -> DynamicImage {
+        DynamicImage::ImageRgba8(RgbaImage::from_fn(width, height, |x, y| {
+            match (x + y) % 2 {
+                0 => BLACK,
+                _ => Rgba([255, 255, 255, 255]),
+            }
+        }))
+    }
+
+    #[test]
+    fn every_redaction_covers_the_grown_face_and_nothing_else() {
+        let face = bounding_box(0.25, 0.25, 0.5, 0.5);
+        for redaction in [Redaction::Blur, Redaction::Pixelate, Redaction::Box] {
+            let original = checkered(200, 200);
+            let mut image = original.clone();
+
+            redaction.apply(&mut image, &face);
+
+            // The box is grown by a tenth of its size, to 40..160.
+            // The white pixels are all darkened.
+            for (x, y) in [(45, 46), (100, 101), (150, 151)] {
+                assert_ne!(image.get_pixel(x, y), WHITE, "({x}, {y})");
+            }
+            for (x, y) in [(30, 30), (100, 170), (170, 100)] {
+                assert_eq!(
+                    image.get_pixel(x, y),
+                    original.get_pixel(x, y),
+                    "({x}, {y})"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn a_box_only_redacts_within_the_image() {
+        let mut image = checkered(100, 100);
+
+        Redaction::Box.apply(&mut image, &bounding_box(0.9, -0.2, 0.5, 0.5));
+
+        assert_eq!(image.get_pixel(99, 0), BLACK);
+        assert_eq!(image.get_pixel(99, 1), BLACK);
+        assert_eq!(image.get_pixel(80, 1), WHITE);
+    }
+
+    #[test]
+    fn a_face_outside_of_the_image_is_left_alone() {
+        let original = checkered(100, 100);
+        let mut image = original.clone();
+
+        Redaction::Pixelate.apply(&mut image, &bounding_box(1.5, 0.2, 0.3, 0.3));
+
+        assert_eq!(image.to_rgba8(), original.to_rgba8());
+    }
+}
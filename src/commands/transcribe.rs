@@ -0,0 +1,392 @@
+use async_trait::async_trait;
+use colored::Colorize;
+use inquire::Text;
+use serde_json::{json, Map, Value};
+use std::fs::{read_to_string, File};
+use std::io::Write;
+
+use super::{jobs::pick_job, run_with, Clients, Operation, ProfileDefault};
+use crate::error::{Result, WithPath};
+use crate::ledger::{JobKind, Ledger};
+use crate::output::Results;
+use crate::services::TranscriptionState;
+
+pub struct StartTranscriptionJob {
+    pub bucket_name: String,
+    pub key_audio_name: String,
+    pub media_format: String,
+    pub job_name: String,
+}
+
+#[async_trait(?Send)]
+impl Operation for StartTranscriptionJob {
+    type Output = ();
+
+    async fn gather(clients: &Clients) -> Result<Option<Self>> {
+        let get_bucket_lists = clients.s3.get_buckets().await?;
+        let existing_buckets = format!(
+            "These buckets are already in your account: {:#?}",
+            get_bucket_lists
+        );
+        let bucket_name = Text::new("Please enter the output bucket name, where the task's output is stored upon completion\n")
+            .with_placeholder(&existing_buckets)
+            .with_help_message("The name must begin with a lowercase letter and should be unique\nAn AWS bucket is a type of object storage designed for storing objects")
+            .with_formatter(&|str| format!("Choosen Bucket Is: {str}"))
+            .with_profile_default(&clients.profile.bucket)
+            .prompt()?;
+        if bucket_name.is_empty() {
+            println!("{}\n", "Bucket Name Can't be emty".red().bold());
+            return Ok(None);
+        }
+        let valid_formats = "  mp3 |  mp4  |  wav  |  flac  |  ogg  |  amr  | webm  ";
+        let media_format = Text::new("Choose the media format of your audio source\n")
+            .with_placeholder(valid_formats)
+            .with_formatter(&|str| format!(".....{str}.....\n"))
+            .prompt()?;
+        let object_names = clients.s3.retrieve_keys_in_a_bucket(&bucket_name).await?;
+        let available_object_names = format!(
+            "The object names are in the {bucket_name} bucket and the URL should begin with: s3://{bucket_name}/ \n{}\n",
+            object_names.join("\n")
+        );
+        let format_of_s3_url = format!("Add the object key after this path: s3://{bucket_name}/");
+        let initial_value = format!("s3://{bucket_name}/");
+        let key_audio_name =
+            Text::new("Enter the S3 key that contains the audio content you wish to transcribe\n")
+                .with_placeholder(&available_object_names)
+                .with_initial_value(&initial_value)
+                .with_formatter(&|str| format!(".....{str}.....\n"))
+                .with_help_message(&format_of_s3_url)
+                .prompt()?;
+        println!("{}","Make sure to create a unique name for each transcription task, as the file is generated based on the job name".yellow().bold());
+        println!("{}\n\n"," If the same name is used for the next task, it will overwrite the content in the same bucket, potentially causing the JSON parser to fail".yellow().bold());
+        let job_name = Text::new("Provide a unique, identifiable job name which will later be used to retrieve the transcription results\n")
+            .with_formatter(&|str| format!(".....{str}.....\n"))
+            .prompt()?;
+        match (
+            media_format.is_empty(),
+            key_audio_name.is_empty(),
+            job_name.is_empty(),
+        ) {
+            (false, false, false) => Ok(Some(Self {
+                bucket_name,
+                key_audio_name,
+                media_format,
+                job_name,
+            })),
+            _ => {
+                println!("{}\n", "Fields Can't be empty".red().bold());
+                Ok(None)
+            }
+        }
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<()> {
+        clients
+            .transcribe
+            .start_transcribe_task(
+                &self.bucket_name,
+                &self.key_audio_name,
+                &self.media_format,
+                &self.job_name,
+            )
+            .await?;
+        Ledger::record(
+            JobKind::Transcription,
+            &self.job_name,
+            &[
+                ("bucket", &self.bucket_name),
+                ("media", &self.key_audio_name),
+                ("format", &self.media_format),
+            ],
+            Some("IN_PROGRESS"),
+        )?;
+        match clients.settings.wait {
+            Some(_) => {
+                let job_name = self.job_name.clone();
+                run_with(GetTranscriptionJob { job_name }, clients).await
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// Prompts for the name given to a transcription job when it was started,
+/// offering the recorded jobs first.
+fn gather_job_name(message: &str) -> Result<Option<String>> {
+    let job_name = pick_job(
+        JobKind::Transcription,
+        Text::new(message)
+            .with_placeholder("You assigned the job name when initiating the transcription task")
+            .with_formatter(&|str| format!(".....{str}.....\n")),
+    )?;
+    match job_name.is_empty() {
+        false => Ok(Some(job_name)),
+        true => {
+            println!("{}\n", "Job name can't be empty".red().bold());
+            Ok(None)
+        }
+    }
+}
+
+/// Prints the details Amazon Transcribe reports for a completed job.
+fn print_transcription_info(job_name: &str, state: &TranscriptionState) {
+    let details = [
+        ("Job Name", Some(job_name)),
+        ("Language Code", state.language_code.as_deref()),
+        ("Media Format", state.media_format.as_deref()),
+        ("Media File URI", state.media_uri.as_deref()),
+        ("Transcript File URI", state.transcript_uri.as_deref()),
+        ("Creation Time", state.creation_time.as_deref()),
+        ("Completion Time", state.completion_time.as_deref()),
+    ];
+    for (label, value) in details {
+        if let Some(value) = value {
+            println!("{label}: {}", value.green().bold());
+        }
+    }
+    println!();
+}
+
+/// Fetches the state of a transcription job, waiting for it when that is on.
+async fn fetch_transcription(
+    clients: &Clients,
+    job_name: &str,
+) -> Result<Option<TranscriptionState>> {
+    clients
+        .fetch_job(
+            job_name,
+            &["QUEUED", "IN_PROGRESS"],
+            |state: &Option<TranscriptionState>| {
+                state.as_ref().and_then(|state| state.status.as_deref())
+            },
+            || clients.transcribe.get_transcribe_results(job_name),
+        )
+        .await
+}
+
+pub struct GetTranscriptionJob {
+    pub job_name: String,
+}
+
+#[async_trait(?Send)]
+impl Operation for GetTranscriptionJob {
+    type Output = Option<TranscriptionState>;
+
+    async fn gather(_: &Clients) -> Result<Option<Self>> {
+        let job_name = gather_job_name("Please enter the job name to retrieve the results of the transcription task's initiation\n")?;
+        Ok(job_name.map(|job_name| Self { job_name }))
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<Option<TranscriptionState>> {
+        let Some(state) = fetch_transcription(clients, &self.job_name).await? else {
+            return Ok(None);
+        };
+        Ledger::update_status(
+            JobKind::Transcription,
+            &self.job_name,
+            state.status.as_deref(),
+        )?;
+        Ok(Some(state))
+    }
+
+    fn results(&self, state: &Option<TranscriptionState>) -> Result<Option<Results>> {
+        Results::new("transcription_job", state.as_slice()).map(Some)
+    }
+
+    /// Prints the transcription information once the job is completed.
+    fn render(&self, state: Option<TranscriptionState>) -> Result<()> {
+        let Some(state) = state else {
+            return Ok(());
+        };
+        let Some(status) = state.status.as_deref() else {
+            return Ok(());
+        };
+        match status {
+            "COMPLETED" => {
+                println!("{}\n", "The job Status is COMPLETED\n".green().bold());
+                print_transcription_info(&self.job_name, &state);
+                println!("{}\n","Alternatively Execute 'Download Transcription Results' to download all the files generated in 'Get Transcription Task' without leaving the Application".yellow().bold());
+            }
+            _ => {
+                println!("{}","Execute the 'Transcription Status' option to check the status of the transcription task".yellow().bold());
+                println!(
+                    "{} '{}'\n",
+                    "You will receive the output only when the status is"
+                        .yellow()
+                        .bold(),
+                    "COMPLETED".green().bold()
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct TranscriptionStatus {
+    pub job_name: String,
+}
+
+#[async_trait(?Send)]
+impl Operation for TranscriptionStatus {
+    type Output = Option<TranscriptionState>;
+
+    async fn gather(_: &Clients) -> Result<Option<Self>> {
+        let job_name = gather_job_name("Please enter the job name to display its status\n")?;
+        Ok(job_name.map(|job_name| Self { job_name }))
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<Option<TranscriptionState>> {
+        let state = fetch_transcription(clients, &self.job_name).await?;
+        if let Some(state) = &state {
+            Ledger::update_status(
+                JobKind::Transcription,
+                &self.job_name,
+                state.status.as_deref(),
+            )?;
+        }
+        Ok(state)
+    }
+
+    fn results(&self, state: &Option<TranscriptionState>) -> Result<Option<Results>> {
+        Results::new("transcription_job", state.as_slice()).map(Some)
+    }
+
+    fn render(&self, state: Option<TranscriptionState>) -> Result<()> {
+        let Some(state) = state else {
+            return Ok(());
+        };
+        let Some(status) = state.status else {
+            return Ok(());
+        };
+        match status.as_str() {
+            "COMPLETED" => {
+                println!("{}\n", "The job Status is COMPLETED".green().bold());
+                println!("{}\n","Now, you can go ahead and execute the 'Get Transcribe Job' option to obtain the result".green().bold());
+            }
+            "QUEUED" => {
+                println!("{}\n", "The job Status is QUEUED".yellow().bold());
+                println!("{}\n", "Let's try again after some time".yellow().bold());
+            }
+            "IN_PROGRESS" => {
+                println!("{}\n", "The job Status is IN_PROGRESS".yellow().bold());
+                println!("{}\n", "Let's try again after some time".yellow().bold());
+            }
+            "FAILED" => {
+                println!("{}\n", "The job Status is FAILED".yellow().bold());
+                println!(
+                    "Failed Reason: {}\n",
+                    state
+                        .failure_reason
+                        .as_deref()
+                        .unwrap_or("No Failure Reason Is Available")
+                        .yellow()
+                        .bold()
+                );
+            }
+            _ => println!("This can't be reached"),
+        }
+        Ok(())
+    }
+}
+
+pub struct DownloadTranscriptionResults {
+    pub bucket_name: String,
+}
+
+#[async_trait(?Send)]
+impl Operation for DownloadTranscriptionResults {
+    type Output = ();
+
+    async fn gather(clients: &Clients) -> Result<Option<Self>> {
+        let get_buckets = clients.s3.get_buckets().await?;
+        let available_buckets = format!("Available buckets in your account:\n{:#?}\n", get_buckets);
+        let bucket_name = Text::new("Please enter the bucket name where the 'Start Transcription Job' was initiated\n")
+            .with_placeholder(&available_buckets)
+            .with_formatter(&|str| format!(".....{str}.....\n"))
+            .with_help_message("The bucket name should be the same as where the 'Start Transcription Job' was initiated,\n  as the key path is used to download the content without requiring manual input")
+            .with_profile_default(&clients.profile.bucket)
+            .prompt()?;
+        match bucket_name.is_empty() {
+            false => Ok(Some(Self { bucket_name })),
+            true => {
+                println!("{}\n", "Bucket Name can't be empty".red().bold());
+                Ok(None)
+            }
+        }
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<()> {
+        let run_dir = clients.run_dir("transcription_results")?;
+        clients
+            .s3
+            .download_transcription_results(&self.bucket_name, &run_dir)
+            .await?;
+        println!(
+            "{} '{}'\n",
+            "The transcription results are downloaded to".green().bold(),
+            run_dir.display().to_string().green().bold()
+        );
+        Ok(())
+    }
+}
+
+pub struct ExtractTranscript {
+    pub json_path: String,
+}
+
+#[async_trait(?Send)]
+impl Operation for ExtractTranscript {
+    type Output = Vec<String>;
+
+    async fn gather(_: &Clients) -> Result<Option<Self>> {
+        let json_path = Text::new("Please provide the path to the JSON file you downloaded either from 'Download Transcription Results' or manually from the web console\n")
+            .with_placeholder("Do not pass any JSON data; this is meant to parse data specific to the transcript JSON file\n")
+            .prompt()?;
+        match json_path.is_empty() {
+            false => Ok(Some(Self { json_path })),
+            true => Ok(None),
+        }
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<Vec<String>> {
+        let json_data = read_to_string(&self.json_path).with_path(&self.json_path)?;
+        let missing_key = |key: &str| -> serde_json::Error {
+            serde::de::Error::custom(format!("the '{key}' key should exist"))
+        };
+
+        let parsed_data: Value = serde_json::from_str(&json_data).with_path(&self.json_path)?;
+        let transcripts: &Map<String, Value> = parsed_data["results"]
+            .as_object()
+            .ok_or_else(|| missing_key("results"))
+            .with_path(&self.json_path)?;
+        let mut texts = Vec::new();
+        if let Some(transcript) = transcripts["transcripts"].as_array() {
+            for data in transcript {
+                let transcript = data["transcript"]
+                    .as_str()
+                    .ok_or_else(|| missing_key("transcript"))
+                    .with_path(&self.json_path)?;
+                texts.push(transcript.to_string());
+            }
+        }
+        let transcript_path = clients.run_dir("transcript")?.join("transcript.txt");
+        let mut file = File::create(&transcript_path).with_path(&transcript_path)?;
+        for transcript in &texts {
+            file.write_all(transcript.as_bytes())
+                .with_path(&transcript_path)?;
+        }
+        println!(
+            "The transcript has been successfully written to '{}'\n",
+            transcript_path.display().to_string().green().bold()
+        );
+        Ok(texts)
+    }
+
+    fn results(&self, transcripts: &Vec<String>) -> Result<Option<Results>> {
+        let records: Vec<Value> = transcripts
+            .iter()
+            .map(|transcript| json!({ "file": self.json_path, "transcript": transcript }))
+            .collect();
+        Results::new("transcripts", &records).map(Some)
+    }
+}
@@ -0,0 +1,404 @@
+use async_trait::async_trait;
+use colored::Colorize;
+use inquire::Text;
+use std::fs::{read_to_string, write};
+use std::path::Path;
+
+use super::{jobs::pick_job, run_with, Clients, Operation, ProfileDefault};
+use crate::error::{Result, WithPath};
+use crate::ledger::{JobKind, Ledger};
+use crate::output::Results;
+use crate::services::{Language, Translation, TranslationJob};
+
+/// Lists the supported languages as 'name: code' pairs for the target language placeholders.
+async fn language_placeholder(clients: &Clients) -> Result<String> {
+    let languages = clients.translate.list_languages(false).await?;
+    let mut placeholder_info = Vec::new();
+    for language in languages {
+        let format_lang_code_and_name = format!("{}: {}", language.name, language.code);
+        placeholder_info.push(format_lang_code_and_name);
+    }
+    Ok(placeholder_info.join(" | "))
+}
+
+pub struct LanguageInfo;
+
+#[async_trait(?Send)]
+impl Operation for LanguageInfo {
+    type Output = Vec<Language>;
+
+    async fn gather(_: &Clients) -> Result<Option<Self>> {
+        Ok(Some(Self))
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<Vec<Language>> {
+        clients.translate.list_languages(true).await
+    }
+
+    fn results(&self, languages: &Vec<Language>) -> Result<Option<Results>> {
+        Results::new("languages", languages).map(Some)
+    }
+}
+
+pub struct TranslateText {
+    pub text_path: String,
+    pub target_lang_code: String,
+}
+
+#[async_trait(?Send)]
+impl Operation for TranslateText {
+    type Output = Translation;
+
+    async fn gather(clients: &Clients) -> Result<Option<Self>> {
+        let text_path = Text::new("Please provide the path to the text file for which you would like the translation\n")
+            .with_placeholder("The provided text should only be in plain text format; no other formats should be used in this option\n")
+            .with_formatter(&|input| format!("Received Text Path: {}\n", input))
+            .prompt()?;
+        let placeholder_info = language_placeholder(clients).await?;
+        let target_lang_code = Text::new("Provide the target language code for which you want to receive the translation in return\n")
+            .with_placeholder(&placeholder_info)
+            .with_help_message("Copy the target language code from the placeholder that corresponds to the language name for which you want a translation")
+            .with_formatter(&|input| {
+                format!("Received Target Language Code to Translate: {}\n", input)
+            })
+            .with_profile_default(&clients.profile.translate_target)
+            .prompt()?;
+        match (text_path.is_empty(), target_lang_code.is_empty()) {
+            (false, false) => Ok(Some(Self {
+                text_path,
+                target_lang_code,
+            })),
+            _ => {
+                println!("{}\n", "Ensure that no fields are left empty".red().bold());
+                Ok(None)
+            }
+        }
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<Translation> {
+        let text = read_to_string(&self.text_path).with_path(&self.text_path)?;
+        clients
+            .translate
+            .translate_text(&text, &self.target_lang_code)
+            .await
+    }
+
+    fn results(&self, translation: &Translation) -> Result<Option<Results>> {
+        Results::new("translations", std::slice::from_ref(translation)).map(Some)
+    }
+
+    fn render(&self, translation: Translation) -> Result<()> {
+        println!(
+            "Translated from '{}' to '{}':\n{}\n",
+            translation.source_language_code.green().bold(),
+            translation.target_language_code.green().bold(),
+            translation.translated_text
+        );
+        Ok(())
+    }
+}
+
+pub struct TranslateDocument {
+    pub document_type: String,
+    pub document_path: String,
+    pub target_lang_code: String,
+}
+
+#[async_trait(?Send)]
+impl Operation for TranslateDocument {
+    type Output = ();
+
+    async fn gather(clients: &Clients) -> Result<Option<Self>> {
+        let document_type = Text::new("Please specify the type of document for which you need translation\n")
+            .with_placeholder("Valid Document Types\n   'Plain' - Plain Text Document\n   'Word' -Word Document\n   'Html' -Html Document\n")
+            .with_formatter(&|input| format!("Received Document Type: {}\n", input))
+            .with_help_message("Copy the document type inside the single quotes depending on the document you provide next")
+            .prompt()?;
+        let document_path = Text::new("Please provide the path to the Document file for which you would like the translation\n")
+            .with_placeholder("The format of the document should reflect the type of document you provided above\n")
+            .with_formatter(&|input| format!("Received Document Path: {}\n", input))
+            .prompt()?;
+        let placeholder_info = language_placeholder(clients).await?;
+        let target_lang_code = Text::new("Provide the target language code for which you want to receive the translation in return\n")
+            .with_placeholder(&placeholder_info)
+            .with_help_message("Copy the target language code from the placeholder that corresponds to the language name for which you want a translation")
+            .with_formatter(&|input| {
+                format!("Received Target Language Code to Translate: {}\n", input)
+            })
+            .with_profile_default(&clients.profile.translate_target)
+            .prompt()?;
+        match (
+            document_type.is_empty(),
+            document_path.is_empty(),
+            target_lang_code.is_empty(),
+        ) {
+            (false, false, false) => Ok(Some(Self {
+                document_type,
+                document_path,
+                target_lang_code,
+            })),
+            _ => {
+                println!("{}\n", "Ensure that no fields are left empty".red().bold());
+                Ok(None)
+            }
+        }
+    }
+
+    /// Writes the translation as '<document name>_<target language code>' with
+    /// the extension of the document.
+    async fn execute(&self, clients: &Clients) -> Result<()> {
+        let translated = clients
+            .translate
+            .translate_document(
+                &self.document_type,
+                &self.document_path,
+                &self.target_lang_code,
+            )
+            .await?;
+        let document_path = Path::new(&self.document_path);
+        let file_stem = document_path
+            .file_stem()
+            .map(|file_stem| file_stem.to_string_lossy())
+            .unwrap_or_default();
+        let mut file_name = format!("{file_stem}_{}", self.target_lang_code);
+        if let Some(extension) = document_path.extension() {
+            file_name.push('.');
+            file_name.push_str(&extension.to_string_lossy());
+        }
+        let translated_path = clients.run_dir("translated_document")?.join(file_name);
+        write(&translated_path, translated).with_path(&translated_path)?;
+        println!(
+            "The translated document has been written to '{}'\n",
+            translated_path.display().to_string().green().bold()
+        );
+        Ok(())
+    }
+}
+
+pub struct StartTextTranslationJob {
+    pub job_name: String,
+    pub document_type: String,
+    pub input_s3_uri: String,
+    pub target_lang_codes: Vec<String>,
+    pub output_s3_uri: String,
+    pub role_arn: String,
+}
+
+#[async_trait(?Send)]
+impl Operation for StartTextTranslationJob {
+    /// The job ID, unless the job was waited for and its details are already shown.
+    type Output = Option<String>;
+
+    async fn gather(clients: &Clients) -> Result<Option<Self>> {
+        let job_name =
+            Text::new("Please choose a unique job name that describes the translation task\n")
+                .with_placeholder("You are responsible for selecting unique descriptive job name\n")
+                .with_formatter(&|input| format!("Received Job Name: {}\n", input))
+                .prompt()?;
+        let document_type = Text::new("Specify the document type you have in the S3 bucket\n")
+            .with_placeholder("Valid Document Types\n   'Plain' - Plain Text File\n   'Word' -Word Document\n   'Html' -Html Document\n   'Ppt' -PPT Document\n   'Xlsx' -XLSX Document\n   'Xlf' -Lossless XLF Document\n")
+            .with_formatter(&|input| format!("Received Document Type: {}\n", input))
+            .with_help_message("You have the option to batch-translate files of the same format. If you need to translate different formats, please start a new job")
+            .prompt()?;
+        let placeholder_info = language_placeholder(clients).await?;
+        let get_bucket_lists = clients.s3.get_buckets().await?;
+        let existing_buckets = format!(
+            "These buckets are already in your account:\n{:#?}\n",
+            get_bucket_lists
+        );
+        let input_s3_uri = Text::new("Specify the bucket path prefix where all documents of the selected format are stored\n")
+            .with_placeholder(&existing_buckets)
+            .with_initial_value("s3://your_bucket_name/folder_name_which_contains_multiple_document_files")
+            .with_help_message("Inside the bucket's path prefix folder, you can have nested subfolders and multiple documents of the same type, each with different content")
+            .with_formatter(&|input| format!("Received Input S3 URI: {}\n", input))
+            .prompt()?;
+        let target_lang_codes = Text::new("You can specify up to 10 target language codes.To specify multiple codes, use single space to separate the target language codes\n")
+            .with_placeholder(&placeholder_info)
+            .with_help_message("First, copy the language code from the placeholder, write multiple language codes with spaces somewhere, and then paste them here without quotation marks")
+            .with_formatter(&|input| format!("Received Target Language Codes: {}\n", input))
+            .with_profile_default(&clients.profile.translate_target)
+            .prompt()?;
+        let output_s3_uri = Text::new("Please provide the output S3 path prefix URL where all translation results will be stored\n")
+            .with_placeholder(&existing_buckets)
+            .with_help_message("It should be in the format ---s3://bucket_name/new_folder_name---")
+            .with_formatter(&|input| format!("Received Output S3 URI: {}\n", input))
+            .prompt()?;
+        let role_arn = Text::new("Please provide the Data Access Role ARN that grants Amazon Translate read access to your S3 input data\n")
+            .with_placeholder("An example of what it should look like is: ---arn:aws:iam::account_id:role/role_name---\n")
+            .with_help_message("Click here to learn more")
+            .with_formatter(&|input| format!("Received Data Access Role Arn: {}\n", input))
+            .prompt()?;
+        match (
+            job_name.is_empty(),
+            target_lang_codes.is_empty(),
+            input_s3_uri.is_empty(),
+            document_type.is_empty(),
+            output_s3_uri.is_empty(),
+            role_arn.is_empty(),
+        ) {
+            (false, false, false, false, false, false) => Ok(Some(Self {
+                job_name,
+                document_type,
+                input_s3_uri,
+                target_lang_codes: target_lang_codes.split(' ').map(String::from).collect(),
+                output_s3_uri,
+                role_arn,
+            })),
+            _ => {
+                println!("{}\n", "Ensure that no fields are left empty".red().bold());
+                Ok(None)
+            }
+        }
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<Option<String>> {
+        let job_id = clients
+            .translate
+            .start_text_translation_job(
+                &self.job_name,
+                self.target_lang_codes.clone(),
+                &self.input_s3_uri,
+                &self.document_type,
+                &self.output_s3_uri,
+                &self.role_arn,
+            )
+            .await?;
+        Ledger::record(
+            JobKind::TextTranslation,
+            &job_id,
+            &[
+                ("job name", &self.job_name),
+                ("document type", &self.document_type),
+                ("targets", &self.target_lang_codes.join(" ")),
+                ("input", &self.input_s3_uri),
+                ("output", &self.output_s3_uri),
+            ],
+            Some("SUBMITTED"),
+        )?;
+        println!("Job ID: {}", job_id.green().bold());
+        if clients.settings.wait.is_none() {
+            return Ok(Some(job_id));
+        }
+        run_with(DescribeTextTranslationJob { job_id }, clients).await?;
+        Ok(None)
+    }
+
+    fn render(&self, job_id: Option<String>) -> Result<()> {
+        if job_id.is_some() {
+            println!("{}\n","The job is recorded, so 'Describe Text Translation Job' offers it without typing the ID".yellow().bold());
+        }
+        Ok(())
+    }
+}
+
+pub struct DescribeTextTranslationJob {
+    pub job_id: String,
+}
+
+#[async_trait(?Send)]
+impl Operation for DescribeTextTranslationJob {
+    type Output = TranslationJob;
+
+    async fn gather(_: &Clients) -> Result<Option<Self>> {
+        println!();
+        println!("{}\n","If you are unable to access the Job ID at all, then exit this operation with empty input.\nAfterward, execute the ---List Text Translation Jobs--- option, which will provide all the Job information without requiring any input from you".yellow().bold());
+        let job_id = pick_job(
+            JobKind::TextTranslation,
+            Text::new("To obtain details of the Translation Job, please enter the Job ID\n")
+                .with_placeholder("The Job ID is generated when you execute the ---Start Text Translation Job--- option\n")
+                .with_formatter(&|input| format!("Received Job ID: {}\n", input)),
+        )?;
+        match job_id.is_empty() {
+            false => Ok(Some(Self { job_id })),
+            true => {
+                println!("{}\n", "Ensure that no fields are left empty".red().bold());
+                Ok(None)
+            }
+        }
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<TranslationJob> {
+        let job = clients
+            .fetch_job(
+                &self.job_id,
+                &["SUBMITTED", "IN_PROGRESS", "STOP_REQUESTED"],
+                |job: &TranslationJob| job.status.as_deref(),
+                || {
+                    clients
+                        .translate
+                        .describe_text_translation_job(&self.job_id)
+                },
+            )
+            .await?;
+        Ledger::update_status(
+            JobKind::TextTranslation,
+            &self.job_id,
+            job.status.as_deref(),
+        )?;
+        Ok(job)
+    }
+
+    fn results(&self, job: &TranslationJob) -> Result<Option<Results>> {
+        Results::new("translation_job", std::slice::from_ref(job)).map(Some)
+    }
+
+    fn render(&self, job: TranslationJob) -> Result<()> {
+        let unavailable = || "Not available".to_string();
+        println!("Job ID: {}", self.job_id.green().bold());
+        println!(
+            "Job Name: {}",
+            job.job_name.unwrap_or_else(unavailable).green().bold()
+        );
+        println!(
+            "Job Status: {}",
+            job.status.unwrap_or_else(unavailable).green().bold()
+        );
+        println!(
+            "Target Language Codes: {}",
+            job.target_language_codes.join(" ").green().bold()
+        );
+        println!(
+            "Output S3 URI: {}",
+            job.output_s3_uri.unwrap_or_else(unavailable).green().bold()
+        );
+        if let Some(message) = job.message {
+            println!("Message: {}", message.yellow().bold());
+        }
+        println!();
+        Ok(())
+    }
+}
+
+pub struct ListTextTranslationJobs;
+
+#[async_trait(?Send)]
+impl Operation for ListTextTranslationJobs {
+    type Output = Vec<TranslationJob>;
+
+    async fn gather(_: &Clients) -> Result<Option<Self>> {
+        Ok(Some(Self))
+    }
+
+    async fn execute(&self, clients: &Clients) -> Result<Vec<TranslationJob>> {
+        clients.translate.list_translation_jobs().await
+    }
+
+    fn render(&self, jobs: Vec<TranslationJob>) -> Result<()> {
+        if jobs.is_empty() {
+            println!(
+                "{}\n",
+                "No text translation jobs were found".yellow().bold()
+            );
+        }
+        let unavailable = || "Not available".to_string();
+        for job in jobs {
+            println!(
+                "Job ID: {}\nJob Name: {}\nJob Status: {}\n",
+                job.job_id.unwrap_or_else(unavailable).green().bold(),
+                job.job_name.unwrap_or_else(unavailable).green().bold(),
+                job.status.unwrap_or_else(unavailable).green().bold()
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+//! The persistent configuration with its named profiles.
+//!
+//! It is read from `config.toml` in the `dlearningclient` directory of the
+//! platform configuration directory, e.g. `~/.config/dlearningclient/config.toml`:
+//!
+//! ```toml
+//! default_profile = "work"
+//!
+//! [profiles.work]
+//! credentials = "file"
+//! region = "eu-west-1"
+//! bucket = "my-bucket"
+//! bucket_prefix = "images/"
+//! local_prefix = "Images/"
+//! polly_engine = "neural"
+//! polly_voice = "Joanna"
+//! translate_target = "fr"
+//! output_dir = "Outputs/"
+//! ```
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs::read_to_string;
+use std::path::PathBuf;
+
+use crate::error::{Error, Result, WithPath};
+
+#[derive(Default, Deserialize)]
+pub struct Config {
+    /// The profile used when none is chosen on the command line.
+    pub default_profile: Option<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+/// Where the credentials of a profile are loaded from.
+#[derive(Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CredentialSource {
+    /// The AWS configuration file.
+    #[default]
+    File,
+    /// The AWS_* environment variables or a .env file.
+    Env,
+}
+
+/// The region and the defaults pre-filled into the prompts.
+#[derive(Clone, Default, Deserialize)]
+pub struct Profile {
+    #[serde(default)]
+    pub credentials: CredentialSource,
+    /// Overrides the region that comes with the credentials.
+    pub region: Option<String>,
+    pub bucket: Option<String>,
+    pub bucket_prefix: Option<String>,
+    pub local_prefix: Option<String>,
+    pub polly_engine: Option<String>,
+    pub polly_voice: Option<String>,
+    pub translate_target: Option<String>,
+    /// Where the files written by the operations go.
+    pub output_dir: Option<PathBuf>,
+}
+
+impl Config {
+    pub fn path() -> Option<PathBuf> {
+        dirs_next::config_dir().map(|dir| dir.join("dlearningclient").join("config.toml"))
+    }
+
+    /// Loads the configuration file, which is optional.
+    pub fn load() -> Result<Self> {
+        let Some(path) = Self::path() else {
+            return Ok(Self::default());
+        };
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let toml_data = read_to_string(&path).with_path(&path)?;
+        toml::from_str(&toml_data).map_err(|error| Error::Config {
+            path,
+            message: error.to_string(),
+        })
+    }
+
+    /// Looks up a profile, falling back to the default profile when no name is given.
+    pub fn profile(&self, name: Option<&str>) -> Result<Option<(String, Profile)>> {
+        let Some(name) = name.or(self.default_profile.as_deref()) else {
+            return Ok(None);
+        };
+        match self.profiles.get(name) {
+            Some(profile) => Ok(Some((name.to_string(), profile.clone()))),
+            None => Err(Error::UnknownProfile(name.to_string())),
+        }
+    }
+}
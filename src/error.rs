@@ -0,0 +1,135 @@
+//! The errors an operation can end with.
+//!
+//! None of them are fatal for the interactive menu: they are reported and the
+//! user is returned to the submenu with the credentials still loaded.
+use colored::Colorize;
+use inquire::InquireError;
+use std::io;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    /// The prompt was left with Esc or Ctrl+C.
+    #[error("The operation was cancelled")]
+    Cancelled,
+    #[error("Error while reading the input: {0}")]
+    Prompt(InquireError),
+    #[error("Ensure that the '{0}' environment variable is set, and its value is provided by AWS")]
+    MissingEnvVar(&'static str),
+    #[error("The configuration in '{}' is invalid: {message}", path.display())]
+    Config { path: PathBuf, message: String },
+    #[error("There is no profile named '{0}' in the configuration")]
+    UnknownProfile(String),
+    #[error("Give --{flag}, or set '{setting}' in the active profile of the configuration")]
+    MissingArgument {
+        flag: &'static str,
+        setting: &'static str,
+    },
+    #[error("Error while accessing '{}': {source}", path.display())]
+    Io { path: PathBuf, source: io::Error },
+    #[error("Error while decoding the image '{}': {source}", path.display())]
+    Image {
+        path: PathBuf,
+        source: image::ImageError,
+    },
+    #[error("Failed to parse the JSON in '{}': {source}", path.display())]
+    Json {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    #[error("Error while compressing the images: {0}")]
+    Compression(String),
+    #[error("Error while writing the PDF '{}': {message}", path.display())]
+    Pdf { path: PathBuf, message: String },
+    #[error("The glob pattern '{pattern}' is invalid: {message}")]
+    Glob { pattern: String, message: String },
+    #[error("'{}' is not the directory of a '{operation}' run that can be resumed", path.display())]
+    NotResumable { path: PathBuf, operation: String },
+    #[error(
+        "The text is {characters} characters long, more than the {limit} a single request accepts"
+    )]
+    TextTooLong { characters: usize, limit: usize },
+    #[error("The {kind} is invalid at line {line}, column {column}: {message}")]
+    Markup {
+        kind: &'static str,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    #[error("'{0}' isn't a text type, which is either 'ssml' or 'text'")]
+    UnknownTextType(String),
+    #[error("'{0}' isn't a lexicon name, which is 1 to 20 letters and digits")]
+    LexiconName(String),
+    #[error("At most {limit} lexicons can be applied to a request, not {count}")]
+    TooManyLexicons { count: usize, limit: usize },
+    #[error("'{0}' isn't a speech mark type, which is 'sentence', 'word', 'viseme' or 'ssml'")]
+    UnknownSpeechMarkType(String),
+    #[error("The 'json' output format needs the types of the speech marks to write")]
+    MissingSpeechMarkTypes,
+    #[error("The speech mark on line {line} can't be read: {message}")]
+    SpeechMarks { line: usize, message: String },
+    #[error("The service call failed: {0}")]
+    Service(String),
+    #[error("Error while writing the results: {0}")]
+    Output(String),
+    #[error("Gave up waiting for the job '{job}' after {seconds} seconds, its last status was '{status}'")]
+    WaitTimeout {
+        job: String,
+        status: String,
+        seconds: u64,
+    },
+}
+
+impl From<InquireError> for Error {
+    fn from(error: InquireError) -> Self {
+        match error {
+            InquireError::OperationCanceled | InquireError::OperationInterrupted => Self::Cancelled,
+            other => Self::Prompt(other),
+        }
+    }
+}
+
+impl Error {
+    /// Prints the error the same way the menus print their other messages.
+    pub fn report(&self) {
+        match self {
+            Self::Cancelled => println!("{}\n", self.to_string().yellow().bold()),
+            _ => println!("{}\n", self.to_string().red().bold()),
+        }
+    }
+}
+
+/// Attaches the path that was being worked on to I/O, image and JSON errors.
+pub trait WithPath<T> {
+    fn with_path(self, path: impl AsRef<Path>) -> Result<T>;
+}
+
+impl<T> WithPath<T> for std::result::Result<T, io::Error> {
+    fn with_path(self, path: impl AsRef<Path>) -> Result<T> {
+        self.map_err(|source| Error::Io {
+            path: path.as_ref().to_path_buf(),
+            source,
+        })
+    }
+}
+
+impl<T> WithPath<T> for std::result::Result<T, image::ImageError> {
+    fn with_path(self, path: impl AsRef<Path>) -> Result<T> {
+        self.map_err(|source| Error::Image {
+            path: path.as_ref().to_path_buf(),
+            source,
+        })
+    }
+}
+
+impl<T> WithPath<T> for std::result::Result<T, serde_json::Error> {
+    fn with_path(self, path: impl AsRef<Path>) -> Result<T> {
+        self.map_err(|source| Error::Json {
+            path: path.as_ref().to_path_buf(),
+            source,
+        })
+    }
+}
@@ -0,0 +1,125 @@
+//! Writing the static HTML page that the voice samples are compared on.
+//!
+//! The page refers to the samples by their file names, so it's meant to stay
+//! in the directory the samples are written to.
+use serde::{Deserialize, Serialize};
+
+use crate::ssml::escape;
+
+/// A sample of the text spoken by one voice.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct VoiceSample {
+    pub voice_id: String,
+    pub voice_name: String,
+    pub gender: String,
+    pub language_code: String,
+    pub language_name: String,
+    pub engine: String,
+    /// The name of the audio file, next to the page.
+    pub file: String,
+    /// The size of the audio file in bytes.
+    pub size: u64,
+}
+
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em; }
+blockquote { white-space: pre-wrap; background: #f4f4f4; padding: 1em; }
+table { border-collapse: collapse; }
+th, td { border-bottom: 1px solid #ddd; padding: 0.5em 1em; text-align: left; }
+td.size { text-align: right; }";
+
+/// A size such as '12.3 KB'.
+fn readable_size(bytes: u64) -> String {
+    match bytes {
+        0..=1023 => format!("{bytes} B"),
+        1024..=1_048_575 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+/// The page listing every sample with a player, under the text they speak.
+pub fn to_html(text: &str, samples: &[VoiceSample]) -> String {
+    let mut rows = String::new();
+    for sample in samples {
+        // The ID is only shown for the voices whose name is something else.
+        let voice = match sample.voice_name == sample.voice_id {
+            true => escape(&sample.voice_name),
+            false => format!(
+                "{} ({})",
+                escape(&sample.voice_name),
+                escape(&sample.voice_id)
+            ),
+        };
+        rows.push_str(&format!(
+            "<tr><td>{voice}</td><td>{}</td><td>{} ({})</td><td>{}</td>\
+             <td><audio controls preload=\"none\" src=\"{}\"></audio></td>\
+             <td class=\"size\">{}</td></tr>\n",
+            escape(&sample.gender),
+            escape(&sample.language_name),
+            escape(&sample.language_code),
+            escape(&sample.engine),
+            escape(&sample.file),
+            readable_size(sample.size)
+        ));
+    }
+    format!(
+        "<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<title>Amazon Polly voice samples</title>
+<style>
+{STYLE}
+</style>
+</head>
+<body>
+<h1>Amazon Polly voice samples</h1>
+<p>{} voice(s) reading:</p>
+<blockquote>{}</blockquote>
+<table>
+<thead><tr><th>Voice</th><th>Gender</th><th>Language</th><th>Engine</th><th>Sample</th><th>Size</th></tr></thead>
+<tbody>
+{rows}</tbody>
+</table>
+</body>
+</html>
+",
+        samples.len(),
+        escape(text.trim())
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(voice_id: &str, voice_name: &str) -> VoiceSample {
+        VoiceSample {
+            voice_id: voice_id.to_string(),
+            voice_name: voice_name.to_string(),
+            gender: "Female".to_string(),
+            language_code: "en-US".to_string(),
+            language_name: "US English".to_string(),
+            engine: "neural".to_string(),
+            file: format!("{voice_id}.mp3"),
+            size: 2048,
+        }
+    }
+
+    #[test]
+    fn the_text_and_the_samples_are_escaped() {
+        let page = to_html("Tom & \"Jerry\" <3", &[sample("a\"b", "<Joanna> & co")]);
+
+        assert!(page.contains("<blockquote>Tom &amp; &quot;Jerry&quot; &lt;3</blockquote>"));
+        assert!(page.contains("<td>&lt;Joanna&gt; &amp; co (a&quot;b)</td>"));
+        assert!(page.contains("src=\"a&quot;b.mp3\""));
+        assert!(!page.contains("<Joanna>"));
+    }
+
+    #[test]
+    fn the_id_is_only_shown_when_it_is_not_the_name() {
+        let page = to_html("Hi", &[sample("Joanna", "Joanna")]);
+
+        assert!(page.contains("<tr><td>Joanna</td>"));
+        assert!(page.contains("<td class=\"size\">2.0 KB</td>"));
+    }
+}
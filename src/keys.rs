@@ -0,0 +1,139 @@
+//! Mapping local paths to S3 keys and keys back to local file names.
+//!
+//! Keys always use '/' between their directories, whatever the platform the
+//! local paths come from, and the local names are kept valid on Windows as well.
+use std::collections::HashSet;
+
+/// The characters Windows doesn't allow in file names, besides the separators.
+const RESERVED: [char; 7] = ['<', '>', ':', '"', '|', '?', '*'];
+
+/// The segments of a local path or key, with either separator, leaving out
+/// the empty ones, '.' and '..', and a Windows drive such as 'C:'.
+fn segments(path: &str) -> impl Iterator<Item = &str> {
+    path.split(['/', '\\'])
+        .enumerate()
+        .filter(|(index, segment)| {
+            let is_drive = *index == 0 && segment.len() == 2 && segment.ends_with(':');
+            !is_drive && !matches!(*segment, "" | "." | "..")
+        })
+        .map(|(_, segment)| segment)
+}
+
+/// Normalizes a path prefix within a bucket so that it either is empty or ends
+/// with a single '/', such as 'faces/2023/' for '/faces\2023'.
+pub fn normalize_prefix(prefix: &str) -> String {
+    let joined = segments(prefix).collect::<Vec<_>>().join("/");
+    if joined.is_empty() {
+        joined
+    } else {
+        format!("{joined}/")
+    }
+}
+
+/// The key a file is uploaded to, made of the path prefix and its path
+/// relative to the directory it's uploaded from, such as 'sub/a b.png'.
+pub fn key_for(prefix: &str, relative_path: &str) -> String {
+    let relative = segments(relative_path).collect::<Vec<_>>().join("/");
+    format!("{}{relative}", normalize_prefix(prefix))
+}
+
+/// The last segment of a local path or key, keeping its Unicode characters.
+pub fn file_name_of(path: &str) -> &str {
+    segments(path).last().unwrap_or_default()
+}
+
+/// The name a file of this key or path is written locally as, with the
+/// characters Windows doesn't allow replaced by '_'.
+pub fn local_name(key: &str) -> String {
+    let name: String = file_name_of(key)
+        .chars()
+        .map(|c| {
+            if RESERVED.contains(&c) || c.is_control() {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    // Windows drops the trailing dots and spaces of a name.
+    let name = name.trim_end_matches(['.', ' ']);
+    if name.is_empty() {
+        "object".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// Hands out local names that don't collide with the ones already handed out,
+/// numbering the later ones like 'a (2).png'.
+#[derive(Default)]
+pub struct LocalNames {
+    used: HashSet<String>,
+}
+
+impl LocalNames {
+    pub fn unique(&mut self, key: &str) -> String {
+        let name = local_name(key);
+        // Names that only differ in case are the same file on Windows and macOS.
+        if self.used.insert(name.to_lowercase()) {
+            return name;
+        }
+        let (stem, extension) = match name.rsplit_once('.') {
+            Some((stem, extension)) if !stem.is_empty() => (stem, format!(".{extension}")),
+            _ => (name.as_str(), String::new()),
+        };
+        let mut attempt = 2;
+        loop {
+            let candidate = format!("{stem} ({attempt}){extension}");
+            if self.used.insert(candidate.to_lowercase()) {
+                return candidate;
+            }
+            attempt += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_prefix_ends_with_a_single_separator() {
+        assert_eq!(normalize_prefix("/faces\\2023"), "faces/2023/");
+        assert_eq!(normalize_prefix("faces//"), "faces/");
+        assert_eq!(normalize_prefix("/"), "");
+    }
+
+    #[test]
+    fn the_parent_and_current_directories_are_left_out_of_keys() {
+        assert_eq!(key_for("uploads", "../sub/./a.png"), "uploads/sub/a.png");
+        assert_eq!(key_for("", "..\\..\\a.png"), "a.png");
+    }
+
+    #[test]
+    fn a_windows_drive_is_left_out_of_keys() {
+        assert_eq!(key_for("", "C:\\images\\a.png"), "images/a.png");
+        assert_eq!(file_name_of("D:"), "");
+        // Only the first segment is read as a drive.
+        assert_eq!(key_for("", "images/c:/a.png"), "images/c:/a.png");
+    }
+
+    #[test]
+    fn reserved_characters_are_replaced_in_local_names() {
+        assert_eq!(local_name("photos/a<b>:c?.png"), "a_b__c_.png");
+        assert_eq!(local_name("notes/todo. "), "todo");
+        assert_eq!(local_name("/"), "object");
+        assert_eq!(local_name("photos/café.png"), "café.png");
+    }
+
+    #[test]
+    fn names_differing_only_in_case_are_numbered() {
+        let mut names = LocalNames::default();
+
+        assert_eq!(names.unique("a/Photo.png"), "Photo.png");
+        assert_eq!(names.unique("b/photo.png"), "photo (2).png");
+        assert_eq!(names.unique("c/PHOTO.PNG"), "PHOTO (3).PNG");
+        assert_eq!(names.unique("d/.env"), ".env");
+        assert_eq!(names.unique("e/.ENV"), ".ENV (2)");
+    }
+}
@@ -0,0 +1,261 @@
+//! The local record of the asynchronous jobs started by the client.
+//!
+//! It is kept as JSON in `jobs.json` in the `dlearningclient` directory of the
+//! platform data directory, e.g. `~/.local/share/dlearningclient/jobs.json`, so
+//! the "Get…" options can offer the started jobs instead of asking for their IDs.
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{create_dir_all, read_to_string, write};
+use std::path::{Path, PathBuf};
+
+use crate::error::{Result, WithPath};
+
+/// The kinds of asynchronous jobs the client can start.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    SpeechSynthesis,
+    FaceDetection,
+    TextDetection,
+    TextTranslation,
+    Transcription,
+}
+
+impl fmt::Display for JobKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::SpeechSynthesis => "Polly speech synthesis",
+            Self::FaceDetection => "Rekognition face detection",
+            Self::TextDetection => "Rekognition text detection",
+            Self::TextTranslation => "Translate text translation",
+            Self::Transcription => "Transcribe transcription",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct JobRecord {
+    pub kind: JobKind,
+    /// The task or job ID, or the job name for transcriptions.
+    pub id: String,
+    /// The inputs the job was started with, keyed by their name.
+    pub inputs: BTreeMap<String, String>,
+    pub started_at: DateTime<Local>,
+    pub status: Option<String>,
+}
+
+impl fmt::Display for JobRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} | started {} | {}",
+            self.id,
+            self.started_at.format("%Y-%m-%d %H:%M:%S"),
+            self.status.as_deref().unwrap_or("status unknown")
+        )?;
+        for (name, value) in &self.inputs {
+            write!(f, " | {name}: {value}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct Ledger {
+    jobs: Vec<JobRecord>,
+}
+
+impl Ledger {
+    pub fn path() -> Option<PathBuf> {
+        dirs_next::data_dir().map(|dir| dir.join("dlearningclient").join("jobs.json"))
+    }
+
+    /// Loads the ledger, which is empty until the first job is started.
+    pub fn load() -> Result<Self> {
+        match Self::path() {
+            Some(path) => Self::load_from(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let json_data = read_to_string(path).with_path(path)?;
+        serde_json::from_str(&json_data).with_path(path)
+    }
+
+    fn save(&self) -> Result<()> {
+        match Self::path() {
+            Some(path) => self.save_to(&path),
+            None => Ok(()),
+        }
+    }
+
+    fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            create_dir_all(dir).with_path(dir)?;
+        }
+        let json_data = serde_json::to_string_pretty(self).with_path(path)?;
+        write(path, json_data).with_path(path)
+    }
+
+    /// Records a job that has just been started.
+    pub fn record(
+        kind: JobKind,
+        id: &str,
+        inputs: &[(&str, &str)],
+        status: Option<&str>,
+    ) -> Result<()> {
+        let mut ledger = Self::load()?;
+        ledger.insert(JobRecord {
+            kind,
+            id: id.to_string(),
+            inputs: inputs
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            started_at: Local::now(),
+            status: status.map(ToOwned::to_owned),
+        });
+        ledger.save()
+    }
+
+    /// Adds the job, replacing an earlier record of the same job.
+    fn insert(&mut self, record: JobRecord) {
+        self.jobs
+            .retain(|job| !(job.kind == record.kind && job.id == record.id));
+        self.jobs.push(record);
+    }
+
+    /// Updates the last known status of a recorded job, leaving unknown jobs alone.
+    pub fn update_status(kind: JobKind, id: &str, status: Option<&str>) -> Result<()> {
+        let Some(status) = status else {
+            return Ok(());
+        };
+        let mut ledger = Self::load()?;
+        match ledger.set_status(kind, id, status) {
+            true => ledger.save(),
+            false => Ok(()),
+        }
+    }
+
+    /// Returns whether the status of the job has changed.
+    fn set_status(&mut self, kind: JobKind, id: &str, status: &str) -> bool {
+        match self
+            .jobs
+            .iter_mut()
+            .find(|job| job.kind == kind && job.id == id)
+        {
+            Some(job) if job.status.as_deref() != Some(status) => {
+                job.status = Some(status.to_string());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The jobs of the given kind, the most recently started first.
+    pub fn jobs(&self, kind: Option<JobKind>) -> Vec<&JobRecord> {
+        let mut jobs: Vec<&JobRecord> = self
+            .jobs
+            .iter()
+            .filter(|job| kind.is_none() || kind == Some(job.kind))
+            .collect();
+        jobs.sort_by_key(|job| Reverse(job.started_at));
+        jobs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use tempfile::TempDir;
+
+    fn job(kind: JobKind, id: &str, minutes_ago: i64) -> JobRecord {
+        JobRecord {
+            kind,
+            id: id.to_string(),
+            inputs: BTreeMap::from([("bucket".to_string(), "photos".to_string())]),
+            started_at: Local::now() - Duration::minutes(minutes_ago),
+            status: None,
+        }
+    }
+
+    fn ids(jobs: Vec<&JobRecord>) -> Vec<&str> {
+        jobs.into_iter().map(|job| job.id.as_str()).collect()
+    }
+
+    #[test]
+    fn the_jobs_of_a_kind_are_listed_the_most_recent_first() {
+        let mut ledger = Ledger::default();
+        ledger.insert(job(JobKind::FaceDetection, "old", 30));
+        ledger.insert(job(JobKind::TextDetection, "text", 20));
+        ledger.insert(job(JobKind::FaceDetection, "new", 10));
+
+        assert_eq!(
+            ids(ledger.jobs(Some(JobKind::FaceDetection))),
+            ["new", "old"]
+        );
+        assert_eq!(ids(ledger.jobs(None)), ["new", "text", "old"]);
+    }
+
+    #[test]
+    fn recording_a_job_again_replaces_it() {
+        let mut ledger = Ledger::default();
+        ledger.insert(job(JobKind::Transcription, "interview", 10));
+        ledger.insert(job(JobKind::SpeechSynthesis, "interview", 10));
+        ledger.insert(job(JobKind::Transcription, "interview", 0));
+
+        assert_eq!(ledger.jobs(None).len(), 2);
+        let transcriptions = ledger.jobs(Some(JobKind::Transcription));
+        assert!(transcriptions[0].started_at > Local::now() - Duration::minutes(1));
+    }
+
+    #[test]
+    fn only_a_changed_status_of_a_known_job_is_set() {
+        let mut ledger = Ledger::default();
+        ledger.insert(job(JobKind::TextTranslation, "job-1", 0));
+
+        assert!(ledger.set_status(JobKind::TextTranslation, "job-1", "IN_PROGRESS"));
+        assert!(!ledger.set_status(JobKind::TextTranslation, "job-1", "IN_PROGRESS"));
+        assert!(!ledger.set_status(JobKind::TextTranslation, "job-2", "COMPLETED"));
+        assert!(!ledger.set_status(JobKind::Transcription, "job-1", "COMPLETED"));
+        assert_eq!(ledger.jobs(None)[0].status.as_deref(), Some("IN_PROGRESS"));
+    }
+
+    #[test]
+    fn the_ledger_is_saved_and_loaded_again() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("dlearningclient").join("jobs.json");
+        assert!(Ledger::load_from(&path).unwrap().jobs(None).is_empty());
+
+        let mut ledger = Ledger::default();
+        ledger.insert(job(JobKind::SpeechSynthesis, "task-1", 0));
+        ledger.set_status(JobKind::SpeechSynthesis, "task-1", "completed");
+        ledger.save_to(&path).unwrap();
+
+        let loaded = Ledger::load_from(&path).unwrap();
+        let jobs = loaded.jobs(None);
+        assert_eq!(ids(jobs.clone()), ["task-1"]);
+        assert_eq!(jobs[0].status.as_deref(), Some("completed"));
+        assert_eq!(jobs[0].inputs["bucket"], "photos");
+    }
+
+    #[test]
+    fn a_record_lists_its_inputs() {
+        let mut record = job(JobKind::FaceDetection, "job-1", 0);
+        record.status = Some("SUCCEEDED".to_string());
+
+        let line = record.to_string();
+
+        assert!(line.starts_with("job-1 | started "), "{line}");
+        assert!(line.ends_with(" | SUCCEEDED | bucket: photos"), "{line}");
+    }
+}
@@ -0,0 +1,312 @@
+//! Structured results of the operations, written as JSON, JSON Lines or CSV so
+//! that other tools can consume them.
+use clap::ValueEnum;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fs::File;
+use std::io::{stdout, Write};
+use std::path::PathBuf;
+
+use crate::error::{Error, Result, WithPath};
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ResultFormat {
+    /// A single JSON array
+    Json,
+    /// One JSON object per line
+    Jsonl,
+    /// Comma separated values, with nested fields flattened into dotted columns
+    Csv,
+}
+
+impl ResultFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Jsonl => "jsonl",
+            Self::Csv => "csv",
+        }
+    }
+}
+
+/// Where the structured results of the operations are written.
+#[derive(Clone)]
+pub struct ResultOutput {
+    pub format: ResultFormat,
+    /// The file to write, or '-' for the standard output. Without it the results
+    /// go to a file named after them in the current directory.
+    pub path: Option<PathBuf>,
+}
+
+/// A result that belongs to one of the images an operation went through.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ImageRecord<T> {
+    pub image: String,
+    #[serde(flatten)]
+    pub result: T,
+}
+
+/// The records an operation produced, named after what they describe.
+pub struct Results {
+    name: &'static str,
+    records: Vec<Value>,
+}
+
+impl Results {
+    pub fn new<T: Serialize>(name: &'static str, records: &[T]) -> Result<Self> {
+        // Going through the text keeps single precision floats, like confidences,
+        // at their shortest form instead of widening them into noisy doubles.
+        let records = records
+            .iter()
+            .map(|record| {
+                serde_json::to_string(record).and_then(|json| serde_json::from_str(&json))
+            })
+            .collect::<std::result::Result<_, _>>()
+            .map_err(|error| Error::Output(error.to_string()))?;
+        Ok(Self { name, records })
+    }
+
+    pub fn write(&self, output: &ResultOutput) -> Result<()> {
+        let path = output.path.clone().unwrap_or_else(|| {
+            PathBuf::from(format!("{}.{}", self.name, output.format.extension()))
+        });
+        let data = match output.format {
+            ResultFormat::Json => {
+                serde_json::to_string_pretty(&self.records)
+                    .map_err(|error| Error::Output(error.to_string()))?
+                    + "\n"
+            }
+            ResultFormat::Jsonl => self
+                .records
+                .iter()
+                .map(|record| record.to_string() + "\n")
+                .collect(),
+            ResultFormat::Csv => self.to_csv()?,
+        };
+        if path.as_os_str() == "-" {
+            stdout().write_all(data.as_bytes()).with_path("<stdout>")?;
+            return Ok(());
+        }
+        let mut file = File::create(&path).with_path(&path)?;
+        file.write_all(data.as_bytes()).with_path(&path)?;
+        println!(
+            "{} '{}'\n",
+            "The results are written to".green().bold(),
+            path.display().to_string().green().bold()
+        );
+        Ok(())
+    }
+
+    fn to_csv(&self) -> Result<String> {
+        let rows: Vec<Vec<(String, String)>> = self
+            .records
+            .iter()
+            .map(|record| {
+                let mut row = Vec::new();
+                flatten("", record, &mut row);
+                row
+            })
+            .collect();
+        let mut columns: Vec<&str> = Vec::new();
+        for (column, _) in rows.iter().flatten() {
+            if !columns.contains(&column.as_str()) {
+                columns.push(column);
+            }
+        }
+        // A missing nested object shows up as empty cells of its dotted columns.
+        let all_columns = columns.clone();
+        columns.retain(|column| {
+            !all_columns.iter().any(|other| {
+                other
+                    .strip_prefix(column)
+                    .is_some_and(|rest| rest.starts_with('.'))
+            })
+        });
+        let csv_error = |error: csv::Error| Error::Output(error.to_string());
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record(&columns).map_err(csv_error)?;
+        for row in &rows {
+            let cells = columns.iter().map(|column| {
+                row.iter()
+                    .find(|(name, _)| name == column)
+                    .map_or("", |(_, value)| value.as_str())
+            });
+            writer.write_record(cells).map_err(csv_error)?;
+        }
+        let data = writer
+            .into_inner()
+            .map_err(|error| Error::Output(error.to_string()))?;
+        Ok(String::from_utf8_lossy(&data).into_owned())
+    }
+}
+
+/// Flattens nested objects into dotted column names. Lists of plain values are
+/// joined with ';', while lists of objects get their index in the column name.
+fn flatten(prefix: &str, value: &Value, row: &mut Vec<(String, String)>) {
+    let column = |name: &str| match prefix.is_empty() {
+        true => name.to_string(),
+        false => format!("{prefix}.{name}"),
+    };
+    match value {
+        Value::Object(fields) => flatten_object(prefix, fields, row),
+        Value::Array(items) if items.iter().any(|item| item.is_object()) => {
+            for (index, item) in items.iter().enumerate() {
+                flatten(&column(&index.to_string()), item, row);
+            }
+        }
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(plain).collect();
+            row.push((prefix.to_string(), items.join(";")));
+        }
+        other => row.push((prefix.to_string(), plain(other))),
+    }
+}
+
+fn flatten_object(prefix: &str, fields: &Map<String, Value>, row: &mut Vec<(String, String)>) {
+    for (name, value) in fields {
+        let column = match prefix.is_empty() {
+            true => name.clone(),
+            false => format!("{prefix}.{name}"),
+        };
+        flatten(&column, value, row);
+    }
+}
+
+fn plain(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::read_to_string;
+    use tempfile::TempDir;
+
+    #[derive(Serialize)]
+    struct Face {
+        name: String,
+        confidence: f32,
+        bounding_box: Option<Area>,
+        urls: Vec<String>,
+    }
+
+    #[derive(Serialize)]
+    struct Area {
+        left: f32,
+        top: f32,
+    }
+
+    #[derive(Serialize)]
+    struct Page {
+        words: Vec<Area>,
+    }
+
+    fn faces() -> Vec<ImageRecord<Face>> {
+        vec![
+            ImageRecord {
+                image: "team.jpg".to_string(),
+                result: Face {
+                    name: "Smith, \"Jo\"".to_string(),
+                    confidence: 99.1,
+                    bounding_box: Some(Area {
+                        left: 0.25,
+                        top: 0.5,
+                    }),
+                    urls: vec!["a.example".to_string(), "b.example".to_string()],
+                },
+            },
+            ImageRecord {
+                image: "crowd.jpg".to_string(),
+                result: Face {
+                    name: "Lee".to_string(),
+                    confidence: 87.5,
+                    bounding_box: None,
+                    urls: Vec::new(),
+                },
+            },
+        ]
+    }
+
+    fn written(results: &Results, format: ResultFormat) -> String {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("results");
+        let output = ResultOutput {
+            format,
+            path: Some(path.clone()),
+        };
+        results.write(&output).unwrap();
+        read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn json_is_a_single_array_of_the_records() {
+        let results = Results::new("faces", &faces()).unwrap();
+
+        let json: Value = serde_json::from_str(&written(&results, ResultFormat::Json)).unwrap();
+
+        assert_eq!(json.as_array().unwrap().len(), 2);
+        assert_eq!(json[0]["image"], "team.jpg");
+        assert_eq!(json[0]["bounding_box"]["left"], 0.25);
+        assert_eq!(json[1]["bounding_box"], Value::Null);
+    }
+
+    #[test]
+    fn json_lines_have_one_record_per_line() {
+        let results = Results::new("faces", &faces()).unwrap();
+
+        let data = written(&results, ResultFormat::Jsonl);
+
+        let lines: Vec<&str> = data.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let second: Value = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(second["name"], "Lee");
+        // Single precision values are kept at their shortest form.
+        assert!(lines[0].contains("\"confidence\":99.1,"), "{}", lines[0]);
+    }
+
+    #[test]
+    fn csv_flattens_nested_fields_into_dotted_columns() {
+        let results = Results::new("faces", &faces()).unwrap();
+
+        let data = written(&results, ResultFormat::Csv);
+
+        let lines: Vec<&str> = data.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "bounding_box.left,bounding_box.top,confidence,image,name,urls",
+                "0.25,0.5,99.1,team.jpg,\"Smith, \"\"Jo\"\"\",a.example;b.example",
+                ",,87.5,crowd.jpg,Lee,",
+            ]
+        );
+    }
+
+    #[test]
+    fn csv_numbers_the_columns_of_listed_objects() {
+        let pages = [Page {
+            words: vec![
+                Area {
+                    left: 0.1,
+                    top: 0.2,
+                },
+                Area {
+                    left: 0.3,
+                    top: 0.4,
+                },
+            ],
+        }];
+        let results = Results::new("pages", &pages).unwrap();
+
+        let data = written(&results, ResultFormat::Csv);
+
+        assert_eq!(
+            data,
+            "words.0.left,words.0.top,words.1.left,words.1.top\n0.1,0.2,0.3,0.4\n"
+        );
+    }
+}
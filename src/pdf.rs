@@ -0,0 +1,195 @@
+//! PDF reports of the operations, written to a given path with the bundled font.
+use genpdf::elements::{Break, FrameCellDecorator, Image, PageBreak, Paragraph, TableLayout};
+use genpdf::fonts::{FontData, FontFamily};
+use genpdf::{style::Style, Alignment, Document, Element, SimplePageDecorator};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result, WithPath};
+
+const FONT: &[u8] = include_bytes!("assets/font.ttf");
+
+/// The printable area of an A4 page within the margins, in millimeters, with
+/// some of the height left for the text below an image.
+const IMAGE_WIDTH: f64 = 190.0;
+const IMAGE_HEIGHT: f64 = 200.0;
+
+/// A part of a report that starts on a new page: a heading, an optional image
+/// and the lines describing it.
+pub struct Section {
+    pub heading: String,
+    pub image: Option<PathBuf>,
+    pub lines: Vec<String>,
+}
+
+/// Writes a report of a single section without an image.
+pub fn write_lines(path: &Path, title: &str, lines: &[String]) -> Result<()> {
+    let section = Section {
+        heading: title.to_string(),
+        image: None,
+        lines: lines.to_vec(),
+    };
+    write_sections(path, title, &[section])
+}
+
+pub fn write_sections(path: &Path, title: &str, sections: &[Section]) -> Result<()> {
+    let mut document = document(path, title)?;
+    for (index, section) in sections.iter().enumerate() {
+        if index > 0 {
+            document.push(PageBreak::new());
+        }
+        document.push(heading(&section.heading));
+        if let Some(image_path) = &section.image {
+            document.push(image(path, image_path)?);
+            document.push(Break::new(1));
+        }
+        for line in &section.lines {
+            document.push(Paragraph::new(line.as_str()));
+        }
+    }
+    render(document, path)
+}
+
+/// Writes a report of a single table, with a framed cell per value.
+pub fn write_table(path: &Path, title: &str, headers: &[&str], rows: &[Vec<String>]) -> Result<()> {
+    let mut document = document(path, title)?;
+    document.push(heading(title));
+    let mut table = TableLayout::new(vec![1; headers.len()]);
+    table.set_cell_decorator(FrameCellDecorator::new(true, true, false));
+    let mut header_row = table.row();
+    for header in headers {
+        header_row.push_element(Paragraph::new(*header).styled(Style::new().bold()));
+    }
+    header_row.push().map_err(|error| pdf_error(path, error))?;
+    for values in rows {
+        let mut row = table.row();
+        for value in values {
+            row.push_element(Paragraph::new(value.as_str()));
+        }
+        row.push().map_err(|error| pdf_error(path, error))?;
+    }
+    document.push(table);
+    render(document, path)
+}
+
+fn document(path: &Path, title: &str) -> Result<Document> {
+    let font = FontData::new(FONT.to_vec(), None).map_err(|error| pdf_error(path, error))?;
+    let mut document = Document::new(FontFamily {
+        regular: font.clone(),
+        bold: font.clone(),
+        italic: font.clone(),
+        bold_italic: font,
+    });
+    document.set_title(title);
+    document.set_font_size(11);
+    let mut decorator = SimplePageDecorator::new();
+    decorator.set_margins(10);
+    document.set_page_decorator(decorator);
+    Ok(document)
+}
+
+fn heading(text: &str) -> impl Element {
+    Paragraph::new(text)
+        .aligned(Alignment::Center)
+        .styled(Style::new().bold().with_font_size(16))
+}
+
+/// Embeds the image at a resolution that fits it within the page. The image is
+/// converted to RGB first, since PDF images can't have an alpha channel.
+fn image(path: &Path, image_path: &Path) -> Result<Image> {
+    let rgb = image::open(image_path).with_path(image_path)?.to_rgb8();
+    let dpi = [
+        300.0,
+        f64::from(rgb.width()) * 25.4 / IMAGE_WIDTH,
+        f64::from(rgb.height()) * 25.4 / IMAGE_HEIGHT,
+    ]
+    .into_iter()
+    .fold(0.0, f64::max);
+    let mut png = Cursor::new(Vec::new());
+    rgb.write_to(&mut png, image::ImageOutputFormat::Png)
+        .with_path(image_path)?;
+    png.set_position(0);
+    let image = Image::from_reader(png).map_err(|error| pdf_error(path, error))?;
+    Ok(image.with_dpi(dpi).with_alignment(Alignment::Center))
+}
+
+fn render(document: Document, path: &Path) -> Result<()> {
+    document
+        .render_to_file(path)
+        .map_err(|error| pdf_error(path, error))
+}
+
+fn pdf_error(path: &Path, error: genpdf::error::Error) -> Error {
+    Error::Pdf {
+        path: path.to_path_buf(),
+        message: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgba, RgbaImage};
+    use std::fs::read;
+    use tempfile::TempDir;
+
+    fn assert_pdf(path: &Path) {
+        let data = read(path).unwrap();
+        assert!(data.starts_with(b"%PDF"), "{}", path.display());
+    }
+
+    #[test]
+    fn sections_embed_their_images_even_with_an_alpha_channel() {
+        let dir = TempDir::new().unwrap();
+        let image_path = dir.path().join("face.png");
+        RgbaImage::from_pixel(2400, 200, Rgba([10, 20, 30, 127]))
+            .save(&image_path)
+            .unwrap();
+        let sections = [
+            Section {
+                heading: "face.png".to_string(),
+                image: Some(image_path),
+                lines: vec!["Gender: Female".to_string()],
+            },
+            Section {
+                heading: "No image".to_string(),
+                image: None,
+                lines: Vec::new(),
+            },
+        ];
+        let path = dir.path().join("faces.pdf");
+
+        write_sections(&path, "Faces", &sections).unwrap();
+
+        assert_pdf(&path);
+    }
+
+    #[test]
+    fn a_table_has_a_row_per_value_list() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("voices.pdf");
+        let rows = vec![
+            vec!["Female".to_string(), "Joanna".to_string()],
+            vec!["Male".to_string(), "Matthew".to_string()],
+        ];
+
+        write_table(&path, "Voices", &["Gender", "Voice ID"], &rows).unwrap();
+
+        assert_pdf(&path);
+    }
+
+    #[test]
+    fn a_missing_image_is_reported_with_its_path() {
+        let dir = TempDir::new().unwrap();
+        let section = Section {
+            heading: "missing.png".to_string(),
+            image: Some(dir.path().join("missing.png")),
+            lines: Vec::new(),
+        };
+
+        let error =
+            write_sections(&dir.path().join("report.pdf"), "Report", &[section]).unwrap_err();
+
+        assert!(error.to_string().contains("missing.png"), "{error}");
+    }
+}
@@ -0,0 +1,358 @@
+//! Checking the pronunciation lexicons before they are stored in Polly.
+//!
+//! A lexicon is a W3C PLS document whose lexemes give the pronunciation, or
+//! an alias, of the words Polly would otherwise mispronounce.
+use crate::error::{Error, Result};
+use crate::xml::{Event, Position, Reader};
+
+const NAMESPACE: &str = "http://www.w3.org/2005/01/pronunciation-lexicon";
+
+/// The most characters of a lexicon Polly stores.
+const MAX_CHARACTERS: usize = 4000;
+
+/// The most characters of a single pronunciation or alias.
+const MAX_REPLACEMENT_CHARACTERS: usize = 100;
+
+/// The most lexicons a single request applies.
+const MAX_APPLIED: usize = 5;
+
+/// What a valid lexicon is for.
+pub struct Summary {
+    pub language_code: String,
+    pub alphabet: String,
+    pub lexemes: usize,
+}
+
+/// Checks a name Polly stores a lexicon under, which is 1 to 20 letters and digits.
+pub fn check_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.len() > 20 || !name.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(Error::LexiconName(name.to_string()));
+    }
+    Ok(())
+}
+
+/// Parses a comma separated list of the lexicons applied to a request,
+/// ignoring the empty ones.
+pub fn parse_names(names: &str) -> Result<Vec<String>> {
+    let names: Vec<String> = names
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(ToOwned::to_owned)
+        .collect();
+    check_names(&names)?;
+    Ok(names)
+}
+
+/// Checks the names of the lexicons applied to a request.
+pub fn check_names(names: &[String]) -> Result<()> {
+    if names.len() > MAX_APPLIED {
+        return Err(Error::TooManyLexicons {
+            count: names.len(),
+            limit: MAX_APPLIED,
+        });
+    }
+    names.iter().try_for_each(|name| check_name(name))
+}
+
+/// A lexeme that is being read, with how many of its graphemes and
+/// pronunciations or aliases were read so far.
+struct Lexeme {
+    position: Position,
+    graphemes: usize,
+    replacements: usize,
+}
+
+/// Checks that the lexicon is a PLS document Polly accepts.
+pub fn validate(content: &str) -> Result<Summary> {
+    let characters = content.chars().count();
+    if characters > MAX_CHARACTERS {
+        return Err(Error::TextTooLong {
+            characters,
+            limit: MAX_CHARACTERS,
+        });
+    }
+    let mut reader = Reader::new(content, "lexicon", "lexicon");
+    let mut summary = Summary {
+        language_code: String::new(),
+        alphabet: String::new(),
+        lexemes: 0,
+    };
+    let mut lexeme: Option<Lexeme> = None;
+    // The text of the grapheme, pronunciation or alias that is being read.
+    let mut text: Option<(String, Position)> = None;
+    // How deep the reader is within a <metadata>, which can have anything.
+    let mut metadata = 0;
+    while let Some(event) = reader.next()? {
+        match event {
+            Event::Start(tag) if metadata > 0 => {
+                if !tag.closed {
+                    metadata += 1;
+                }
+            }
+            Event::Start(tag) => match (tag.parent, tag.name) {
+                (None, _) => {
+                    let namespace = tag
+                        .attributes
+                        .iter()
+                        .find(|attribute| attribute.name == "xmlns")
+                        .map(|attribute| attribute.value.as_str());
+                    if namespace != Some(NAMESPACE) {
+                        return Err(reader.error_at(
+                            tag.position,
+                            format!("<lexicon> needs the namespace xmlns=\"{NAMESPACE}\""),
+                        ));
+                    }
+                    for attribute in tag.attributes() {
+                        let value = attribute.value.as_str();
+                        let (valid, expected) = match attribute.name {
+                            "version" => (value == "1.0", "'1.0'"),
+                            "alphabet" => {
+                                (matches!(value, "ipa" | "x-sampa"), "'ipa' or 'x-sampa'")
+                            }
+                            "xml:lang" => {
+                                (!value.trim().is_empty(), "a language code such as 'en-US'")
+                            }
+                            "xsi:schemaLocation" => (true, ""),
+                            other => {
+                                return Err(reader.error_at(
+                                    attribute.position,
+                                    format!("<lexicon> has no '{other}' attribute"),
+                                ))
+                            }
+                        };
+                        if !valid {
+                            return Err(reader.error_at(
+                                attribute.position,
+                                format!(
+                                    "'{value}' isn't a valid '{}' of <lexicon>, which is {expected}",
+                                    attribute.name
+                                ),
+                            ));
+                        }
+                    }
+                    for required in ["version", "alphabet", "xml:lang"] {
+                        if tag.attribute(required).is_none() {
+                            return Err(reader.error_at(
+                                tag.position,
+                                format!("<lexicon> needs the '{required}' attribute"),
+                            ));
+                        }
+                    }
+                    summary.alphabet = tag.attribute("alphabet").unwrap_or_default().to_string();
+                    summary.language_code =
+                        tag.attribute("xml:lang").unwrap_or_default().to_string();
+                }
+                (Some("lexicon"), "meta") => {}
+                (Some("lexicon"), "metadata") => {
+                    if !tag.closed {
+                        metadata += 1;
+                    }
+                }
+                (Some("lexicon"), "lexeme") => {
+                    if tag.closed {
+                        return Err(reader.error_at(
+                            tag.position,
+                            "a <lexeme> needs a <grapheme>, and a <phoneme> or an <alias>",
+                        ));
+                    }
+                    lexeme = Some(Lexeme {
+                        position: tag.position,
+                        graphemes: 0,
+                        replacements: 0,
+                    });
+                }
+                (Some("lexeme"), name @ ("grapheme" | "phoneme" | "alias" | "example")) => {
+                    for attribute in tag.attributes() {
+                        let valid = match (name, attribute.name) {
+                            ("phoneme", "alphabet") => {
+                                matches!(attribute.value.as_str(), "ipa" | "x-sampa")
+                            }
+                            ("phoneme" | "alias", "prefer") => {
+                                matches!(attribute.value.as_str(), "true" | "false")
+                            }
+                            (_, other) => {
+                                return Err(reader.error_at(
+                                    attribute.position,
+                                    format!("<{name}> has no '{other}' attribute"),
+                                ))
+                            }
+                        };
+                        if !valid {
+                            return Err(reader.error_at(
+                                attribute.position,
+                                format!(
+                                    "'{}' isn't a valid '{}' of <{name}>",
+                                    attribute.value, attribute.name
+                                ),
+                            ));
+                        }
+                    }
+                    if tag.closed {
+                        return Err(reader.error_at(tag.position, format!("<{name}> is empty")));
+                    }
+                    text = Some((String::new(), tag.position));
+                }
+                (Some(parent @ ("grapheme" | "phoneme" | "alias" | "example")), name) => {
+                    return Err(reader.error_at(
+                        tag.position,
+                        format!("<{parent}> can only have text, not <{name}>"),
+                    ))
+                }
+                (Some(parent), name) => {
+                    return Err(reader
+                        .error_at(tag.position, format!("<{name}> can't be within <{parent}>")))
+                }
+            },
+            Event::Text {
+                text: read,
+                position,
+            } => match &mut text {
+                Some((text, _)) => text.push_str(&read),
+                None if metadata > 0 || read.trim().is_empty() => {}
+                None => {
+                    let within = reader.within().unwrap_or("lexicon");
+                    return Err(
+                        reader.error_at(position, format!("<{within}> can't have text of its own"))
+                    );
+                }
+            },
+            Event::End { .. } if metadata > 0 => metadata -= 1,
+            Event::End { name } => match (name, text.take(), lexeme.as_mut()) {
+                (
+                    "grapheme" | "phoneme" | "alias" | "example",
+                    Some((read, position)),
+                    Some(lexeme),
+                ) => {
+                    let characters = read.trim().chars().count();
+                    if characters == 0 {
+                        return Err(reader.error_at(position, format!("<{name}> is empty")));
+                    }
+                    if name != "grapheme" && characters > MAX_REPLACEMENT_CHARACTERS {
+                        return Err(reader.error_at(
+                            position,
+                            format!("<{name}> is {characters} characters long, more than the {MAX_REPLACEMENT_CHARACTERS} Polly accepts"),
+                        ));
+                    }
+                    match name {
+                        "grapheme" => lexeme.graphemes += 1,
+                        "phoneme" | "alias" => lexeme.replacements += 1,
+                        _ => {}
+                    }
+                }
+                ("lexeme", _, _) => {
+                    if let Some(lexeme) = lexeme.take() {
+                        if lexeme.graphemes == 0 || lexeme.replacements == 0 {
+                            return Err(reader.error_at(
+                                lexeme.position,
+                                "a <lexeme> needs a <grapheme>, and a <phoneme> or an <alias>",
+                            ));
+                        }
+                        summary.lexemes += 1;
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lexicon(lexemes: &str) -> String {
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <lexicon version=\"1.0\" xmlns=\"{NAMESPACE}\" alphabet=\"ipa\" xml:lang=\"en-US\">\n\
+             {lexemes}\n</lexicon>"
+        )
+    }
+
+    fn error_of(content: &str) -> String {
+        validate(content).err().unwrap().to_string()
+    }
+
+    #[test]
+    fn a_valid_lexicon_is_summarized() {
+        let content = lexicon(
+            "<metadata><dc:title>Anything</dc:title></metadata>\
+             <lexeme><grapheme>W3C</grapheme><alias>World Wide Web Consortium</alias></lexeme>\
+             <lexeme><grapheme>tomato</grapheme><phoneme>təˈmɑːtoʊ</phoneme></lexeme>",
+        );
+
+        let summary = validate(&content).unwrap();
+
+        assert_eq!(summary.lexemes, 2);
+        assert_eq!(summary.alphabet, "ipa");
+        assert_eq!(summary.language_code, "en-US");
+    }
+
+    #[test]
+    fn the_lexicon_needs_the_namespace() {
+        let content = lexicon("").replace(NAMESPACE, "http://example.com/");
+
+        assert!(error_of(&content).contains("needs the namespace"));
+    }
+
+    #[test]
+    fn a_lexeme_needs_a_pronunciation_or_an_alias() {
+        let content = lexicon("<lexeme><grapheme>W3C</grapheme></lexeme>");
+
+        let error = error_of(&content);
+        assert!(error.contains("line 3, column 1"), "{error}");
+        assert!(error.contains("a <phoneme> or an <alias>"), "{error}");
+    }
+
+    #[test]
+    fn an_alias_over_the_limit_is_reported() {
+        let alias = "a".repeat(MAX_REPLACEMENT_CHARACTERS + 1);
+        let content = lexicon(&format!(
+            "<lexeme><grapheme>A</grapheme><alias>{alias}</alias></lexeme>"
+        ));
+
+        assert!(error_of(&content).contains("101 characters long"));
+    }
+
+    #[test]
+    fn a_lexicon_over_the_limit_is_reported() {
+        let lexeme = "<lexeme><grapheme>A</grapheme><alias>B</alias></lexeme>";
+        let content = lexicon(&lexeme.repeat(MAX_CHARACTERS / lexeme.len() + 1));
+
+        assert!(matches!(
+            validate(&content),
+            Err(Error::TextTooLong { limit: 4000, .. })
+        ));
+    }
+
+    #[test]
+    fn tags_and_text_are_only_read_where_they_belong() {
+        let error = error_of(&lexicon("<lexeme><grapheme><b>A</b></grapheme></lexeme>"));
+        assert!(error.contains("<grapheme> can only have text"), "{error}");
+
+        let error = error_of(&lexicon("Stray"));
+        assert!(
+            error.contains("<lexicon> can't have text of its own"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn a_name_is_letters_and_digits() {
+        check_name("Names2023").unwrap();
+        for name in ["", "../names", "a b", "a".repeat(21).as_str()] {
+            assert!(check_name(name).is_err(), "{name}");
+        }
+    }
+
+    #[test]
+    fn the_names_of_a_request_are_counted_and_checked() {
+        assert_eq!(parse_names(" a, ,b ,").unwrap(), ["a", "b"]);
+        assert!(matches!(
+            parse_names("a,b,c,d,e,f"),
+            Err(Error::TooManyLexicons { count: 6, limit: 5 })
+        ));
+        assert!(parse_names("a,b/c").is_err());
+    }
+}
@@ -0,0 +1,349 @@
+//! The preprocessing the images go through before they are uploaded.
+//!
+//! Every step is optional, and an image that needs none of them is uploaded
+//! as it is. Whatever the steps, an image never leaves larger than
+//! [`Preprocess::max_bytes`], so that Rekognition can still read it from S3.
+use clap::ValueEnum;
+use colored::Colorize;
+use exif::{In, Reader, Tag};
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::io::Reader as ImageReader;
+use image::{DynamicImage, GenericImageView, ImageFormat, ImageOutputFormat};
+use std::fs::{metadata, write, File};
+use std::io::{BufReader, Cursor};
+use std::path::{Path, PathBuf};
+
+use crate::error::{Result, WithPath};
+
+/// The largest image Rekognition reads from an S3 object.
+pub const REKOGNITION_MAX_BYTES: u64 = 15 * 1024 * 1024;
+
+/// How many times an image is scaled down before it's given up on as too large.
+const MAX_SHRINKS: usize = 12;
+
+/// The formats Rekognition reads, which are the only ones the images are converted to.
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ConvertTo {
+    Jpeg,
+    Png,
+}
+
+impl ConvertTo {
+    fn extension(&self) -> &'static str {
+        match self {
+            Self::Jpeg => "jpg",
+            Self::Png => "png",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Preprocess {
+    /// Scales down the images whose width or height is larger, keeping the aspect ratio.
+    pub max_dimension: Option<u32>,
+    /// Turns the images upright according to the orientation in their EXIF data.
+    pub auto_rotate: bool,
+    /// Keeps JPEG images as JPEG and writes every other format as PNG when not set.
+    pub convert_to: Option<ConvertTo>,
+    /// The JPEG quality, from 1 to 100.
+    pub quality: u8,
+    pub max_bytes: u64,
+}
+
+impl Default for Preprocess {
+    fn default() -> Self {
+        Self {
+            max_dimension: None,
+            auto_rotate: false,
+            convert_to: None,
+            quality: 90,
+            max_bytes: REKOGNITION_MAX_BYTES,
+        }
+    }
+}
+
+impl Preprocess {
+    fn changes_pixels(&self) -> bool {
+        self.max_dimension.is_some() || self.auto_rotate || self.convert_to.is_some()
+    }
+
+    /// Returns the path of the image to upload, which is either the original
+    /// or a processed copy written into `out_dir`, or `None` when the file
+    /// isn't an image or can't be brought under the size limit.
+    pub fn apply(&self, path: &Path, out_dir: &Path) -> Result<Option<PathBuf>> {
+        let reader = ImageReader::open(path)
+            .with_path(path)?
+            .with_guessed_format()
+            .with_path(path)?;
+        let Some(format) = reader.format() else {
+            skip(path, "it isn't an image");
+            return Ok(None);
+        };
+        // Reading the header is enough to tell a broken image apart when it's
+        // uploaded as it is.
+        if let Err(error) = reader.into_dimensions() {
+            skip(path, &format!("it can't be read as an image: {error}"));
+            return Ok(None);
+        }
+        let size = metadata(path).with_path(path)?.len();
+        let convert_to = match (self.convert_to, format) {
+            (Some(convert_to), _) => convert_to,
+            (None, ImageFormat::Jpeg) => ConvertTo::Jpeg,
+            (None, ImageFormat::Png) => ConvertTo::Png,
+            // Rekognition reads neither, so these are always converted.
+            (None, _) => ConvertTo::Png,
+        };
+        let keeps_format = matches!(
+            (convert_to, format),
+            (ConvertTo::Jpeg, ImageFormat::Jpeg) | (ConvertTo::Png, ImageFormat::Png)
+        );
+        if !self.changes_pixels() && keeps_format && size <= self.max_bytes {
+            return Ok(Some(path.to_path_buf()));
+        }
+
+        let mut image = match image::open(path) {
+            Ok(image) => image,
+            Err(error) => {
+                skip(path, &format!("it can't be read as an image: {error}"));
+                return Ok(None);
+            }
+        };
+        if self.auto_rotate {
+            image = match orientation(path) {
+                Some(2) => image.fliph(),
+                Some(3) => image.rotate180(),
+                Some(4) => image.flipv(),
+                Some(5) => image.rotate90().fliph(),
+                Some(6) => image.rotate90(),
+                Some(7) => image.rotate270().fliph(),
+                Some(8) => image.rotate270(),
+                _ => image,
+            };
+        }
+        if let Some(max_dimension) = self.max_dimension {
+            let (width, height) = image.dimensions();
+            if width.max(height) > max_dimension {
+                image = image.resize(max_dimension, max_dimension, FilterType::Lanczos3);
+            }
+        }
+        let mut bytes = self.encode(&image, convert_to, path)?;
+        let mut shrinks = 0;
+        while bytes.len() as u64 > self.max_bytes {
+            if shrinks == MAX_SHRINKS {
+                skip(path, "it can't be brought under the size limit");
+                return Ok(None);
+            }
+            let (width, height) = image.dimensions();
+            image = image.resize(
+                (width * 4 / 5).max(1),
+                (height * 4 / 5).max(1),
+                FilterType::Lanczos3,
+            );
+            bytes = self.encode(&image, convert_to, path)?;
+            shrinks += 1;
+        }
+
+        let processed = out_dir.join(processed_name(path, convert_to));
+        write(&processed, bytes).with_path(&processed)?;
+        Ok(Some(processed))
+    }
+
+    fn encode(&self, image: &DynamicImage, convert_to: ConvertTo, path: &Path) -> Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        let encoded = match convert_to {
+            // JPEG has no alpha channel to keep.
+            ConvertTo::Jpeg => {
+                JpegEncoder::new_with_quality(&mut bytes, self.quality.clamp(1, 100))
+                    .encode_image(&DynamicImage::ImageRgb8(image.to_rgb8()))
+            }
+            ConvertTo::Png => image.write_to(&mut Cursor::new(&mut bytes), ImageOutputFormat::Png),
+        };
+        encoded.with_path(path)?;
+        Ok(bytes)
+    }
+}
+
+/// The file name of the processed copy, which keeps the name of the original
+/// when the format is kept, and otherwise adds the new extension to it, so
+/// that 'a.webp' and 'a.png' of the same directory don't both become 'a.png'.
+fn processed_name(path: &Path, convert_to: ConvertTo) -> String {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let extension = convert_to.extension();
+    let keeps_extension = path
+        .extension()
+        .and_then(|current| current.to_str())
+        .is_some_and(|current| match convert_to {
+            ConvertTo::Jpeg => ["jpg", "jpeg"].contains(&current.to_lowercase().as_str()),
+            ConvertTo::Png => current.eq_ignore_ascii_case(extension),
+        });
+    match keeps_extension {
+        true => file_name,
+        false => format!("{file_name}.{extension}"),
+    }
+}
+
+/// The EXIF orientation of the image, from 1 (upright) to 8.
+fn orientation(path: &Path) -> Option<u32> {
+    let file = File::open(path).ok()?;
+    let exif = Reader::new()
+        .read_from_container(&mut BufReader::new(file))
+        .ok()?;
+    exif.get_field(Tag::Orientation, In::PRIMARY)?
+        .value
+        .get_uint(0)
+}
+
+fn skip(path: &Path, reason: &str) {
+    println!(
+        "{} '{}' {}\n",
+        "Skipping".yellow().bold(),
+        path.display().to_string().yellow().bold(),
+        format!("because {reason}").yellow().bold()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+    use tempfile::TempDir;
+
+    /// Pixels that don't compress well, so that the encoded size follows the dimensions.
+    fn noise(width: u32, height: u32) -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_fn(width, height, |x, y| {
+            let seed = (x * 7919 + y * 104_729) ^ (x * y);
+            Rgb([seed as u8, (seed >> 8) as u8, (seed >> 3) as u8])
+        }))
+    }
+
+    /// A JPEG with an EXIF segment that holds nothing but the orientation.
+    fn jpeg_with_orientation(image: &DynamicImage, orientation: u8) -> Vec<u8> {
+        let mut jpeg = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut jpeg), ImageOutputFormat::Jpeg(90))
+            .unwrap();
+        let mut tiff = b"MM\0\x2a\0\0\0\x08\0\x01".to_vec();
+        tiff.extend([0x01, 0x12, 0, 3, 0, 0, 0, 1, 0, orientation, 0, 0]);
+        tiff.extend([0, 0, 0, 0]);
+        let length = (2 + 6 + tiff.len()) as u16;
+        let mut app1 = vec![0xff, 0xe1];
+        app1.extend(length.to_be_bytes());
+        app1.extend(b"Exif\0\0");
+        app1.extend(tiff);
+        [&jpeg[..2], &app1, &jpeg[2..]].concat()
+    }
+
+    #[test]
+    fn an_image_needing_nothing_is_uploaded_as_it_is() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("a.png");
+        noise(20, 10).save(&path).unwrap();
+
+        let processed = Preprocess::default().apply(&path, dir.path()).unwrap();
+
+        assert_eq!(processed, Some(path));
+    }
+
+    #[test]
+    fn large_images_are_scaled_down_keeping_the_aspect_ratio() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("a.png");
+        noise(400, 200).save(&path).unwrap();
+        let out_dir = TempDir::new().unwrap();
+        let preprocess = Preprocess {
+            max_dimension: Some(100),
+            ..Preprocess::default()
+        };
+
+        let processed = preprocess.apply(&path, out_dir.path()).unwrap().unwrap();
+
+        assert_eq!(processed, out_dir.path().join("a.png"));
+        assert_eq!(image::open(&processed).unwrap().dimensions(), (100, 50));
+    }
+
+    #[test]
+    fn images_are_turned_upright_from_their_exif_orientation() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("photo.jpg");
+        write(&path, jpeg_with_orientation(&noise(40, 20), 6)).unwrap();
+        let out_dir = TempDir::new().unwrap();
+        let preprocess = Preprocess {
+            auto_rotate: true,
+            ..Preprocess::default()
+        };
+
+        let processed = preprocess.apply(&path, out_dir.path()).unwrap().unwrap();
+
+        assert_eq!(processed, out_dir.path().join("photo.jpg"));
+        assert_eq!(image::open(&processed).unwrap().dimensions(), (20, 40));
+    }
+
+    #[test]
+    fn converted_copies_keep_the_name_of_the_original() {
+        let dir = TempDir::new().unwrap();
+        let out_dir = TempDir::new().unwrap();
+        let preprocess = Preprocess {
+            convert_to: Some(ConvertTo::Png),
+            ..Preprocess::default()
+        };
+        let mut processed = Vec::new();
+        for name in ["a.png", "a.jpg", "a.bmp"] {
+            let path = dir.path().join(name);
+            noise(8, 8).save(&path).unwrap();
+            processed.push(preprocess.apply(&path, out_dir.path()).unwrap().unwrap());
+        }
+
+        let names: Vec<_> = processed
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, ["a.png", "a.jpg.png", "a.bmp.png"]);
+    }
+
+    #[test]
+    fn an_image_over_the_limit_is_shrunk_until_it_fits() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("a.png");
+        noise(200, 200).save(&path).unwrap();
+        let out_dir = TempDir::new().unwrap();
+        let preprocess = Preprocess {
+            max_bytes: 20_000,
+            ..Preprocess::default()
+        };
+
+        let processed = preprocess.apply(&path, out_dir.path()).unwrap().unwrap();
+
+        assert!(metadata(&processed).unwrap().len() <= 20_000);
+        let (width, height) = image::open(&processed).unwrap().dimensions();
+        assert!(width < 200 && width == height, "{width}x{height}");
+    }
+
+    #[test]
+    fn an_image_that_cant_be_shrunk_enough_is_skipped() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("a.png");
+        noise(50, 50).save(&path).unwrap();
+        let preprocess = Preprocess {
+            max_bytes: 10,
+            ..Preprocess::default()
+        };
+
+        assert_eq!(preprocess.apply(&path, dir.path()).unwrap(), None);
+    }
+
+    #[test]
+    fn files_that_arent_images_are_skipped() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("notes.txt");
+        write(&path, "not an image").unwrap();
+
+        assert_eq!(
+            Preprocess::default().apply(&path, dir.path()).unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,1167 @@
+use async_trait::async_trait;
+use aws_apis::{PollyOps, TranslateOps};
+use aws_sdk_polly::types::{
+    Engine, LanguageCode, LexiconAttributes, OutputFormat, SpeechMarkType, TextType, VoiceId,
+};
+use aws_sdk_rekognition::types::{Attribute, FaceAttributes, FaceDetail, Image, S3Object, Video};
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_transcribe::types::{Media, MediaFormat};
+use aws_sdk_translate::types::{
+    Document, InputDataConfig, OutputDataConfig, TextTranslationJobProperties,
+};
+use aws_smithy_types::date_time::Format;
+use aws_smithy_types::error::display::DisplayErrorContext;
+use aws_smithy_types::{Blob, DateTime};
+use aws_types::SdkConfig;
+use std::fs::{create_dir_all, read, write};
+use std::path::Path;
+
+use crate::error::{Error, Result, WithPath};
+use crate::keys::local_name;
+use crate::pdf;
+
+use super::{
+    BoundingBox, CelebrityFace, DetectionJob, Emotion, FaceFlag, FaceSummary, ImageQuality,
+    Landmark, Language, Lexicon, Point, PollyService, Pose, RekognitionService, S3Service,
+    SynthesisTask, TextSummary, TranscribeService, TranscriptionState, TranslateService,
+    Translation, TranslationJob, Voice,
+};
+
+/// The Polly operations of `aws_apis`, along with an SDK client for the calls
+/// whose results `aws_apis` only prints.
+pub struct AwsPolly {
+    ops: PollyOps,
+    sdk: aws_sdk_polly::Client,
+}
+
+impl AwsPolly {
+    pub fn build(sdk_config: &SdkConfig) -> Self {
+        Self {
+            ops: PollyOps::build(sdk_config),
+            sdk: aws_sdk_polly::Client::new(sdk_config),
+        }
+    }
+}
+
+/// The Rekognition calls, made with the SDK client so that every failure is
+/// returned and images can be sent as bytes.
+pub struct AwsRekognition {
+    sdk: aws_sdk_rekognition::Client,
+}
+
+impl AwsRekognition {
+    pub fn build(sdk_config: &SdkConfig) -> Self {
+        Self {
+            sdk: aws_sdk_rekognition::Client::new(sdk_config),
+        }
+    }
+}
+
+/// The S3 calls, made with the SDK client so that every failure is returned.
+pub struct AwsS3 {
+    sdk: aws_sdk_s3::Client,
+}
+
+impl AwsS3 {
+    pub fn build(sdk_config: &SdkConfig) -> Self {
+        Self {
+            sdk: aws_sdk_s3::Client::new(sdk_config),
+        }
+    }
+
+    async fn download(&self, bucket_name: &str, key: &str, local_path: &Path) -> Result<()> {
+        let output = self
+            .sdk
+            .get_object()
+            .bucket(bucket_name)
+            .key(key)
+            .send()
+            .await
+            .map_err(service_error)?;
+        let content = output
+            .body
+            .collect()
+            .await
+            .map_err(|error| Error::Service(error.to_string()))?;
+        write(local_path, content.into_bytes()).with_path(local_path)
+    }
+}
+
+/// The Transcribe calls, made with the SDK client so that every failure is returned.
+pub struct AwsTranscribe {
+    sdk: aws_sdk_transcribe::Client,
+}
+
+impl AwsTranscribe {
+    pub fn build(sdk_config: &SdkConfig) -> Self {
+        Self {
+            sdk: aws_sdk_transcribe::Client::new(sdk_config),
+        }
+    }
+}
+
+/// The Translate operations of `aws_apis`, along with an SDK client for the
+/// calls whose results `aws_apis` only prints.
+pub struct AwsTranslate {
+    ops: TranslateOps,
+    sdk: aws_sdk_translate::Client,
+}
+
+impl AwsTranslate {
+    pub fn build(sdk_config: &SdkConfig) -> Self {
+        Self {
+            ops: TranslateOps::build(sdk_config),
+            sdk: aws_sdk_translate::Client::new(sdk_config),
+        }
+    }
+}
+
+/// Turns the error of any SDK call into a service error that keeps the reason
+/// given by AWS, which the plain `Display` of the SDK errors leaves out.
+fn service_error(error: impl std::error::Error) -> Error {
+    Error::Service(DisplayErrorContext(error).to_string())
+}
+
+fn time_of(date_time: Option<&DateTime>) -> Option<String> {
+    date_time.and_then(|date_time| date_time.fmt(Format::DateTime).ok())
+}
+
+fn synthesis_task_of(task: &aws_sdk_polly::types::SynthesisTask) -> SynthesisTask {
+    SynthesisTask {
+        task_id: task.task_id().map(ToOwned::to_owned),
+        status: task.task_status().map(|status| status.as_str().to_string()),
+        engine: task.engine().map(|engine| engine.as_str().to_string()),
+        output_uri: task.output_uri().map(ToOwned::to_owned),
+        output_format: task
+            .output_format()
+            .map(|format| format.as_str().to_string()),
+        text_type: task
+            .text_type()
+            .map(|text_type| text_type.as_str().to_string()),
+        voice_id: task
+            .voice_id()
+            .map(|voice_id| voice_id.as_str().to_string()),
+        language_code: task.language_code().map(|code| code.as_str().to_string()),
+        status_reason: task.task_status_reason().map(ToOwned::to_owned),
+    }
+}
+
+fn lexicon_of(name: &str, attributes: Option<&LexiconAttributes>) -> Lexicon {
+    Lexicon {
+        name: name.to_owned(),
+        language_code: attributes
+            .and_then(|attributes| attributes.language_code())
+            .map(|language_code| language_code.as_str().to_owned()),
+        alphabet: attributes
+            .and_then(|attributes| attributes.alphabet())
+            .map(ToOwned::to_owned),
+        lexemes_count: attributes.map_or(0, |attributes| attributes.lexemes_count()),
+        size: attributes.map_or(0, |attributes| attributes.size()),
+    }
+}
+
+/// Leaves the lexicons out of a request instead of sending an empty list.
+fn lexicon_names_of(lexicon_names: &[String]) -> Option<Vec<String>> {
+    (!lexicon_names.is_empty()).then(|| lexicon_names.to_vec())
+}
+
+/// Leaves the speech mark types out of a request when there are none,
+/// which Polly only accepts along with the 'json' output format.
+fn speech_mark_types_of(speech_mark_types: &[String]) -> Option<Vec<SpeechMarkType>> {
+    (!speech_mark_types.is_empty()).then(|| {
+        speech_mark_types
+            .iter()
+            .map(|kind| SpeechMarkType::from(kind.as_str()))
+            .collect()
+    })
+}
+
+fn missing_id(what: &str) -> Error {
+    Error::Service(format!("No ID was returned for the {what}"))
+}
+
+fn bounding_box_of(bounding_box: &aws_sdk_rekognition::types::BoundingBox) -> BoundingBox {
+    BoundingBox {
+        width: bounding_box.width().unwrap_or_default(),
+        height: bounding_box.height().unwrap_or_default(),
+        left: bounding_box.left().unwrap_or_default(),
+        top: bounding_box.top().unwrap_or_default(),
+    }
+}
+
+/// Maps every attribute of a face detail, leaving the ones the service omits at their defaults.
+fn face_summary_of(detail: &FaceDetail) -> FaceSummary {
+    let bounding_box = detail
+        .bounding_box()
+        .map(bounding_box_of)
+        .unwrap_or_default();
+    let flag = |value: bool, confidence: Option<f32>| FaceFlag {
+        value,
+        confidence: confidence.unwrap_or_default(),
+    };
+    let mut emotions: Vec<Emotion> = detail
+        .emotions()
+        .unwrap_or_default()
+        .iter()
+        .map(|emotion| Emotion {
+            kind: emotion
+                .r#type()
+                .map(|kind| kind.as_str().to_string())
+                .unwrap_or_default(),
+            confidence: emotion.confidence().unwrap_or_default(),
+        })
+        .collect();
+    emotions.sort_by(|first, second| second.confidence.total_cmp(&first.confidence));
+    FaceSummary {
+        gender: detail
+            .gender()
+            .and_then(|gender| gender.value())
+            .map(|gender| gender.as_str().to_string())
+            .unwrap_or_default(),
+        gender_confidence: detail
+            .gender()
+            .and_then(|gender| gender.confidence())
+            .unwrap_or_default(),
+        age_low: detail
+            .age_range()
+            .and_then(|age| age.low())
+            .unwrap_or_default(),
+        age_high: detail
+            .age_range()
+            .and_then(|age| age.high())
+            .unwrap_or_default(),
+        smile: detail
+            .smile()
+            .map(|smile| smile.value())
+            .unwrap_or_default(),
+        smile_confidence: detail
+            .smile()
+            .and_then(|smile| smile.confidence())
+            .unwrap_or_default(),
+        beard: detail
+            .beard()
+            .map(|beard| beard.value())
+            .unwrap_or_default(),
+        beard_confidence: detail
+            .beard()
+            .and_then(|beard| beard.confidence())
+            .unwrap_or_default(),
+        width: bounding_box.width,
+        height: bounding_box.height,
+        left: bounding_box.left,
+        top: bounding_box.top,
+        confidence: detail.confidence().unwrap_or_default(),
+        mustache: detail
+            .mustache()
+            .map(|mustache| flag(mustache.value(), mustache.confidence()))
+            .unwrap_or_default(),
+        eyeglasses: detail
+            .eyeglasses()
+            .map(|eyeglasses| flag(eyeglasses.value(), eyeglasses.confidence()))
+            .unwrap_or_default(),
+        sunglasses: detail
+            .sunglasses()
+            .map(|sunglasses| flag(sunglasses.value(), sunglasses.confidence()))
+            .unwrap_or_default(),
+        eyes_open: detail
+            .eyes_open()
+            .map(|eyes_open| flag(eyes_open.value(), eyes_open.confidence()))
+            .unwrap_or_default(),
+        mouth_open: detail
+            .mouth_open()
+            .map(|mouth_open| flag(mouth_open.value(), mouth_open.confidence()))
+            .unwrap_or_default(),
+        emotions,
+        pose: detail
+            .pose()
+            .map(|pose| Pose {
+                roll: pose.roll().unwrap_or_default(),
+                yaw: pose.yaw().unwrap_or_default(),
+                pitch: pose.pitch().unwrap_or_default(),
+            })
+            .unwrap_or_default(),
+        quality: detail
+            .quality()
+            .map(|quality| ImageQuality {
+                brightness: quality.brightness().unwrap_or_default(),
+                sharpness: quality.sharpness().unwrap_or_default(),
+            })
+            .unwrap_or_default(),
+        landmarks: detail
+            .landmarks()
+            .unwrap_or_default()
+            .iter()
+            .map(|landmark| Landmark {
+                kind: landmark
+                    .r#type()
+                    .map(|kind| kind.as_str().to_string())
+                    .unwrap_or_default(),
+                x: landmark.x().unwrap_or_default(),
+                y: landmark.y().unwrap_or_default(),
+            })
+            .collect(),
+    }
+}
+
+/// Writes the results of a video job as text and PDF into `local_dir`.
+fn write_job_results(local_dir: &Path, name: &str, title: &str, lines: &[String]) -> Result<()> {
+    let text_path = local_dir.join(format!("{name}.txt"));
+    write(&text_path, lines.join("\n")).with_path(&text_path)?;
+    pdf::write_lines(&local_dir.join(format!("{name}.pdf")), title, lines)
+}
+
+/// The bytes of a local image, or the location of an S3 object, as Rekognition takes it.
+fn image_of(
+    local_path: Option<&str>,
+    bucket_name: Option<&str>,
+    key: Option<&str>,
+) -> Result<Option<Image>> {
+    match (local_path, bucket_name, key) {
+        (Some(local_path), _, _) => {
+            let bytes = read(local_path).with_path(local_path)?;
+            Ok(Some(Image::builder().bytes(Blob::new(bytes)).build()))
+        }
+        (None, Some(bucket_name), Some(key)) => Ok(Some(
+            Image::builder()
+                .s3_object(S3Object::builder().bucket(bucket_name).name(key).build())
+                .build(),
+        )),
+        _ => Ok(None),
+    }
+}
+
+fn video_of(bucket_name: &str, key: &str) -> Video {
+    Video::builder()
+        .s3_object(S3Object::builder().bucket(bucket_name).name(key).build())
+        .build()
+}
+
+/// Maps the document types offered by the prompts to their content types.
+fn content_type_of(document_type: &str) -> &str {
+    match document_type.to_lowercase().as_str() {
+        "plain" => "text/plain",
+        "html" => "text/html",
+        "word" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "ppt" => "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "xlf" => "application/x-xliff+xml",
+        _ => document_type,
+    }
+}
+
+fn translation_job_of(properties: &TextTranslationJobProperties) -> TranslationJob {
+    TranslationJob {
+        job_id: properties.job_id().map(ToOwned::to_owned),
+        job_name: properties.job_name().map(ToOwned::to_owned),
+        status: properties
+            .job_status()
+            .map(|status| status.as_str().to_string()),
+        message: properties.message().map(ToOwned::to_owned),
+        target_language_codes: properties
+            .target_language_codes()
+            .map(|codes| codes.to_vec())
+            .unwrap_or_default(),
+        output_s3_uri: properties
+            .output_data_config()
+            .and_then(|config| config.s3_uri())
+            .map(ToOwned::to_owned),
+    }
+}
+
+#[async_trait(?Send)]
+impl S3Service for AwsS3 {
+    async fn get_buckets(&self) -> Result<Vec<String>> {
+        let output = self
+            .sdk
+            .list_buckets()
+            .send()
+            .await
+            .map_err(service_error)?;
+        Ok(output
+            .buckets()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|bucket| bucket.name())
+            .map(ToOwned::to_owned)
+            .collect())
+    }
+
+    async fn retrieve_keys_in_a_bucket(&self, bucket_name: &str) -> Result<Vec<String>> {
+        self.list_objects_given_prefix(bucket_name, "").await
+    }
+
+    async fn list_objects_given_prefix(
+        &self,
+        bucket_name: &str,
+        prefix: &str,
+    ) -> Result<Vec<String>> {
+        let mut keys = Vec::new();
+        let mut continuation_token = None;
+        loop {
+            let output = self
+                .sdk
+                .list_objects_v2()
+                .bucket(bucket_name)
+                .prefix(prefix)
+                .set_continuation_token(continuation_token)
+                .send()
+                .await
+                .map_err(service_error)?;
+            keys.extend(
+                output
+                    .contents()
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|object| object.key())
+                    .map(ToOwned::to_owned),
+            );
+            continuation_token = output.next_continuation_token().map(ToOwned::to_owned);
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+        Ok(keys)
+    }
+
+    async fn upload_content_to_a_bucket(
+        &self,
+        bucket_name: &str,
+        local_path: &str,
+        key: &str,
+    ) -> Result<()> {
+        let content = read(local_path).with_path(local_path)?;
+        self.sdk
+            .put_object()
+            .bucket(bucket_name)
+            .key(key)
+            .body(ByteStream::from(content))
+            .send()
+            .await
+            .map_err(service_error)?;
+        Ok(())
+    }
+
+    async fn download_content_from_bucket(
+        &self,
+        bucket_name: &str,
+        key: &str,
+        local_prefix: &str,
+    ) -> Result<()> {
+        let local_path = Path::new(local_prefix).join(local_name(key));
+        self.download(bucket_name, key, &local_path).await
+    }
+
+    async fn download_transcription_results(
+        &self,
+        bucket_name: &str,
+        local_dir: &Path,
+    ) -> Result<()> {
+        let outputs = local_dir.join("TranscribeOutputs");
+        create_dir_all(&outputs).with_path(&outputs)?;
+        // Transcribe writes '<job name>.json' at the top of the output bucket.
+        for key in self.retrieve_keys_in_a_bucket(bucket_name).await? {
+            if key.ends_with(".json") && !key.contains('/') {
+                let local_path = outputs.join(local_name(&key));
+                self.download(bucket_name, &key, &local_path).await?;
+                println!("Downloaded '{}'", local_path.display());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl PollyService for AwsPolly {
+    async fn start_speech_synthesis_task(
+        &self,
+        engine_name: &str,
+        voice_id: &str,
+        language_code: Option<&str>,
+        text_type: &str,
+        text: &str,
+        output_format: &str,
+        bucket_name: &str,
+        lexicon_names: &[String],
+        speech_mark_types: &[String],
+    ) -> Result<String> {
+        let output = self
+            .sdk
+            .start_speech_synthesis_task()
+            .engine(Engine::from(engine_name))
+            .voice_id(VoiceId::from(voice_id))
+            .set_language_code(language_code.map(LanguageCode::from))
+            .text_type(TextType::from(text_type))
+            .text(text)
+            .output_format(OutputFormat::from(output_format))
+            .output_s3_bucket_name(bucket_name)
+            .set_lexicon_names(lexicon_names_of(lexicon_names))
+            .set_speech_mark_types(speech_mark_types_of(speech_mark_types))
+            .send()
+            .await
+            .map_err(service_error)?;
+        output
+            .synthesis_task()
+            .and_then(|task| task.task_id())
+            .map(ToOwned::to_owned)
+            .ok_or_else(|| missing_id("speech synthesis task"))
+    }
+
+    async fn generate_all_available_voices_in_mp3(
+        &self,
+        text_type: &str,
+        text: &str,
+        language_code: &str,
+        engine_name: &str,
+        path_prefix: &str,
+        lexicon_names: &[String],
+    ) -> Result<()> {
+        // The voices are synthesized here rather than by `aws_apis`, which has
+        // no way to apply the lexicons.
+        let mut voice_ids = Vec::new();
+        let mut next_token = None;
+        loop {
+            let output = self
+                .sdk
+                .describe_voices()
+                .engine(Engine::from(engine_name))
+                .language_code(LanguageCode::from(language_code))
+                .set_next_token(next_token)
+                .send()
+                .await
+                .map_err(service_error)?;
+            voice_ids.extend(
+                output
+                    .voices()
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|voice| voice.id())
+                    .map(|voice_id| voice_id.as_str().to_owned()),
+            );
+            next_token = output.next_token().map(ToOwned::to_owned);
+            if next_token.is_none() {
+                break;
+            }
+        }
+        for voice_id in voice_ids {
+            let audio = self
+                .synthesize_speech(
+                    engine_name,
+                    &voice_id,
+                    Some(language_code),
+                    text_type,
+                    text,
+                    "mp3",
+                    lexicon_names,
+                    &[],
+                )
+                .await?;
+            let audio_path = Path::new(path_prefix).join(format!("{voice_id}.mp3"));
+            write(&audio_path, audio).with_path(&audio_path)?;
+            println!("Generated '{}'", audio_path.display());
+        }
+        Ok(())
+    }
+
+    async fn synthesize_speech(
+        &self,
+        engine_name: &str,
+        voice_id: &str,
+        language_code: Option<&str>,
+        text_type: &str,
+        text: &str,
+        output_format: &str,
+        lexicon_names: &[String],
+        speech_mark_types: &[String],
+    ) -> Result<Vec<u8>> {
+        let output = self
+            .sdk
+            .synthesize_speech()
+            .engine(Engine::from(engine_name))
+            .voice_id(VoiceId::from(voice_id))
+            .set_language_code(language_code.map(LanguageCode::from))
+            .text_type(TextType::from(text_type))
+            .text(text)
+            .output_format(OutputFormat::from(output_format))
+            .set_lexicon_names(lexicon_names_of(lexicon_names))
+            .set_speech_mark_types(speech_mark_types_of(speech_mark_types))
+            .send()
+            .await
+            .map_err(service_error)?;
+        let audio = output
+            .audio_stream
+            .collect()
+            .await
+            .map_err(|error| Error::Service(error.to_string()))?;
+        Ok(audio.into_bytes().to_vec())
+    }
+
+    async fn put_lexicon(&self, name: &str, content: &str) -> Result<()> {
+        self.sdk
+            .put_lexicon()
+            .name(name)
+            .content(content)
+            .send()
+            .await
+            .map_err(service_error)?;
+        Ok(())
+    }
+
+    async fn list_lexicons(&self) -> Result<Vec<Lexicon>> {
+        let mut lexicons = Vec::new();
+        let mut next_token = None;
+        loop {
+            let output = self
+                .sdk
+                .list_lexicons()
+                .set_next_token(next_token)
+                .send()
+                .await
+                .map_err(service_error)?;
+            lexicons.extend(output.lexicons().unwrap_or_default().iter().map(|lexicon| {
+                lexicon_of(lexicon.name().unwrap_or_default(), lexicon.attributes())
+            }));
+            next_token = output.next_token().map(ToOwned::to_owned);
+            if next_token.is_none() {
+                return Ok(lexicons);
+            }
+        }
+    }
+
+    async fn get_lexicon(&self, name: &str) -> Result<(Lexicon, String)> {
+        let output = self
+            .sdk
+            .get_lexicon()
+            .name(name)
+            .send()
+            .await
+            .map_err(service_error)?;
+        let content = output
+            .lexicon()
+            .and_then(|lexicon| lexicon.content())
+            .unwrap_or_default()
+            .to_owned();
+        Ok((lexicon_of(name, output.lexicon_attributes()), content))
+    }
+
+    async fn delete_lexicon(&self, name: &str) -> Result<()> {
+        self.sdk
+            .delete_lexicon()
+            .name(name)
+            .send()
+            .await
+            .map_err(service_error)?;
+        Ok(())
+    }
+
+    async fn get_speech_synthesis_result(&self, task_id: &str) -> Result<Option<SynthesisTask>> {
+        let output = self
+            .sdk
+            .get_speech_synthesis_task()
+            .task_id(task_id)
+            .send()
+            .await
+            .map_err(service_error)?;
+        Ok(output.synthesis_task().map(synthesis_task_of))
+    }
+
+    async fn list_speech_synthesis_tasks(&self) -> Result<Vec<SynthesisTask>> {
+        let mut tasks = Vec::new();
+        let mut next_token = None;
+        loop {
+            let output = self
+                .sdk
+                .list_speech_synthesis_tasks()
+                .set_next_token(next_token)
+                .send()
+                .await
+                .map_err(service_error)?;
+            tasks.extend(
+                output
+                    .synthesis_tasks()
+                    .unwrap_or_default()
+                    .iter()
+                    .map(synthesis_task_of),
+            );
+            next_token = output.next_token().map(ToOwned::to_owned);
+            if next_token.is_none() {
+                break;
+            }
+        }
+        Ok(tasks)
+    }
+
+    async fn describe_voices(&self) -> Result<Vec<Voice>> {
+        let info = self.ops.describe_voices().await;
+        let mut voices = Vec::new();
+        info.into_iter().for_each(|voice_info| {
+            if let (
+                Some(gender),
+                Some(voiceid),
+                Some(lang_code),
+                Some(lang_name),
+                Some(voice_name),
+                Some(engines),
+            ) = (
+                voice_info.get_gender(),
+                voice_info.get_voiceid(),
+                voice_info.get_language_code(),
+                voice_info.get_language_name(),
+                voice_info.get_voice_name(),
+                voice_info.get_supported_engines(),
+            ) {
+                voices.push(Voice {
+                    gender: gender.to_string(),
+                    voice_id: voiceid.to_string(),
+                    language_code: lang_code.to_string(),
+                    language_name: lang_name.to_string(),
+                    voice_name: voice_name.to_string(),
+                    supported_engines: engines.into_iter().map(ToOwned::to_owned).collect(),
+                });
+            }
+        });
+        Ok(voices)
+    }
+}
+
+#[async_trait(?Send)]
+impl RekognitionService for AwsRekognition {
+    async fn detect_faces(
+        &self,
+        local_path: Option<&str>,
+        bucket_name: Option<&str>,
+        key: Option<&str>,
+    ) -> Result<Vec<FaceSummary>> {
+        let Some(image) = image_of(local_path, bucket_name, key)? else {
+            return Ok(Vec::new());
+        };
+        let output = self
+            .sdk
+            .detect_faces()
+            .image(image)
+            .attributes(Attribute::All)
+            .send()
+            .await
+            .map_err(service_error)?;
+        Ok(output
+            .face_details()
+            .unwrap_or_default()
+            .iter()
+            .map(face_summary_of)
+            .collect())
+    }
+
+    async fn detect_texts(
+        &self,
+        local_path: Option<&str>,
+        bucket_name: Option<&str>,
+        key: Option<&str>,
+    ) -> Result<Vec<TextSummary>> {
+        let Some(image) = image_of(local_path, bucket_name, key)? else {
+            return Ok(Vec::new());
+        };
+        let output = self
+            .sdk
+            .detect_text()
+            .image(image)
+            .send()
+            .await
+            .map_err(service_error)?;
+        Ok(output
+            .text_detections()
+            .unwrap_or_default()
+            .iter()
+            .map(|detection| TextSummary {
+                text: detection.detected_text().unwrap_or_default().to_string(),
+                text_type: detection
+                    .r#type()
+                    .map(|text_type| text_type.as_str().to_string())
+                    .unwrap_or_default(),
+                confidence: detection.confidence().unwrap_or_default(),
+                polygon: detection
+                    .geometry()
+                    .and_then(|geometry| geometry.polygon())
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|point| Point {
+                        x: point.x().unwrap_or_default(),
+                        y: point.y().unwrap_or_default(),
+                    })
+                    .collect(),
+            })
+            .collect())
+    }
+
+    async fn recognize_celebrities(
+        &self,
+        local_path: Option<&str>,
+        bucket_name: Option<&str>,
+        key: Option<&str>,
+    ) -> Result<Vec<CelebrityFace>> {
+        let Some(image) = image_of(local_path, bucket_name, key)? else {
+            return Ok(Vec::new());
+        };
+        let output = self
+            .sdk
+            .recognize_celebrities()
+            .image(image)
+            .send()
+            .await
+            .map_err(service_error)?;
+        let mut faces: Vec<CelebrityFace> = output
+            .celebrity_faces()
+            .unwrap_or_default()
+            .iter()
+            .map(|celebrity| CelebrityFace {
+                name: celebrity.name().map(ToOwned::to_owned),
+                id: celebrity.id().map(ToOwned::to_owned),
+                match_confidence: celebrity.match_confidence(),
+                urls: celebrity.urls().unwrap_or_default().to_vec(),
+                bounding_box: celebrity
+                    .face()
+                    .and_then(|face| face.bounding_box())
+                    .map(bounding_box_of),
+            })
+            .collect();
+        faces.extend(
+            output
+                .unrecognized_faces()
+                .unwrap_or_default()
+                .iter()
+                .map(|face| CelebrityFace {
+                    bounding_box: face.bounding_box().map(bounding_box_of),
+                    ..CelebrityFace::default()
+                }),
+        );
+        Ok(faces)
+    }
+
+    async fn start_face_detection_task(&self, bucket_name: &str, key: &str) -> Result<String> {
+        let output = self
+            .sdk
+            .start_face_detection()
+            .video(video_of(bucket_name, key))
+            .face_attributes(FaceAttributes::All)
+            .send()
+            .await
+            .map_err(service_error)?;
+        output
+            .job_id()
+            .map(ToOwned::to_owned)
+            .ok_or_else(|| missing_id("face detection job"))
+    }
+
+    async fn get_face_detection_results(
+        &self,
+        job_id: &str,
+        local_dir: &Path,
+    ) -> Result<DetectionJob> {
+        let mut job = DetectionJob::default();
+        let mut lines = Vec::new();
+        let mut next_token = None;
+        loop {
+            let output = self
+                .sdk
+                .get_face_detection()
+                .job_id(job_id)
+                .set_next_token(next_token)
+                .send()
+                .await
+                .map_err(service_error)?;
+            job.status = output.job_status().map(|status| status.as_str().to_owned());
+            job.status_message = output.status_message().map(ToOwned::to_owned);
+            for detection in output.faces().unwrap_or_default() {
+                let Some(face) = detection.face() else {
+                    continue;
+                };
+                let face = face_summary_of(face);
+                lines.extend([
+                    format!("Timestamp: {} ms", detection.timestamp()),
+                    format!(
+                        "Gender: {}, with a confidence level of {}",
+                        face.gender, face.gender_confidence
+                    ),
+                    format!("Age Range: {} - {}", face.age_low, face.age_high),
+                    format!(
+                        "Smile: {}, with a confidence level of {}",
+                        face.smile, face.smile_confidence
+                    ),
+                    format!(
+                        "Beard: {}, with a confidence level of {}",
+                        face.beard, face.beard_confidence
+                    ),
+                    format!(
+                        "Bounding Box Details: Width: {}, Height: {}, Left: {}, Top: {}\n",
+                        face.width, face.height, face.left, face.top
+                    ),
+                ]);
+            }
+            next_token = output.next_token().map(ToOwned::to_owned);
+            if next_token.is_none() {
+                break;
+            }
+        }
+        if job.status.as_deref() == Some("SUCCEEDED") {
+            write_job_results(
+                local_dir,
+                "face_detection_results",
+                "Face Detection Results",
+                &lines,
+            )?;
+        }
+        Ok(job)
+    }
+
+    async fn start_text_detection_task(&self, bucket_name: &str, key: &str) -> Result<String> {
+        let output = self
+            .sdk
+            .start_text_detection()
+            .video(video_of(bucket_name, key))
+            .send()
+            .await
+            .map_err(service_error)?;
+        output
+            .job_id()
+            .map(ToOwned::to_owned)
+            .ok_or_else(|| missing_id("text detection job"))
+    }
+
+    async fn get_text_detection_results(
+        &self,
+        job_id: &str,
+        local_dir: &Path,
+    ) -> Result<DetectionJob> {
+        let mut job = DetectionJob::default();
+        let mut lines = Vec::new();
+        let mut next_token = None;
+        loop {
+            let output = self
+                .sdk
+                .get_text_detection()
+                .job_id(job_id)
+                .set_next_token(next_token)
+                .send()
+                .await
+                .map_err(service_error)?;
+            job.status = output.job_status().map(|status| status.as_str().to_owned());
+            job.status_message = output.status_message().map(ToOwned::to_owned);
+            for detection in output.text_detections().unwrap_or_default() {
+                let Some(text) = detection.text_detection() else {
+                    continue;
+                };
+                lines.extend([
+                    format!("Timestamp: {} ms", detection.timestamp()),
+                    format!(
+                        "Detected Text: {}",
+                        text.detected_text().unwrap_or_default()
+                    ),
+                    format!(
+                        "Text Type: {}",
+                        text.r#type()
+                            .map(|text_type| text_type.as_str())
+                            .unwrap_or_default()
+                    ),
+                    format!("Confidence: {}\n", text.confidence().unwrap_or_default()),
+                ]);
+            }
+            next_token = output.next_token().map(ToOwned::to_owned);
+            if next_token.is_none() {
+                break;
+            }
+        }
+        if job.status.as_deref() == Some("SUCCEEDED") {
+            write_job_results(
+                local_dir,
+                "text_detection_results",
+                "Text Detection Results",
+                &lines,
+            )?;
+        }
+        Ok(job)
+    }
+}
+
+#[async_trait(?Send)]
+impl TranscribeService for AwsTranscribe {
+    async fn start_transcribe_task(
+        &self,
+        bucket_name: &str,
+        media_uri: &str,
+        media_format: &str,
+        job_name: &str,
+    ) -> Result<()> {
+        self.sdk
+            .start_transcription_job()
+            .transcription_job_name(job_name)
+            .media(Media::builder().media_file_uri(media_uri).build())
+            .media_format(MediaFormat::from(media_format))
+            .identify_language(true)
+            .output_bucket_name(bucket_name)
+            .send()
+            .await
+            .map_err(service_error)?;
+        Ok(())
+    }
+
+    async fn get_transcribe_results(&self, job_name: &str) -> Result<Option<TranscriptionState>> {
+        let output = self
+            .sdk
+            .get_transcription_job()
+            .transcription_job_name(job_name)
+            .send()
+            .await
+            .map_err(service_error)?;
+        Ok(output.transcription_job().map(|job| TranscriptionState {
+            status: job
+                .transcription_job_status()
+                .map(|status| status.as_str().to_string()),
+            failure_reason: job.failure_reason().map(ToOwned::to_owned),
+            language_code: job.language_code().map(|code| code.as_str().to_string()),
+            media_format: job.media_format().map(|format| format.as_str().to_string()),
+            media_uri: job
+                .media()
+                .and_then(|media| media.media_file_uri())
+                .map(ToOwned::to_owned),
+            transcript_uri: job
+                .transcript()
+                .and_then(|transcript| transcript.transcript_file_uri())
+                .map(ToOwned::to_owned),
+            creation_time: time_of(job.creation_time()),
+            completion_time: time_of(job.completion_time()),
+        }))
+    }
+}
+
+#[async_trait(?Send)]
+impl TranslateService for AwsTranslate {
+    async fn list_languages(&self, print: bool) -> Result<Vec<Language>> {
+        let (lang_names, lang_codes) = self.ops.list_languages(print).await;
+        Ok(lang_names
+            .into_iter()
+            .zip(lang_codes)
+            .map(|(name, code)| Language { name, code })
+            .collect())
+    }
+
+    async fn translate_text(&self, text: &str, target_lang_code: &str) -> Result<Translation> {
+        let output = self
+            .sdk
+            .translate_text()
+            .text(text)
+            .source_language_code("auto")
+            .target_language_code(target_lang_code)
+            .send()
+            .await
+            .map_err(service_error)?;
+        Ok(Translation {
+            source_language_code: output
+                .source_language_code()
+                .unwrap_or_default()
+                .to_string(),
+            target_language_code: output
+                .target_language_code()
+                .unwrap_or_default()
+                .to_string(),
+            translated_text: output.translated_text().unwrap_or_default().to_string(),
+        })
+    }
+
+    async fn translate_document(
+        &self,
+        document_type: &str,
+        document_path: &str,
+        target_lang_code: &str,
+    ) -> Result<Vec<u8>> {
+        let content = read(document_path).with_path(document_path)?;
+        let output = self
+            .sdk
+            .translate_document()
+            .document(
+                Document::builder()
+                    .content(Blob::new(content))
+                    .content_type(content_type_of(document_type))
+                    .build(),
+            )
+            .source_language_code("auto")
+            .target_language_code(target_lang_code)
+            .send()
+            .await
+            .map_err(service_error)?;
+        output
+            .translated_document()
+            .and_then(|document| document.content())
+            .map(|content| content.as_ref().to_vec())
+            .ok_or_else(|| Error::Service("No translated document was returned".into()))
+    }
+
+    async fn start_text_translation_job(
+        &self,
+        job_name: &str,
+        target_lang_codes: Vec<String>,
+        input_s3_uri: &str,
+        document_type: &str,
+        output_s3_uri: &str,
+        role_arn: &str,
+    ) -> Result<String> {
+        let output = self
+            .sdk
+            .start_text_translation_job()
+            .job_name(job_name)
+            .source_language_code("auto")
+            .set_target_language_codes(Some(target_lang_codes))
+            .input_data_config(
+                InputDataConfig::builder()
+                    .s3_uri(input_s3_uri)
+                    .content_type(content_type_of(document_type))
+                    .build(),
+            )
+            .output_data_config(OutputDataConfig::builder().s3_uri(output_s3_uri).build())
+            .data_access_role_arn(role_arn)
+            .send()
+            .await
+            .map_err(service_error)?;
+        output
+            .job_id()
+            .map(ToOwned::to_owned)
+            .ok_or_else(|| missing_id("text translation job"))
+    }
+
+    async fn describe_text_translation_job(&self, job_id: &str) -> Result<TranslationJob> {
+        let output = self
+            .sdk
+            .describe_text_translation_job()
+            .job_id(job_id)
+            .send()
+            .await
+            .map_err(service_error)?;
+        Ok(output
+            .text_translation_job_properties()
+            .map(translation_job_of)
+            .unwrap_or_default())
+    }
+
+    async fn list_translation_jobs(&self) -> Result<Vec<TranslationJob>> {
+        let mut jobs = Vec::new();
+        let mut next_token = None;
+        loop {
+            let output = self
+                .sdk
+                .list_text_translation_jobs()
+                .set_next_token(next_token)
+                .send()
+                .await
+                .map_err(service_error)?;
+            jobs.extend(
+                output
+                    .text_translation_job_properties_list()
+                    .unwrap_or_default()
+                    .iter()
+                    .map(translation_job_of),
+            );
+            next_token = output.next_token().map(ToOwned::to_owned);
+            if next_token.is_none() {
+                break;
+            }
+        }
+        Ok(jobs)
+    }
+}
@@ -0,0 +1,622 @@
+//! An in-process backend answering every service call from a directory of fixture files.
+//!
+//! The directory is laid out as follows, and every file is optional:
+//!
+//! ```text
+//! objects/<bucket>/<key>     the buckets and their objects; uploads to the existing buckets are copied here
+//! faces.json                 {"<local path or bucket/key>": [FaceSummary, ...]}
+//! texts.json                 {"<local path or bucket/key>": [TextSummary, ...]}
+//! celebrities.json           {"<local path or bucket/key>": [CelebrityFace, ...]}
+//! voices.json                [Voice, ...]
+//! synthesis_tasks.json       {"<task id>": SynthesisTask}
+//! detection_jobs.json        {"<job id>": DetectionJob}
+//! transcription_jobs.json    {"<job name>": TranscriptionState}
+//! translation_jobs.json      {"<job id>": TranslationJob}
+//! languages.json             [Language, ...]
+//! lexicons/<name>.pls        the pronunciation lexicons; stored lexicons are written here
+//! ```
+use async_trait::async_trait;
+use colored::Colorize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::fs::{copy, create_dir_all, read, read_dir, read_to_string, remove_file, write, File};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::{Error, Result, WithPath};
+use crate::keys::local_name;
+use crate::pls;
+
+use super::{
+    CelebrityFace, DetectionJob, FaceSummary, Language, Lexicon, PollyService, RekognitionService,
+    S3Service, SynthesisTask, TextSummary, TranscribeService, TranscriptionState, TranslateService,
+    Translation, TranslationJob, Voice,
+};
+
+#[derive(Clone)]
+pub struct FakeBackend {
+    root: PathBuf,
+}
+
+impl FakeBackend {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Reads a fixture file, falling back to the default value when it doesn't exist.
+    fn fixture<T: DeserializeOwned + Default>(&self, name: &str) -> Result<T> {
+        let path = self.root.join(name);
+        match read_to_string(&path) {
+            Ok(json_data) => serde_json::from_str(&json_data).with_path(&path),
+            Err(_) => Ok(T::default()),
+        }
+    }
+
+    fn bucket_dir(&self, bucket_name: &str) -> PathBuf {
+        self.root.join("objects").join(bucket_name)
+    }
+
+    fn keys_in(&self, bucket_name: &str) -> Vec<String> {
+        let bucket_dir = self.bucket_dir(bucket_name);
+        let mut keys = Vec::new();
+        collect_keys(&bucket_dir, &bucket_dir, &mut keys);
+        keys.sort();
+        keys
+    }
+
+    fn lexicon_path(&self, name: &str) -> PathBuf {
+        self.root.join("lexicons").join(format!("{name}.pls"))
+    }
+
+    /// Fails like Polly does when a lexicon applied to a request isn't stored.
+    fn applied_lexicons(&self, lexicon_names: &[String]) -> Result<String> {
+        if let Some(name) = lexicon_names
+            .iter()
+            .find(|name| !self.lexicon_path(name).exists())
+        {
+            return Err(Error::Service(format!(
+                "The lexicon '{name}' was not found"
+            )));
+        }
+        Ok(match lexicon_names {
+            [] => String::new(),
+            names => format!(" applying {}", names.join(", ")),
+        })
+    }
+
+    fn notice(&self, message: &str) {
+        println!("{} {}\n", "[fake]".yellow().bold(), message);
+    }
+}
+
+/// The output format of a synthesis, with the speech mark types of 'json'.
+fn output_of(output_format: &str, speech_mark_types: &[String]) -> String {
+    match (output_format, speech_mark_types) {
+        ("json", [_, ..]) => format!("{} speech marks", speech_mark_types.join(", ")),
+        _ => output_format.to_string(),
+    }
+}
+
+/// The words of the text along with the byte offsets they start at.
+fn words_of(text: &str) -> Vec<(usize, &str)> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (index, c) in text.char_indices().chain([(text.len(), ' ')]) {
+        match (c.is_whitespace(), start) {
+            (true, Some(word_start)) => {
+                words.push((word_start, &text[word_start..index]));
+                start = None;
+            }
+            (false, None) => start = Some(index),
+            _ => {}
+        }
+    }
+    words
+}
+
+/// Sentence and word marks as if every character took 50 milliseconds to
+/// speak, leaving out the SSML tags.
+fn fake_speech_marks(text: &str, speech_mark_types: &[String]) -> String {
+    let wants = |kind: &str| speech_mark_types.iter().any(|wanted| wanted == kind);
+    let mut json_lines = String::new();
+    let mut mark = |kind: &str, start: usize, value: &str| {
+        json_lines.push_str(
+            &serde_json::json!({
+                "time": text[..start].chars().count() * 50,
+                "type": kind,
+                "start": start,
+                "end": start + value.len(),
+                "value": value,
+            })
+            .to_string(),
+        );
+        json_lines.push('\n');
+    };
+    // The words of the sentence that is being read, which come after its mark.
+    let mut sentence: Vec<(usize, &str)> = Vec::new();
+    let mut words = words_of(text)
+        .into_iter()
+        .filter(|(_, word)| !word.contains(['<', '>']))
+        .peekable();
+    while let Some((start, word)) = words.next() {
+        sentence.push((start, word));
+        if !word.ends_with(['.', '!', '?']) && words.peek().is_some() {
+            continue;
+        }
+        let sentence_start = sentence[0].0;
+        if wants("sentence") {
+            mark(
+                "sentence",
+                sentence_start,
+                &text[sentence_start..start + word.len()],
+            );
+        }
+        for (start, word) in sentence.drain(..) {
+            if wants("word") {
+                mark("word", start, word.trim_end_matches(['.', ',', '!', '?']));
+            }
+        }
+    }
+    json_lines
+}
+
+fn collect_keys(bucket_dir: &Path, dir: &Path, keys: &mut Vec<String>) {
+    let Ok(entries) = read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_keys(bucket_dir, &path, keys);
+        } else if let Ok(relative) = path.strip_prefix(bucket_dir) {
+            let key: Vec<String> = relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy().into_owned())
+                .collect();
+            keys.push(key.join("/"));
+        }
+    }
+}
+
+/// Makes up an ID for a started task, which has no fixture until one is added for it.
+fn new_id(prefix: &str) -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos())
+        .unwrap_or_default();
+    format!("fake-{prefix}-{nanos:x}")
+}
+
+/// How an image is keyed in the image fixtures: by its local path as given, or
+/// by `<bucket>/<key>`.
+fn fixture_image(
+    local_path: Option<&str>,
+    bucket_name: Option<&str>,
+    key: Option<&str>,
+) -> Option<String> {
+    match (local_path, bucket_name, key) {
+        (Some(local_path), _, _) => Some(local_path.to_string()),
+        (None, Some(bucket_name), Some(key)) => Some(format!("{bucket_name}/{key}")),
+        _ => None,
+    }
+}
+
+#[async_trait(?Send)]
+impl S3Service for FakeBackend {
+    async fn get_buckets(&self) -> Result<Vec<String>> {
+        let mut buckets: Vec<String> = read_dir(self.root.join("objects"))
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter(|entry| entry.path().is_dir())
+                    .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                    .collect()
+            })
+            .unwrap_or_default();
+        buckets.sort();
+        Ok(buckets)
+    }
+
+    async fn retrieve_keys_in_a_bucket(&self, bucket_name: &str) -> Result<Vec<String>> {
+        Ok(self.keys_in(bucket_name))
+    }
+
+    async fn list_objects_given_prefix(
+        &self,
+        bucket_name: &str,
+        prefix: &str,
+    ) -> Result<Vec<String>> {
+        Ok(self
+            .keys_in(bucket_name)
+            .into_iter()
+            .filter(|key| key.starts_with(prefix))
+            .collect())
+    }
+
+    async fn upload_content_to_a_bucket(
+        &self,
+        bucket_name: &str,
+        local_path: &str,
+        key: &str,
+    ) -> Result<()> {
+        let bucket_dir = self.bucket_dir(bucket_name);
+        if !bucket_dir.is_dir() {
+            return Err(Error::Service(format!(
+                "The bucket '{bucket_name}' doesn't exist"
+            )));
+        }
+        let destination = bucket_dir.join(key);
+        if let Some(parent) = destination.parent() {
+            create_dir_all(parent).with_path(parent)?;
+        }
+        copy(local_path, &destination).with_path(local_path)?;
+        self.notice(&format!("Uploaded '{local_path}' to '{bucket_name}/{key}'"));
+        Ok(())
+    }
+
+    async fn download_content_from_bucket(
+        &self,
+        bucket_name: &str,
+        key: &str,
+        local_prefix: &str,
+    ) -> Result<()> {
+        let destination = Path::new(local_prefix).join(local_name(key));
+        let object = self.bucket_dir(bucket_name).join(key);
+        if !object.is_file() {
+            return Err(Error::Service(format!(
+                "The object '{key}' doesn't exist in the '{bucket_name}' bucket"
+            )));
+        }
+        copy(&object, &destination).with_path(&destination)?;
+        Ok(())
+    }
+
+    async fn download_transcription_results(
+        &self,
+        bucket_name: &str,
+        local_dir: &Path,
+    ) -> Result<()> {
+        let outputs = local_dir.join("TranscribeOutputs");
+        create_dir_all(&outputs).with_path(&outputs)?;
+        for key in self.keys_in(bucket_name) {
+            if key.ends_with(".json") {
+                let destination = outputs.join(local_name(&key));
+                copy(self.bucket_dir(bucket_name).join(&key), &destination)
+                    .with_path(&destination)?;
+            }
+        }
+        self.notice(&format!(
+            "The transcription results are saved in '{}'",
+            outputs.display()
+        ));
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl PollyService for FakeBackend {
+    async fn start_speech_synthesis_task(
+        &self,
+        engine_name: &str,
+        voice_id: &str,
+        language_code: Option<&str>,
+        _text_type: &str,
+        text: &str,
+        output_format: &str,
+        bucket_name: &str,
+        lexicon_names: &[String],
+        speech_mark_types: &[String],
+    ) -> Result<String> {
+        let applied = self.applied_lexicons(lexicon_names)?;
+        let language_code = language_code.unwrap_or("the voice's language");
+        self.notice(&format!(
+            "Speech synthesis of {} characters with the '{voice_id}' voice ({engine_name}, {language_code}) into '{bucket_name}' as {}{applied}",
+            text.chars().count(),
+            output_of(output_format, speech_mark_types)
+        ));
+        Ok(new_id("synthesis"))
+    }
+
+    async fn generate_all_available_voices_in_mp3(
+        &self,
+        _text_type: &str,
+        _text: &str,
+        language_code: &str,
+        engine_name: &str,
+        path_prefix: &str,
+        lexicon_names: &[String],
+    ) -> Result<()> {
+        let applied = self.applied_lexicons(lexicon_names)?;
+        let voices: Vec<Voice> = self.fixture("voices.json")?;
+        for voice in voices.into_iter().filter(|voice| {
+            voice.language_code == language_code
+                && voice
+                    .supported_engines
+                    .iter()
+                    .any(|engine| engine == engine_name)
+        }) {
+            let audio_path = Path::new(path_prefix).join(format!("{}.mp3", voice.voice_id));
+            File::create(&audio_path).with_path(&audio_path)?;
+            self.notice(&format!("Generated '{}'{applied}", audio_path.display()));
+        }
+        Ok(())
+    }
+
+    async fn synthesize_speech(
+        &self,
+        engine_name: &str,
+        voice_id: &str,
+        language_code: Option<&str>,
+        _text_type: &str,
+        text: &str,
+        output_format: &str,
+        lexicon_names: &[String],
+        speech_mark_types: &[String],
+    ) -> Result<Vec<u8>> {
+        let applied = self.applied_lexicons(lexicon_names)?;
+        let language_code = language_code.unwrap_or("the voice's language");
+        self.notice(&format!(
+            "Synthesized {} characters with the '{voice_id}' voice ({engine_name}, {language_code}) as {}{applied}",
+            text.chars().count(),
+            output_of(output_format, speech_mark_types)
+        ));
+        if output_format == "json" {
+            return Ok(fake_speech_marks(text, speech_mark_types).into_bytes());
+        }
+        // Stands in for the audio, so that the parts can be told apart in the file.
+        Ok(format!("[{voice_id}: {text}]\n").into_bytes())
+    }
+
+    async fn put_lexicon(&self, name: &str, content: &str) -> Result<()> {
+        let lexicon_path = self.lexicon_path(name);
+        if let Some(dir) = lexicon_path.parent() {
+            create_dir_all(dir).with_path(dir)?;
+        }
+        write(&lexicon_path, content).with_path(&lexicon_path)?;
+        self.notice(&format!("Stored the lexicon '{name}'"));
+        Ok(())
+    }
+
+    async fn list_lexicons(&self) -> Result<Vec<Lexicon>> {
+        let lexicons_dir = self.root.join("lexicons");
+        let Ok(entries) = read_dir(&lexicons_dir) else {
+            return Ok(Vec::new());
+        };
+        let mut names: Vec<String> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                let name = path.file_stem()?.to_string_lossy().into_owned();
+                (path.extension()? == "pls").then_some(name)
+            })
+            .collect();
+        names.sort();
+        let mut lexicons = Vec::new();
+        for name in names {
+            lexicons.push(self.get_lexicon(&name).await?.0);
+        }
+        Ok(lexicons)
+    }
+
+    async fn get_lexicon(&self, name: &str) -> Result<(Lexicon, String)> {
+        let lexicon_path = self.lexicon_path(name);
+        let Ok(content) = read_to_string(&lexicon_path) else {
+            return Err(Error::Service(format!(
+                "The lexicon '{name}' was not found"
+            )));
+        };
+        let mut lexicon = Lexicon {
+            name: name.to_string(),
+            size: content.chars().count() as i32,
+            ..Lexicon::default()
+        };
+        if let Ok(summary) = pls::validate(&content) {
+            lexicon.language_code = Some(summary.language_code);
+            lexicon.alphabet = Some(summary.alphabet);
+            lexicon.lexemes_count = summary.lexemes as i32;
+        }
+        Ok((lexicon, content))
+    }
+
+    async fn delete_lexicon(&self, name: &str) -> Result<()> {
+        let lexicon_path = self.lexicon_path(name);
+        if !lexicon_path.exists() {
+            return Err(Error::Service(format!(
+                "The lexicon '{name}' was not found"
+            )));
+        }
+        remove_file(&lexicon_path).with_path(&lexicon_path)?;
+        self.notice(&format!("Deleted the lexicon '{name}'"));
+        Ok(())
+    }
+
+    async fn get_speech_synthesis_result(&self, task_id: &str) -> Result<Option<SynthesisTask>> {
+        let mut tasks: HashMap<String, SynthesisTask> = self.fixture("synthesis_tasks.json")?;
+        Ok(tasks.remove(task_id).map(|task| SynthesisTask {
+            task_id: Some(task_id.to_string()),
+            ..task
+        }))
+    }
+
+    async fn list_speech_synthesis_tasks(&self) -> Result<Vec<SynthesisTask>> {
+        let tasks: HashMap<String, SynthesisTask> = self.fixture("synthesis_tasks.json")?;
+        let mut tasks: Vec<SynthesisTask> = tasks
+            .into_iter()
+            .map(|(task_id, task)| SynthesisTask {
+                task_id: Some(task_id),
+                ..task
+            })
+            .collect();
+        tasks.sort_by(|a, b| a.task_id.cmp(&b.task_id));
+        Ok(tasks)
+    }
+
+    async fn describe_voices(&self) -> Result<Vec<Voice>> {
+        self.fixture("voices.json")
+    }
+}
+
+#[async_trait(?Send)]
+impl RekognitionService for FakeBackend {
+    async fn detect_faces(
+        &self,
+        local_path: Option<&str>,
+        bucket_name: Option<&str>,
+        key: Option<&str>,
+    ) -> Result<Vec<FaceSummary>> {
+        let Some(image) = fixture_image(local_path, bucket_name, key) else {
+            return Ok(Vec::new());
+        };
+        let mut faces: HashMap<String, Vec<FaceSummary>> = self.fixture("faces.json")?;
+        Ok(faces.remove(&image).unwrap_or_default())
+    }
+
+    async fn detect_texts(
+        &self,
+        local_path: Option<&str>,
+        bucket_name: Option<&str>,
+        key: Option<&str>,
+    ) -> Result<Vec<TextSummary>> {
+        let Some(image) = fixture_image(local_path, bucket_name, key) else {
+            return Ok(Vec::new());
+        };
+        let mut texts: HashMap<String, Vec<TextSummary>> = self.fixture("texts.json")?;
+        Ok(texts.remove(&image).unwrap_or_default())
+    }
+
+    async fn recognize_celebrities(
+        &self,
+        local_path: Option<&str>,
+        bucket_name: Option<&str>,
+        key: Option<&str>,
+    ) -> Result<Vec<CelebrityFace>> {
+        let Some(image) = fixture_image(local_path, bucket_name, key) else {
+            return Ok(Vec::new());
+        };
+        let mut celebrities: HashMap<String, Vec<CelebrityFace>> =
+            self.fixture("celebrities.json")?;
+        Ok(celebrities.remove(&image).unwrap_or_default())
+    }
+
+    async fn start_face_detection_task(&self, bucket_name: &str, key: &str) -> Result<String> {
+        self.notice(&format!("Face detection started for '{bucket_name}/{key}'"));
+        Ok(new_id("face-detection"))
+    }
+
+    async fn get_face_detection_results(
+        &self,
+        job_id: &str,
+        _local_dir: &Path,
+    ) -> Result<DetectionJob> {
+        let mut jobs: HashMap<String, DetectionJob> = self.fixture("detection_jobs.json")?;
+        Ok(jobs.remove(job_id).unwrap_or_default())
+    }
+
+    async fn start_text_detection_task(&self, bucket_name: &str, key: &str) -> Result<String> {
+        self.notice(&format!("Text detection started for '{bucket_name}/{key}'"));
+        Ok(new_id("text-detection"))
+    }
+
+    async fn get_text_detection_results(
+        &self,
+        job_id: &str,
+        _local_dir: &Path,
+    ) -> Result<DetectionJob> {
+        let mut jobs: HashMap<String, DetectionJob> = self.fixture("detection_jobs.json")?;
+        Ok(jobs.remove(job_id).unwrap_or_default())
+    }
+}
+
+#[async_trait(?Send)]
+impl TranscribeService for FakeBackend {
+    async fn start_transcribe_task(
+        &self,
+        bucket_name: &str,
+        media_uri: &str,
+        media_format: &str,
+        job_name: &str,
+    ) -> Result<()> {
+        self.notice(&format!(
+            "Transcription job '{job_name}' started for '{media_uri}' ({media_format}), writing into '{bucket_name}'"
+        ));
+        Ok(())
+    }
+
+    async fn get_transcribe_results(&self, job_name: &str) -> Result<Option<TranscriptionState>> {
+        let mut jobs: HashMap<String, TranscriptionState> =
+            self.fixture("transcription_jobs.json")?;
+        Ok(jobs.remove(job_name))
+    }
+}
+
+#[async_trait(?Send)]
+impl TranslateService for FakeBackend {
+    async fn list_languages(&self, print: bool) -> Result<Vec<Language>> {
+        let languages: Vec<Language> = self.fixture("languages.json")?;
+        if print {
+            for language in &languages {
+                println!(
+                    "Language Name: {}\nLanguage Code: {}\n",
+                    language.name.green().bold(),
+                    language.code.green().bold()
+                );
+            }
+        }
+        Ok(languages)
+    }
+
+    async fn translate_text(&self, text: &str, target_lang_code: &str) -> Result<Translation> {
+        Ok(Translation {
+            source_language_code: "auto".into(),
+            target_language_code: target_lang_code.into(),
+            translated_text: format!("[{target_lang_code}] {text}"),
+        })
+    }
+
+    async fn translate_document(
+        &self,
+        document_type: &str,
+        document_path: &str,
+        target_lang_code: &str,
+    ) -> Result<Vec<u8>> {
+        let content = read(document_path).with_path(document_path)?;
+        self.notice(&format!(
+            "Translated the {document_type} document '{document_path}' into '{target_lang_code}'"
+        ));
+        Ok(content)
+    }
+
+    async fn start_text_translation_job(
+        &self,
+        job_name: &str,
+        target_lang_codes: Vec<String>,
+        input_s3_uri: &str,
+        _document_type: &str,
+        output_s3_uri: &str,
+        _role_arn: &str,
+    ) -> Result<String> {
+        self.notice(&format!(
+            "Translation job '{job_name}' started from '{input_s3_uri}' to '{output_s3_uri}' for {}",
+            target_lang_codes.join(", ")
+        ));
+        Ok(new_id("translation"))
+    }
+
+    async fn describe_text_translation_job(&self, job_id: &str) -> Result<TranslationJob> {
+        let mut jobs: HashMap<String, TranslationJob> = self.fixture("translation_jobs.json")?;
+        Ok(jobs.remove(job_id).unwrap_or_default())
+    }
+
+    async fn list_translation_jobs(&self) -> Result<Vec<TranslationJob>> {
+        let jobs: HashMap<String, TranslationJob> = self.fixture("translation_jobs.json")?;
+        let mut jobs: Vec<TranslationJob> = jobs
+            .into_iter()
+            .map(|(job_id, job)| TranslationJob {
+                job_id: Some(job_id),
+                ..job
+            })
+            .collect();
+        jobs.sort_by(|a, b| a.job_id.cmp(&b.job_id));
+        Ok(jobs)
+    }
+}
@@ -0,0 +1,413 @@
+//! The service calls the client depends on.
+//!
+//! The commands only talk to these traits, so they can run either against AWS
+//! through `aws_apis` and the SDK clients, or against the fixture files of the
+//! fake backend.
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::error::Result;
+
+pub mod aws;
+pub mod fake;
+
+/// The details of a speech synthesis task as reported by Amazon Polly.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct SynthesisTask {
+    pub task_id: Option<String>,
+    pub status: Option<String>,
+    pub engine: Option<String>,
+    pub output_uri: Option<String>,
+    pub output_format: Option<String>,
+    pub text_type: Option<String>,
+    pub voice_id: Option<String>,
+    pub language_code: Option<String>,
+    pub status_reason: Option<String>,
+}
+
+/// A voice offered by Amazon Polly.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Voice {
+    pub gender: String,
+    pub voice_id: String,
+    pub language_code: String,
+    pub language_name: String,
+    pub voice_name: String,
+    pub supported_engines: Vec<String>,
+}
+
+/// A pronunciation lexicon stored in Amazon Polly.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Lexicon {
+    pub name: String,
+    pub language_code: Option<String>,
+    pub alphabet: Option<String>,
+    pub lexemes_count: i32,
+    /// The size of the lexicon in characters.
+    pub size: i32,
+}
+
+/// The attributes of a face detected by the 'DetectFaces' feature.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FaceSummary {
+    pub gender: String,
+    pub gender_confidence: f32,
+    pub age_low: i32,
+    pub age_high: i32,
+    pub smile: bool,
+    pub smile_confidence: f32,
+    pub beard: bool,
+    pub beard_confidence: f32,
+    pub width: f32,
+    pub height: f32,
+    pub left: f32,
+    pub top: f32,
+    /// How confident the service is that this is a face.
+    #[serde(default)]
+    pub confidence: f32,
+    #[serde(default)]
+    pub mustache: FaceFlag,
+    #[serde(default)]
+    pub eyeglasses: FaceFlag,
+    #[serde(default)]
+    pub sunglasses: FaceFlag,
+    #[serde(default)]
+    pub eyes_open: FaceFlag,
+    #[serde(default)]
+    pub mouth_open: FaceFlag,
+    /// The emotions, the most confident first.
+    #[serde(default)]
+    pub emotions: Vec<Emotion>,
+    #[serde(default)]
+    pub pose: Pose,
+    #[serde(default)]
+    pub quality: ImageQuality,
+    #[serde(default)]
+    pub landmarks: Vec<Landmark>,
+}
+
+/// A yes or no attribute of a face along with the confidence in it.
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct FaceFlag {
+    pub value: bool,
+    pub confidence: f32,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Emotion {
+    /// E.g. 'HAPPY', 'CALM' or 'SURPRISED'.
+    pub kind: String,
+    pub confidence: f32,
+}
+
+/// The rotation of a face in degrees.
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Pose {
+    pub roll: f32,
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+/// The brightness and sharpness of a face, from 0 to 100.
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ImageQuality {
+    pub brightness: f32,
+    pub sharpness: f32,
+}
+
+/// A facial landmark, positioned as ratios of the image width and height.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Landmark {
+    /// E.g. 'eyeLeft', 'nose' or 'mouthRight'.
+    pub kind: String,
+    pub x: f32,
+    pub y: f32,
+}
+
+/// The position of a face or text as ratios of the image width and height.
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct BoundingBox {
+    pub width: f32,
+    pub height: f32,
+    pub left: f32,
+    pub top: f32,
+}
+
+/// A face found by the 'RecognizeCelebrities' feature, with the details of the
+/// celebrity when it was recognized.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct CelebrityFace {
+    pub name: Option<String>,
+    pub id: Option<String>,
+    pub match_confidence: Option<f32>,
+    #[serde(default)]
+    pub urls: Vec<String>,
+    pub bounding_box: Option<BoundingBox>,
+}
+
+/// A corner of the polygon around a detected text, as ratios of the image width
+/// and height.
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+/// A line or word found by the 'DetectText' feature.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TextSummary {
+    pub text: String,
+    /// 'LINE' or 'WORD'.
+    pub text_type: String,
+    pub confidence: f32,
+    /// The corners of the text, which is tighter than its bounding box when
+    /// the text is rotated.
+    #[serde(default)]
+    pub polygon: Vec<Point>,
+}
+
+/// The state of a face or text detection job.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct DetectionJob {
+    pub status: Option<String>,
+    pub status_message: Option<String>,
+}
+
+/// The state of a transcription job as reported by Amazon Transcribe.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct TranscriptionState {
+    pub status: Option<String>,
+    pub failure_reason: Option<String>,
+    pub language_code: Option<String>,
+    pub media_format: Option<String>,
+    pub media_uri: Option<String>,
+    /// Where the transcript is written once the job is completed.
+    pub transcript_uri: Option<String>,
+    pub creation_time: Option<String>,
+    pub completion_time: Option<String>,
+}
+
+/// The state of a batch translation job as reported by Amazon Translate.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct TranslationJob {
+    pub job_id: Option<String>,
+    pub job_name: Option<String>,
+    pub status: Option<String>,
+    pub message: Option<String>,
+    pub target_language_codes: Vec<String>,
+    pub output_s3_uri: Option<String>,
+}
+
+/// A text translated by Amazon Translate.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Translation {
+    pub source_language_code: String,
+    pub target_language_code: String,
+    pub translated_text: String,
+}
+
+/// A language supported by Amazon Translate.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Language {
+    pub name: String,
+    pub code: String,
+}
+
+#[async_trait(?Send)]
+pub trait S3Service {
+    async fn get_buckets(&self) -> Result<Vec<String>>;
+
+    async fn retrieve_keys_in_a_bucket(&self, bucket_name: &str) -> Result<Vec<String>>;
+
+    async fn list_objects_given_prefix(
+        &self,
+        bucket_name: &str,
+        prefix: &str,
+    ) -> Result<Vec<String>>;
+
+    async fn upload_content_to_a_bucket(
+        &self,
+        bucket_name: &str,
+        local_path: &str,
+        key: &str,
+    ) -> Result<()>;
+
+    /// Saves the object under `local_prefix` using the file name of the key.
+    async fn download_content_from_bucket(
+        &self,
+        bucket_name: &str,
+        key: &str,
+        local_prefix: &str,
+    ) -> Result<()>;
+
+    /// Saves the transcription outputs of the bucket into the 'TranscribeOutputs/'
+    /// directory within `local_dir`.
+    async fn download_transcription_results(
+        &self,
+        bucket_name: &str,
+        local_dir: &Path,
+    ) -> Result<()>;
+}
+
+#[async_trait(?Send)]
+pub trait PollyService {
+    /// Returns the ID of the started task. Without a language code, the voice
+    /// speaks its own language.
+    ///
+    /// The speech marks of `speech_mark_types` are written instead of the
+    /// audio when the output format is 'json'.
+    #[allow(clippy::too_many_arguments)]
+    async fn start_speech_synthesis_task(
+        &self,
+        engine_name: &str,
+        voice_id: &str,
+        language_code: Option<&str>,
+        text_type: &str,
+        text: &str,
+        output_format: &str,
+        bucket_name: &str,
+        lexicon_names: &[String],
+        speech_mark_types: &[String],
+    ) -> Result<String>;
+
+    /// Writes the text of `text_type` spoken by every voice of the language
+    /// as '<voice ID>.mp3' files within `path_prefix`.
+    async fn generate_all_available_voices_in_mp3(
+        &self,
+        text_type: &str,
+        text: &str,
+        language_code: &str,
+        engine_name: &str,
+        path_prefix: &str,
+        lexicon_names: &[String],
+    ) -> Result<()>;
+
+    /// Synthesizes the text right away and returns the audio, which only
+    /// works for the texts within the character limit of a single request.
+    ///
+    /// With the 'json' output format, the speech marks of `speech_mark_types`
+    /// are returned as JSON Lines instead.
+    #[allow(clippy::too_many_arguments)]
+    async fn synthesize_speech(
+        &self,
+        engine_name: &str,
+        voice_id: &str,
+        language_code: Option<&str>,
+        text_type: &str,
+        text: &str,
+        output_format: &str,
+        lexicon_names: &[String],
+        speech_mark_types: &[String],
+    ) -> Result<Vec<u8>>;
+
+    /// Stores the PLS document under the name, replacing the lexicon that
+    /// already has it.
+    async fn put_lexicon(&self, name: &str, content: &str) -> Result<()>;
+
+    async fn list_lexicons(&self) -> Result<Vec<Lexicon>>;
+
+    /// Returns the lexicon along with its PLS document.
+    async fn get_lexicon(&self, name: &str) -> Result<(Lexicon, String)>;
+
+    async fn delete_lexicon(&self, name: &str) -> Result<()>;
+
+    async fn get_speech_synthesis_result(&self, task_id: &str) -> Result<Option<SynthesisTask>>;
+
+    async fn list_speech_synthesis_tasks(&self) -> Result<Vec<SynthesisTask>>;
+
+    async fn describe_voices(&self) -> Result<Vec<Voice>>;
+}
+
+#[async_trait(?Send)]
+pub trait RekognitionService {
+    /// Detects the faces in a local image, sent as bytes, or in an S3 object.
+    async fn detect_faces(
+        &self,
+        local_path: Option<&str>,
+        bucket_name: Option<&str>,
+        key: Option<&str>,
+    ) -> Result<Vec<FaceSummary>>;
+
+    /// Detects the lines and words in a local image, sent as bytes, or in an S3 object.
+    async fn detect_texts(
+        &self,
+        local_path: Option<&str>,
+        bucket_name: Option<&str>,
+        key: Option<&str>,
+    ) -> Result<Vec<TextSummary>>;
+
+    /// Recognizes the celebrities in a local image or in an S3 object, along with
+    /// the faces that are not recognized.
+    async fn recognize_celebrities(
+        &self,
+        local_path: Option<&str>,
+        bucket_name: Option<&str>,
+        key: Option<&str>,
+    ) -> Result<Vec<CelebrityFace>>;
+
+    /// Returns the ID of the started job.
+    async fn start_face_detection_task(&self, bucket_name: &str, key: &str) -> Result<String>;
+
+    /// Writes the results as text and PDF into `local_dir` once the job has succeeded.
+    async fn get_face_detection_results(
+        &self,
+        job_id: &str,
+        local_dir: &Path,
+    ) -> Result<DetectionJob>;
+
+    /// Returns the ID of the started job.
+    async fn start_text_detection_task(&self, bucket_name: &str, key: &str) -> Result<String>;
+
+    /// Writes the results as text and PDF into `local_dir` once the job has succeeded.
+    async fn get_text_detection_results(
+        &self,
+        job_id: &str,
+        local_dir: &Path,
+    ) -> Result<DetectionJob>;
+}
+
+#[async_trait(?Send)]
+pub trait TranscribeService {
+    async fn start_transcribe_task(
+        &self,
+        bucket_name: &str,
+        media_uri: &str,
+        media_format: &str,
+        job_name: &str,
+    ) -> Result<()>;
+
+    async fn get_transcribe_results(&self, job_name: &str) -> Result<Option<TranscriptionState>>;
+}
+
+#[async_trait(?Send)]
+pub trait TranslateService {
+    /// Prints the languages as well when `print` is set.
+    async fn list_languages(&self, print: bool) -> Result<Vec<Language>>;
+
+    /// Translates the text, detecting its language.
+    async fn translate_text(&self, text: &str, target_lang_code: &str) -> Result<Translation>;
+
+    /// Returns the translated document, which has the type of the original.
+    async fn translate_document(
+        &self,
+        document_type: &str,
+        document_path: &str,
+        target_lang_code: &str,
+    ) -> Result<Vec<u8>>;
+
+    /// Returns the ID of the started job.
+    async fn start_text_translation_job(
+        &self,
+        job_name: &str,
+        target_lang_codes: Vec<String>,
+        input_s3_uri: &str,
+        document_type: &str,
+        output_s3_uri: &str,
+        role_arn: &str,
+    ) -> Result<String>;
+
+    async fn describe_text_translation_job(&self, job_id: &str) -> Result<TranslationJob>;
+
+    async fn list_translation_jobs(&self) -> Result<Vec<TranslationJob>>;
+}
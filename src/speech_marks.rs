@@ -0,0 +1,497 @@
+//! Reading the speech marks Polly returns next to the audio, and turning
+//! them into subtitles.
+//!
+//! Speech marks come as JSON Lines, one mark per line, giving the time in
+//! milliseconds at which a sentence, word, viseme or SSML `<mark>` starts
+//! within the audio synthesized for the same text.
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+
+/// The speech mark types Polly writes.
+pub const SPEECH_MARK_TYPES: [&str; 4] = ["sentence", "word", "viseme", "ssml"];
+
+/// The most characters of a subtitle cue, which is two lines of the usual length.
+const MAX_CUE_CHARACTERS: usize = 84;
+
+/// How long the last mark is taken to last when the length of the audio
+/// can't be read from it.
+const TAIL_MILLISECONDS: u64 = 1000;
+
+/// The sample rate Polly writes PCM audio at when none is asked for.
+const PCM_SAMPLE_RATE: u64 = 16_000;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SpeechMark {
+    /// Milliseconds from the start of the audio.
+    pub time: u64,
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// The byte offsets of the marked text within the text sent, which
+    /// visemes don't have.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<usize>,
+    pub value: String,
+}
+
+/// Checks the speech mark types, such as the ones of a comma separated list.
+pub fn check_types(types: &[String]) -> Result<()> {
+    match types
+        .iter()
+        .find(|kind| !SPEECH_MARK_TYPES.contains(&kind.as_str()))
+    {
+        Some(kind) => Err(Error::UnknownSpeechMarkType(kind.clone())),
+        None => Ok(()),
+    }
+}
+
+/// Parses a comma separated list of speech mark types, ignoring the empty ones.
+pub fn parse_types(types: &str) -> Result<Vec<String>> {
+    let types: Vec<String> = types
+        .split(',')
+        .map(|kind| kind.trim().to_lowercase())
+        .filter(|kind| !kind.is_empty())
+        .collect();
+    check_types(&types)?;
+    Ok(types)
+}
+
+/// Parses the JSON Lines of the speech marks.
+pub fn parse(json_lines: &str) -> Result<Vec<SpeechMark>> {
+    json_lines
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).map_err(|error| Error::SpeechMarks {
+                line: index + 1,
+                message: error.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Writes the speech marks back as JSON Lines.
+pub fn to_json_lines(marks: &[SpeechMark]) -> Result<String> {
+    let mut json_lines = String::new();
+    for mark in marks {
+        let line = serde_json::to_string(mark).map_err(|error| Error::Output(error.to_string()))?;
+        json_lines.push_str(&line);
+        json_lines.push('\n');
+    }
+    Ok(json_lines)
+}
+
+/// The length of MP3 audio in milliseconds, adding up its frames.
+fn mp3_milliseconds(audio: &[u8]) -> Option<u64> {
+    const MPEG1_BITRATES: [u64; 15] = [
+        0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320,
+    ];
+    const MPEG2_BITRATES: [u64; 15] =
+        [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160];
+    let mut offset = 0;
+    // Skips an ID3v2 tag, whose size is stored in 7 bits a byte.
+    if audio.starts_with(b"ID3") && audio.len() >= 10 {
+        let size = audio[6..10]
+            .iter()
+            .fold(0usize, |size, byte| (size << 7) | usize::from(byte & 0x7f));
+        offset = 10 + size;
+    }
+    let mut milliseconds = 0.0;
+    let mut frames = 0;
+    while let Some(header) = audio.get(offset..offset + 4) {
+        if header[0] != 0xff || header[1] & 0xe0 != 0xe0 {
+            break;
+        }
+        let version = (header[1] >> 3) & 0b11;
+        let layer = (header[1] >> 1) & 0b11;
+        let bitrate_index = usize::from(header[2] >> 4);
+        let sample_rate_index = usize::from((header[2] >> 2) & 0b11);
+        let padding = u64::from((header[2] >> 1) & 1);
+        // Polly only writes MPEG Layer III.
+        if version == 0b01 || layer != 0b01 || bitrate_index == 0 || bitrate_index == 15 {
+            break;
+        }
+        let (bitrates, sample_rates, samples) = match version {
+            0b11 => (MPEG1_BITRATES, [44_100, 48_000, 32_000], 1152),
+            0b10 => (MPEG2_BITRATES, [22_050, 24_000, 16_000], 576),
+            _ => (MPEG2_BITRATES, [11_025, 12_000, 8_000], 576),
+        };
+        let sample_rate: u64 = *sample_rates.get(sample_rate_index)?;
+        let bitrate = bitrates[bitrate_index] * 1000;
+        let length = samples / 8 * bitrate / sample_rate + padding;
+        milliseconds += samples as f64 * 1000.0 / sample_rate as f64;
+        frames += 1;
+        offset += length as usize;
+    }
+    (frames > 0).then_some(milliseconds.round() as u64)
+}
+
+/// The length of Ogg Vorbis audio in milliseconds, from the sample rate of
+/// its first page and the position of its last one.
+fn ogg_milliseconds(audio: &[u8]) -> Option<u64> {
+    let mut offset = 0;
+    let mut sample_rate = None;
+    let mut samples = None;
+    while audio.get(offset..offset + 4) == Some(b"OggS") {
+        let header = audio.get(offset..offset + 27)?;
+        let granule = u64::from_le_bytes(header[6..14].try_into().ok()?);
+        let segments = usize::from(header[26]);
+        let table = audio.get(offset + 27..offset + 27 + segments)?;
+        let payload = offset + 27 + segments;
+        if sample_rate.is_none() {
+            // The identification header: '\x01vorbis', the version, the channels and the rate.
+            let identification = audio.get(payload..payload + 16)?;
+            if &identification[..7] != b"\x01vorbis" {
+                return None;
+            }
+            sample_rate = Some(u32::from_le_bytes(identification[12..16].try_into().ok()?));
+        }
+        // A page that no packet ends on has no position.
+        if granule != u64::MAX {
+            samples = Some(granule);
+        }
+        offset = payload + table.iter().map(|&size| usize::from(size)).sum::<usize>();
+    }
+    let sample_rate = u64::from(sample_rate.filter(|&rate| rate > 0)?);
+    Some(samples? * 1000 / sample_rate)
+}
+
+/// The length of the audio in milliseconds, or `None` when it can't be read
+/// from the audio of that format.
+pub fn audio_milliseconds(format: &str, audio: &[u8]) -> Option<u64> {
+    match format {
+        "mp3" => mp3_milliseconds(audio),
+        "ogg_vorbis" => ogg_milliseconds(audio),
+        // 16-bit mono samples.
+        "pcm" => Some(audio.len() as u64 * 1000 / (PCM_SAMPLE_RATE * 2)),
+        _ => None,
+    }
+}
+
+/// The length of the audio the marks were synthesized with, as if the last
+/// mark lasted a second.
+pub fn estimated_length(marks: &[SpeechMark]) -> u64 {
+    marks
+        .iter()
+        .map(|mark| mark.time + TAIL_MILLISECONDS)
+        .max()
+        .unwrap_or_default()
+}
+
+/// The length of the audio the marks were synthesized with, estimated from
+/// the last mark when it can't be read from the audio.
+pub fn length_of(format: &str, audio: &[u8], marks: &[SpeechMark]) -> u64 {
+    audio_milliseconds(format, audio).unwrap_or_else(|| estimated_length(marks))
+}
+
+/// The text of a mark as it's spoken, without the SSML tags it may have.
+fn spoken(value: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in value.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            }
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    let text = text
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&");
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// A subtitle shown between two times of the audio, in milliseconds.
+pub struct Cue {
+    pub start: u64,
+    pub end: u64,
+    pub text: String,
+}
+
+/// The cues of the sentences, splitting the long ones at their words when
+/// there are word marks, and ending the last one at `length`.
+///
+/// Without sentence marks, the words are grouped into cues instead.
+pub fn cues(marks: &[SpeechMark], length: u64) -> Vec<Cue> {
+    let of_kind = |kind: &str| -> Vec<&SpeechMark> {
+        marks.iter().filter(|mark| mark.kind == kind).collect()
+    };
+    let sentences = of_kind("sentence");
+    let words = of_kind("word");
+    let mut cues = Vec::new();
+    if sentences.is_empty() {
+        group_words(&words, length, &mut cues);
+        return cues;
+    }
+    for (index, sentence) in sentences.iter().enumerate() {
+        let end = sentences
+            .get(index + 1)
+            .map_or(length, |next| next.time)
+            .max(sentence.time);
+        let text = spoken(&sentence.value);
+        let sentence_words: Vec<&SpeechMark> = words
+            .iter()
+            .filter(|word| word.time >= sentence.time && word.time < end)
+            .copied()
+            .collect();
+        if text.chars().count() > MAX_CUE_CHARACTERS && !sentence_words.is_empty() {
+            let first = cues.len();
+            group_words(&sentence_words, end, &mut cues);
+            // The sentence starts a little before its first word.
+            if let Some(cue) = cues.get_mut(first) {
+                cue.start = sentence.time;
+            }
+        } else if !text.is_empty() {
+            cues.push(Cue {
+                start: sentence.time,
+                end,
+                text,
+            });
+        }
+    }
+    cues
+}
+
+/// Groups the words into cues of at most [`MAX_CUE_CHARACTERS`], the last
+/// one ending at `end`.
+fn group_words(words: &[&SpeechMark], end: u64, cues: &mut Vec<Cue>) {
+    let mut groups: Vec<(u64, String)> = Vec::new();
+    for word in words {
+        let text = spoken(&word.value);
+        if text.is_empty() {
+            continue;
+        }
+        match groups.last_mut() {
+            Some((_, group))
+                if group.chars().count() + 1 + text.chars().count() <= MAX_CUE_CHARACTERS =>
+            {
+                group.push(' ');
+                group.push_str(&text);
+            }
+            _ => groups.push((word.time, text)),
+        }
+    }
+    for (index, (start, text)) in groups.iter().enumerate() {
+        let cue_end = groups.get(index + 1).map_or(end, |(next, _)| *next);
+        cues.push(Cue {
+            start: *start,
+            end: cue_end.max(*start),
+            text: text.clone(),
+        });
+    }
+}
+
+/// A time such as '00:01:02.345', with ',' before the milliseconds for SRT.
+fn timestamp(milliseconds: u64, separator: char) -> String {
+    let hours = milliseconds / 3_600_000;
+    let minutes = milliseconds / 60_000 % 60;
+    let seconds = milliseconds / 1000 % 60;
+    let millis = milliseconds % 1000;
+    format!("{hours:02}:{minutes:02}:{seconds:02}{separator}{millis:03}")
+}
+
+/// The text of a cue without the '-->' that would be read as the times of
+/// another cue.
+fn cue_text(text: &str) -> String {
+    let mut text = text.to_string();
+    while text.contains("-->") {
+        text = text.replace("-->", "->");
+    }
+    text
+}
+
+pub fn to_webvtt(cues: &[Cue]) -> String {
+    let mut webvtt = String::from("WEBVTT\n\n");
+    for cue in cues {
+        // WebVTT reads '&' and '<' as the start of an entity or a tag.
+        let text = cue_text(&cue.text)
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;");
+        webvtt.push_str(&format!(
+            "{} --> {}\n{text}\n\n",
+            timestamp(cue.start, '.'),
+            timestamp(cue.end, '.'),
+        ));
+    }
+    webvtt
+}
+
+pub fn to_srt(cues: &[Cue]) -> String {
+    let mut srt = String::new();
+    for (index, cue) in cues.iter().enumerate() {
+        srt.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            index + 1,
+            timestamp(cue.start, ','),
+            timestamp(cue.end, ','),
+            cue_text(&cue.text)
+        ));
+    }
+    srt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mark(time: u64, kind: &str, value: &str) -> SpeechMark {
+        SpeechMark {
+            time,
+            kind: kind.to_string(),
+            start: None,
+            end: None,
+            value: value.to_string(),
+        }
+    }
+
+    fn cue(start: u64, end: u64, text: &str) -> Cue {
+        Cue {
+            start,
+            end,
+            text: text.to_string(),
+        }
+    }
+
+    /// An MPEG 1 Layer III frame of 128 kbit/s at 44.1 kHz, which lasts
+    /// 1152 samples.
+    fn mp3_frame() -> Vec<u8> {
+        let mut frame = vec![0xff, 0xfb, 0x90, 0x00];
+        frame.resize(417, 0);
+        frame
+    }
+
+    fn ogg_page(granule: u64, payload: &[u8]) -> Vec<u8> {
+        let mut page = b"OggS\0\0".to_vec();
+        page.extend(granule.to_le_bytes());
+        page.extend([0; 12]);
+        page.push(1);
+        page.push(payload.len() as u8);
+        page.extend(payload);
+        page
+    }
+
+    #[test]
+    fn the_sentences_are_cues_until_the_next_one() {
+        let marks = [
+            mark(0, "sentence", "<s>Hello &amp; welcome.</s>"),
+            mark(0, "word", "Hello"),
+            mark(1200, "sentence", "Goodbye."),
+        ];
+
+        let cues = cues(&marks, 2500);
+
+        assert_eq!(cues.len(), 2);
+        assert_eq!((cues[0].start, cues[0].end), (0, 1200));
+        assert_eq!(cues[0].text, "Hello & welcome.");
+        assert_eq!((cues[1].start, cues[1].end), (1200, 2500));
+    }
+
+    #[test]
+    fn a_long_sentence_is_split_at_its_words() {
+        let words: Vec<String> = (0..30).map(|index| format!("word{index}")).collect();
+        let mut marks = vec![mark(100, "sentence", &words.join(" "))];
+        for (index, word) in words.iter().enumerate() {
+            marks.push(mark(200 + index as u64 * 100, "word", word));
+        }
+
+        let cues = cues(&marks, 4000);
+
+        assert!(cues.len() > 1);
+        assert_eq!(cues[0].start, 100);
+        assert_eq!(cues.last().unwrap().end, 4000);
+        for pair in cues.windows(2) {
+            assert_eq!(pair[0].end, pair[1].start);
+        }
+        assert!(cues
+            .iter()
+            .all(|cue| cue.text.chars().count() <= MAX_CUE_CHARACTERS));
+    }
+
+    #[test]
+    fn the_words_are_grouped_without_sentences() {
+        let marks = [
+            mark(0, "word", "Hello"),
+            mark(0, "viseme", "p"),
+            mark(400, "word", "there"),
+        ];
+
+        let cues = cues(&marks, 900);
+
+        assert_eq!(cues.len(), 1);
+        assert_eq!(cues[0].text, "Hello there");
+        assert_eq!((cues[0].start, cues[0].end), (0, 900));
+    }
+
+    #[test]
+    fn the_timestamps_have_hours_and_milliseconds() {
+        assert_eq!(timestamp(0, '.'), "00:00:00.000");
+        assert_eq!(timestamp(3_723_004, ','), "01:02:03,004");
+    }
+
+    #[test]
+    fn the_webvtt_cues_are_escaped() {
+        let webvtt = to_webvtt(&[cue(0, 1500, "a < b & c --> d")]);
+
+        assert_eq!(
+            webvtt,
+            "WEBVTT\n\n00:00:00.000 --> 00:00:01.500\na &lt; b &amp; c -&gt; d\n\n"
+        );
+    }
+
+    #[test]
+    fn the_srt_cues_have_no_arrow_of_their_own() {
+        let srt = to_srt(&[cue(0, 1500, "a ---> b"), cue(1500, 2000, "c")]);
+
+        assert_eq!(
+            srt,
+            "1\n00:00:00,000 --> 00:00:01,500\na -> b\n\n2\n00:00:01,500 --> 00:00:02,000\nc\n\n"
+        );
+    }
+
+    #[test]
+    fn the_mp3_frames_are_added_up() {
+        let audio = mp3_frame().repeat(10);
+        assert_eq!(mp3_milliseconds(&audio), Some(261));
+
+        // An ID3v2 tag of 20 bytes comes first.
+        let mut tagged = b"ID3\x04\0\0\0\0\0\x14".to_vec();
+        tagged.extend([0; 20]);
+        tagged.extend(&audio);
+        assert_eq!(mp3_milliseconds(&tagged), Some(261));
+
+        assert_eq!(mp3_milliseconds(b"not audio"), None);
+    }
+
+    #[test]
+    fn the_ogg_length_is_the_last_position_over_the_rate() {
+        let mut identification = b"\x01vorbis\0\0\0\0\x01".to_vec();
+        identification.extend(48_000u32.to_le_bytes());
+        identification.resize(30, 0);
+        let mut audio = ogg_page(0, &identification);
+        audio.extend(ogg_page(48_000, &[0; 10]));
+        audio.extend(ogg_page(96_000, &[0; 10]));
+        audio.extend(ogg_page(u64::MAX, &[0; 10]));
+
+        assert_eq!(ogg_milliseconds(&audio), Some(2000));
+        assert_eq!(ogg_milliseconds(&audio[..20]), None);
+    }
+
+    #[test]
+    fn the_length_falls_back_to_the_last_mark() {
+        let marks = [mark(0, "word", "Hi"), mark(700, "word", "there")];
+
+        assert_eq!(length_of("mp3", b"", &marks), 1700);
+        assert_eq!(length_of("pcm", &[0; 32_000], &marks), 1000);
+    }
+}
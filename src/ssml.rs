@@ -0,0 +1,952 @@
+//! Checking the SSML sent to Polly before the request is made.
+//!
+//! Polly only rejects a malformed document once it has been sent, so the
+//! documents are parsed here first, and their tags and attributes checked
+//! against the ones the chosen engine reads, reporting the line and column of
+//! the first problem.
+use std::fmt;
+
+use crate::error::{Error, Result};
+use crate::xml::{Event, Reader, StartTag};
+
+/// The engines whose SSML support differs.
+#[derive(Clone, Copy, PartialEq)]
+pub enum Engine {
+    Standard,
+    Neural,
+}
+
+impl Engine {
+    /// The engine of a name such as 'neural', or `None` for the engines whose
+    /// tags aren't checked.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "standard" => Some(Self::Standard),
+            "neural" => Some(Self::Neural),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Engine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Standard => write!(f, "standard"),
+            Self::Neural => write!(f, "neural"),
+        }
+    }
+}
+
+const BOTH: &[Engine] = &[Engine::Standard, Engine::Neural];
+const STANDARD: &[Engine] = &[Engine::Standard];
+const NEURAL: &[Engine] = &[Engine::Neural];
+
+struct Attribute {
+    name: &'static str,
+    required: bool,
+    engines: &'static [Engine],
+    valid: fn(&str) -> bool,
+    /// What a valid value looks like, for the error.
+    expected: &'static str,
+}
+
+struct Tag {
+    name: &'static str,
+    engines: &'static [Engine],
+    attributes: &'static [Attribute],
+    /// Whether the tag does nothing without one of its attributes.
+    needs_attribute: bool,
+}
+
+/// The milliseconds of a time such as '500ms' or '1.5s'.
+fn milliseconds(value: &str) -> Option<f64> {
+    let (number, scale) = match value.strip_suffix("ms") {
+        Some(number) => (number, 1.0),
+        None => (value.strip_suffix('s')?, 1000.0),
+    };
+    let number: f64 = number.parse().ok()?;
+    (number.is_finite() && number >= 0.0).then_some(number * scale)
+}
+
+/// A relative change such as '+6dB' or '-10%'.
+fn is_signed(value: &str, unit: &str) -> bool {
+    value
+        .strip_suffix(unit)
+        .filter(|number| number.starts_with(['+', '-']))
+        .and_then(|number| number.parse::<f64>().ok())
+        .is_some_and(f64::is_finite)
+}
+
+/// A language code such as 'fr-FR'.
+fn is_language(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .split('-')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+const LANGUAGE: Attribute = Attribute {
+    name: "xml:lang",
+    required: false,
+    engines: BOTH,
+    valid: is_language,
+    expected: "a language code such as 'fr-FR'",
+};
+
+fn is_breath_volume(value: &str) -> bool {
+    matches!(
+        value,
+        "default" | "x-soft" | "soft" | "medium" | "loud" | "x-loud"
+    )
+}
+
+fn is_breath_duration(value: &str) -> bool {
+    matches!(
+        value,
+        "default" | "x-short" | "short" | "medium" | "long" | "x-long"
+    )
+}
+
+const BREATH_VOLUME: Attribute = Attribute {
+    name: "volume",
+    required: false,
+    engines: STANDARD,
+    valid: is_breath_volume,
+    expected: "'default', 'x-soft', 'soft', 'medium', 'loud' or 'x-loud'",
+};
+
+const BREATH_DURATION: Attribute = Attribute {
+    name: "duration",
+    required: false,
+    engines: STANDARD,
+    valid: is_breath_duration,
+    expected: "'default', 'x-short', 'short', 'medium', 'long' or 'x-long'",
+};
+
+/// The tags Polly reads, with their attributes and the engines that read them.
+const TAGS: &[Tag] = &[
+    Tag {
+        name: "speak",
+        engines: BOTH,
+        attributes: &[
+            LANGUAGE,
+            Attribute {
+                name: "version",
+                required: false,
+                engines: BOTH,
+                valid: |value| value == "1.0" || value == "1.1",
+                expected: "'1.0' or '1.1'",
+            },
+            Attribute {
+                name: "xml:base",
+                required: false,
+                engines: BOTH,
+                valid: |value| !value.is_empty(),
+                expected: "a URI",
+            },
+        ],
+        needs_attribute: false,
+    },
+    Tag {
+        name: "break",
+        engines: BOTH,
+        attributes: &[
+            Attribute {
+                name: "strength",
+                required: false,
+                engines: BOTH,
+                valid: |value| {
+                    matches!(
+                        value,
+                        "none" | "x-weak" | "weak" | "medium" | "strong" | "x-strong"
+                    )
+                },
+                expected: "'none', 'x-weak', 'weak', 'medium', 'strong' or 'x-strong'",
+            },
+            Attribute {
+                name: "time",
+                required: false,
+                engines: BOTH,
+                valid: |value| milliseconds(value).is_some_and(|time| time <= 10_000.0),
+                expected: "a time of at most 10 seconds such as '500ms' or '2s'",
+            },
+        ],
+        needs_attribute: false,
+    },
+    Tag {
+        name: "prosody",
+        engines: BOTH,
+        attributes: &[
+            Attribute {
+                name: "volume",
+                required: false,
+                engines: BOTH,
+                valid: |value| {
+                    matches!(
+                        value,
+                        "default" | "silent" | "x-soft" | "soft" | "medium" | "loud" | "x-loud"
+                    ) || is_signed(value, "dB")
+                },
+                expected: "'silent', 'x-soft', 'soft', 'medium', 'loud', 'x-loud' or a change such as '+6dB'",
+            },
+            Attribute {
+                name: "rate",
+                required: false,
+                engines: BOTH,
+                valid: |value| {
+                    matches!(value, "x-slow" | "slow" | "medium" | "fast" | "x-fast")
+                        || value
+                            .strip_suffix('%')
+                            .and_then(|percent| percent.parse::<u32>().ok())
+                            .is_some_and(|percent| percent >= 20)
+                },
+                expected: "'x-slow', 'slow', 'medium', 'fast', 'x-fast' or a percentage of at least '20%'",
+            },
+            Attribute {
+                name: "pitch",
+                required: false,
+                engines: STANDARD,
+                valid: |value| {
+                    matches!(
+                        value,
+                        "default" | "x-low" | "low" | "medium" | "high" | "x-high"
+                    ) || is_signed(value, "%")
+                },
+                expected: "'x-low', 'low', 'medium', 'high', 'x-high' or a change such as '+5%'",
+            },
+            Attribute {
+                name: "amazon:max-duration",
+                required: false,
+                engines: STANDARD,
+                valid: |value| milliseconds(value).is_some(),
+                expected: "a time such as '500ms' or '2s'",
+            },
+        ],
+        needs_attribute: true,
+    },
+    Tag {
+        name: "emphasis",
+        engines: STANDARD,
+        attributes: &[Attribute {
+            name: "level",
+            required: false,
+            engines: STANDARD,
+            valid: |value| matches!(value, "strong" | "moderate" | "reduced"),
+            expected: "'strong', 'moderate' or 'reduced'",
+        }],
+        needs_attribute: false,
+    },
+    Tag {
+        name: "say-as",
+        engines: BOTH,
+        attributes: &[
+            Attribute {
+                name: "interpret-as",
+                required: true,
+                engines: BOTH,
+                valid: |value| {
+                    matches!(
+                        value,
+                        "characters"
+                            | "spell-out"
+                            | "cardinal"
+                            | "number"
+                            | "ordinal"
+                            | "digits"
+                            | "fraction"
+                            | "unit"
+                            | "date"
+                            | "time"
+                            | "address"
+                            | "expletive"
+                            | "telephone"
+                            | "interjection"
+                    )
+                },
+                expected: "'characters', 'spell-out', 'cardinal', 'number', 'ordinal', 'digits', 'fraction', 'unit', 'date', 'time', 'address', 'expletive', 'telephone' or 'interjection'",
+            },
+            Attribute {
+                name: "format",
+                required: false,
+                engines: BOTH,
+                valid: |value| {
+                    matches!(
+                        value,
+                        "mdy" | "dmy" | "ymd" | "md" | "dm" | "ym" | "my" | "d" | "m" | "y" | "yyyymmdd"
+                    )
+                },
+                expected: "a date format such as 'mdy', 'dmy', 'ymd' or 'yyyymmdd'",
+            },
+        ],
+        needs_attribute: false,
+    },
+    Tag {
+        name: "phoneme",
+        engines: BOTH,
+        attributes: &[
+            Attribute {
+                name: "alphabet",
+                required: true,
+                engines: BOTH,
+                valid: |value| matches!(value, "ipa" | "x-sampa"),
+                expected: "'ipa' or 'x-sampa'",
+            },
+            Attribute {
+                name: "ph",
+                required: true,
+                engines: BOTH,
+                valid: |value| !value.trim().is_empty(),
+                expected: "the pronunciation",
+            },
+        ],
+        needs_attribute: false,
+    },
+    Tag {
+        name: "lang",
+        engines: BOTH,
+        attributes: &[
+            Attribute {
+                required: true,
+                ..LANGUAGE
+            },
+            Attribute {
+                name: "onlangfailure",
+                required: false,
+                engines: BOTH,
+                valid: |value| {
+                    matches!(
+                        value,
+                        "setsymbols" | "changevoice" | "ignoretext" | "ignorelang" | "processorchoice"
+                    )
+                },
+                expected: "'setsymbols', 'changevoice', 'ignoretext', 'ignorelang' or 'processorchoice'",
+            },
+        ],
+        needs_attribute: false,
+    },
+    Tag {
+        name: "mark",
+        engines: BOTH,
+        attributes: &[Attribute {
+            name: "name",
+            required: true,
+            engines: BOTH,
+            valid: |value| !value.is_empty(),
+            expected: "the name of the mark",
+        }],
+        needs_attribute: false,
+    },
+    Tag {
+        name: "p",
+        engines: BOTH,
+        attributes: &[],
+        needs_attribute: false,
+    },
+    Tag {
+        name: "s",
+        engines: BOTH,
+        attributes: &[],
+        needs_attribute: false,
+    },
+    Tag {
+        name: "sub",
+        engines: BOTH,
+        attributes: &[Attribute {
+            name: "alias",
+            required: true,
+            engines: BOTH,
+            valid: |value| !value.trim().is_empty(),
+            expected: "the text that is spoken instead",
+        }],
+        needs_attribute: false,
+    },
+    Tag {
+        name: "w",
+        engines: BOTH,
+        attributes: &[Attribute {
+            name: "role",
+            required: true,
+            engines: BOTH,
+            valid: |value| {
+                matches!(
+                    value,
+                    "amazon:VB"
+                        | "amazon:VBD"
+                        | "amazon:DT"
+                        | "amazon:IN"
+                        | "amazon:JJ"
+                        | "amazon:NN"
+                        | "amazon:SENSE_1"
+                )
+            },
+            expected: "'amazon:VB', 'amazon:VBD', 'amazon:DT', 'amazon:IN', 'amazon:JJ', 'amazon:NN' or 'amazon:SENSE_1'",
+        }],
+        needs_attribute: false,
+    },
+    Tag {
+        name: "amazon:domain",
+        engines: NEURAL,
+        attributes: &[Attribute {
+            name: "name",
+            required: true,
+            engines: NEURAL,
+            valid: |value| matches!(value, "news" | "conversational"),
+            expected: "'news' or 'conversational'",
+        }],
+        needs_attribute: false,
+    },
+    Tag {
+        name: "amazon:effect",
+        engines: BOTH,
+        attributes: &[
+            Attribute {
+                name: "name",
+                required: false,
+                engines: BOTH,
+                valid: |value| matches!(value, "whispered" | "drc"),
+                expected: "'whispered' or 'drc'",
+            },
+            Attribute {
+                name: "phonation",
+                required: false,
+                engines: STANDARD,
+                valid: |value| value == "soft",
+                expected: "'soft'",
+            },
+            Attribute {
+                name: "vocal-tract-length",
+                required: false,
+                engines: STANDARD,
+                valid: |value| is_signed(value, "%"),
+                expected: "a change such as '+15%'",
+            },
+        ],
+        needs_attribute: true,
+    },
+    Tag {
+        name: "amazon:auto-breaths",
+        engines: STANDARD,
+        attributes: &[
+            BREATH_VOLUME,
+            Attribute {
+                name: "frequency",
+                required: false,
+                engines: STANDARD,
+                valid: |value| {
+                    matches!(
+                        value,
+                        "default" | "x-low" | "low" | "medium" | "high" | "x-high"
+                    )
+                },
+                expected: "'default', 'x-low', 'low', 'medium', 'high' or 'x-high'",
+            },
+            BREATH_DURATION,
+        ],
+        needs_attribute: false,
+    },
+    Tag {
+        name: "amazon:breath",
+        engines: STANDARD,
+        attributes: &[BREATH_VOLUME, BREATH_DURATION],
+        needs_attribute: false,
+    },
+];
+
+/// The values of an attribute that only the standard engine reads, even
+/// though the neural one reads the attribute itself.
+const STANDARD_ONLY_VALUES: &[(&str, &str, &str)] = &[
+    ("say-as", "interpret-as", "interjection"),
+    ("amazon:effect", "name", "whispered"),
+];
+
+/// Escapes the characters that have a meaning in XML, so that a plain text
+/// reads the same within a document.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Turns a plain text into an SSML document that reads the same.
+pub fn wrap(text: &str) -> String {
+    format!("<speak>{}</speak>", escape(text.trim()))
+}
+
+/// Whether the text is a whole document rather than a fragment of one, which
+/// is when `<speak>` is the first tag after the XML declaration and the
+/// comments.
+fn is_document(text: &str) -> bool {
+    let mut rest = text.trim_start_matches('\u{feff}').trim_start();
+    loop {
+        let end = match rest {
+            _ if rest.starts_with("<?") => "?>",
+            _ if rest.starts_with("<!--") => "-->",
+            _ => break,
+        };
+        // The reader reports a declaration or a comment that isn't closed.
+        let Some(index) = rest.find(end) else {
+            return true;
+        };
+        rest = rest[index + end.len()..].trim_start();
+    }
+    rest.strip_prefix("<speak")
+        .is_some_and(|rest| rest.starts_with(|c: char| c.is_whitespace() || matches!(c, '>' | '/')))
+}
+
+/// Whether the text has something that looks like a tag, rather than only a
+/// '<' such as the one of 'a < b'.
+fn has_tags(text: &str) -> bool {
+    text.match_indices('<').any(|(index, _)| {
+        text[index + 1..].starts_with(|c: char| c.is_alphabetic() || matches!(c, '/' | '!'))
+    })
+}
+
+/// Checks that the document is well formed, has a single `<speak>` around
+/// everything, and only uses tags and attributes that `engine` reads, or that
+/// either engine reads when it's `None`.
+pub fn validate(document: &str, engine: Option<Engine>) -> Result<()> {
+    check(Reader::new(document, "SSML", "speak"), engine)
+}
+
+/// The text Polly is sent for the content of a file of `text_type`.
+///
+/// Plain text is sent as it is. SSML is checked for `engine_name`, and a
+/// fragment of a document without `<speak>` around it is wrapped first; when
+/// it has no tags at all, its XML characters are escaped as well.
+pub fn prepare(content: &str, text_type: &str, engine_name: &str) -> Result<String> {
+    match text_type {
+        "text" => Ok(content.to_string()),
+        "ssml" => {
+            let engine = Engine::from_name(engine_name);
+            if is_document(content) {
+                validate(content, engine)?;
+                Ok(content.to_string())
+            } else if has_tags(content) {
+                check(Reader::fragment(content, "SSML", "speak"), engine)?;
+                Ok(format!("<speak>{}</speak>", content.trim()))
+            } else {
+                Ok(wrap(content))
+            }
+        }
+        _ => Err(Error::UnknownTextType(text_type.to_string())),
+    }
+}
+
+/// The characters of the document that Polly bills, which are the ones of its
+/// text rather than of its tags.
+pub fn billed_characters(document: &str) -> Result<usize> {
+    let mut reader = Reader::new(document, "SSML", "speak");
+    let mut characters = 0;
+    while let Some(event) = reader.next()? {
+        if let Event::Text { text, .. } = event {
+            characters += text.chars().count();
+        }
+    }
+    Ok(characters)
+}
+
+/// A part of a document that is synthesized on its own.
+pub struct Part {
+    /// The part as a document of its own.
+    pub text: String,
+    /// How many bytes of `text` come before the content of the part, which
+    /// are the tags it's opened with.
+    pub opening: usize,
+    /// Where the content of the part starts within the whole document.
+    pub start: usize,
+}
+
+/// A place where a document can be split, with the start tags of the elements
+/// open there and the billed characters before it.
+struct Cut<'a> {
+    offset: usize,
+    open: Vec<(&'a str, &'a str)>,
+    billed: usize,
+}
+
+/// Splits a document so that each part has at most `billed_limit` characters
+/// of text, which are the ones Polly bills, and at most `total_limit`
+/// characters with its tags.
+///
+/// The document is split before and after its paragraphs and sentences, and
+/// after its breaks. Every part is a document of its own: it is wrapped in the
+/// `<speak>` of the document, and the elements the part starts or ends within
+/// are opened again at its start and closed at its end.
+pub fn split(document: &str, billed_limit: usize, total_limit: usize) -> Result<Vec<Part>> {
+    let mut reader = Reader::new(document, "SSML", "speak");
+    let mut speak = "<speak>";
+    let mut open: Vec<(&str, &str)> = Vec::new();
+    let mut cuts = Vec::new();
+    let mut billed = 0;
+    while let Some(event) = reader.next()? {
+        let end = reader.offset();
+        match event {
+            Event::Start(tag) => {
+                let start = document[..end].rfind('<').unwrap_or(end);
+                if tag.name == "speak" {
+                    speak = &document[start..end];
+                    cuts.push(Cut {
+                        offset: end,
+                        open: Vec::new(),
+                        billed,
+                    });
+                    continue;
+                }
+                if matches!(tag.name, "p" | "s") {
+                    cuts.push(Cut {
+                        offset: start,
+                        open: open.clone(),
+                        billed,
+                    });
+                }
+                if !tag.closed {
+                    open.push((tag.name, &document[start..end]));
+                } else if tag.name == "break" {
+                    cuts.push(Cut {
+                        offset: end,
+                        open: open.clone(),
+                        billed,
+                    });
+                }
+            }
+            Event::End { name } => {
+                if name == "speak" {
+                    let start = document[..end].rfind('<').unwrap_or(end);
+                    cuts.push(Cut {
+                        offset: start,
+                        open: Vec::new(),
+                        billed,
+                    });
+                    continue;
+                }
+                open.pop();
+                if matches!(name, "p" | "s" | "break") {
+                    cuts.push(Cut {
+                        offset: end,
+                        open: open.clone(),
+                        billed,
+                    });
+                }
+            }
+            Event::Text { text, .. } => billed += text.chars().count(),
+        }
+    }
+    if billed <= billed_limit && document.chars().count() <= total_limit {
+        return Ok(vec![Part {
+            text: document.to_string(),
+            opening: 0,
+            start: 0,
+        }]);
+    }
+    // A `<speak/>` without content has no cut at its end.
+    if cuts.len() < 2 {
+        return Ok(Vec::new());
+    }
+
+    let part = |from: &Cut, to: &Cut| {
+        let mut text = speak.to_string();
+        for (_, start_tag) in &from.open {
+            text.push_str(start_tag);
+        }
+        let opening = text.len();
+        text.push_str(&document[from.offset..to.offset]);
+        for (name, _) in to.open.iter().rev() {
+            text.push_str(&format!("</{name}>"));
+        }
+        text.push_str("</speak>");
+        Part {
+            text,
+            opening,
+            start: from.offset,
+        }
+    };
+    let mut parts = Vec::new();
+    let mut from = 0;
+    while from < cuts.len() - 1 {
+        // The part runs to the furthest cut that keeps it within the limits.
+        let mut to = from + 1;
+        let mut fitting = None;
+        while to < cuts.len() {
+            let candidate = part(&cuts[from], &cuts[to]);
+            let billed = cuts[to].billed - cuts[from].billed;
+            if billed > billed_limit || candidate.text.chars().count() > total_limit {
+                break;
+            }
+            fitting = Some((to, candidate));
+            to += 1;
+        }
+        let Some((to, fitting)) = fitting else {
+            let billed = cuts[from + 1].billed - cuts[from].billed;
+            return Err(match billed > billed_limit {
+                true => Error::TextTooLong {
+                    characters: billed,
+                    limit: billed_limit,
+                },
+                false => Error::TextTooLong {
+                    characters: part(&cuts[from], &cuts[from + 1]).text.chars().count(),
+                    limit: total_limit,
+                },
+            });
+        };
+        if !document[cuts[from].offset..cuts[to].offset]
+            .trim()
+            .is_empty()
+        {
+            parts.push(fitting);
+        }
+        from = to;
+    }
+    Ok(parts)
+}
+
+fn check(mut reader: Reader, engine: Option<Engine>) -> Result<()> {
+    while let Some(event) = reader.next()? {
+        if let Event::Start(tag) = event {
+            check_tag(&reader, &tag, engine)?;
+        }
+    }
+    Ok(())
+}
+
+/// Checks the tag and its attributes against the ones Polly reads.
+fn check_tag(reader: &Reader, tag: &StartTag, engine: Option<Engine>) -> Result<()> {
+    let name = tag.name;
+    let Some(rules) = TAGS.iter().find(|rules| rules.name == name) else {
+        return Err(reader.error_at(tag.position, format!("<{name}> isn't a tag Polly reads")));
+    };
+    if let Some(engine) = engine {
+        if !rules.engines.contains(&engine) {
+            return Err(reader.error_at(
+                tag.position,
+                format!("the {engine} engine doesn't read <{name}>"),
+            ));
+        }
+    }
+    for written in tag.attributes() {
+        let attribute = written.name;
+        let value = written.value.as_str();
+        let Some(rule) = rules.attributes.iter().find(|rule| rule.name == attribute) else {
+            return Err(reader.error_at(
+                written.position,
+                format!("<{name}> has no '{attribute}' attribute"),
+            ));
+        };
+        if !(rule.valid)(value) {
+            return Err(reader.error_at(
+                written.position,
+                format!(
+                    "'{value}' isn't a valid '{attribute}' of <{name}>, which is {}",
+                    rule.expected
+                ),
+            ));
+        }
+        if let Some(engine) = engine {
+            if !rule.engines.contains(&engine) {
+                return Err(reader.error_at(
+                    written.position,
+                    format!("the {engine} engine doesn't read the '{attribute}' of <{name}>"),
+                ));
+            }
+            let standard_only = STANDARD_ONLY_VALUES.contains(&(name, attribute, value));
+            if standard_only && engine != Engine::Standard {
+                return Err(reader.error_at(
+                    written.position,
+                    format!(
+                        "the {engine} engine doesn't read '{value}' as the '{attribute}' of <{name}>"
+                    ),
+                ));
+            }
+        }
+    }
+    if let Some(rule) = rules
+        .attributes
+        .iter()
+        .find(|rule| rule.required && tag.attribute(rule.name).is_none())
+    {
+        return Err(reader.error_at(
+            tag.position,
+            format!("<{name}> needs the '{}' attribute", rule.name),
+        ));
+    }
+    if rules.needs_attribute && tag.attributes().next().is_none() {
+        let names: Vec<String> = rules
+            .attributes
+            .iter()
+            .map(|rule| format!("'{}'", rule.name))
+            .collect();
+        return Err(reader.error_at(
+            tag.position,
+            format!("<{name}> needs one of the attributes {}", names.join(", ")),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn billed(document: &str) -> usize {
+        billed_characters(document).unwrap()
+    }
+
+    fn error_of(document: &str, engine: Option<Engine>) -> String {
+        validate(document, engine).err().unwrap().to_string()
+    }
+
+    #[test]
+    fn speak_takes_a_language_a_version_and_a_base() {
+        validate(
+            "<speak version=\"1.1\" xml:lang=\"en-US\" xml:base=\"https://example.com/\">Hi</speak>",
+            Some(Engine::Neural),
+        )
+        .unwrap();
+
+        let error = error_of("<speak xml:lang=\"en_US\">Hi</speak>", None);
+        assert!(error.contains("language code"), "{error}");
+        let error = error_of("<speak voice=\"Joanna\">Hi</speak>", None);
+        assert!(error.contains("has no 'voice' attribute"), "{error}");
+    }
+
+    #[test]
+    fn the_tags_are_checked_against_the_engine() {
+        let emphasis = "<speak><emphasis level=\"strong\">Hi</emphasis></speak>";
+        validate(emphasis, Some(Engine::Standard)).unwrap();
+        let error = error_of(emphasis, Some(Engine::Neural));
+        assert!(error.contains("neural"), "{error}");
+
+        let domain = "<speak><amazon:domain name=\"news\">Hi</amazon:domain></speak>";
+        validate(domain, Some(Engine::Neural)).unwrap();
+        assert!(validate(domain, Some(Engine::Standard)).is_err());
+    }
+
+    #[test]
+    fn an_invalid_value_is_reported_where_it_is() {
+        let error = error_of("<speak>\nHello <break time=\"3\"/></speak>", None);
+
+        assert!(error.contains("line 2, column 14"), "{error}");
+    }
+
+    #[test]
+    fn the_tags_have_to_be_closed_in_order() {
+        let error = error_of("<speak><p><s>Hi</p></s></speak>", None);
+
+        assert!(error.contains("</p>"), "{error}");
+    }
+
+    #[test]
+    fn a_document_can_start_with_a_declaration_and_comments() {
+        let document = "\u{feff}<?xml version=\"1.0\"?>\n<!-- intro -->\n<speak>Hi</speak>";
+
+        assert!(is_document(document));
+        assert_eq!(prepare(document, "ssml", "neural").unwrap(), document);
+    }
+
+    #[test]
+    fn a_fragment_is_wrapped_in_speak() {
+        assert!(!is_document("<!-- intro --><p>Hi</p>"));
+        assert!(!is_document("<speaker>Hi</speaker>"));
+        assert_eq!(
+            prepare("<!-- intro --><p>Hi</p>", "ssml", "neural").unwrap(),
+            "<speak><!-- intro --><p>Hi</p></speak>"
+        );
+        assert_eq!(
+            prepare("Tom & Jerry", "ssml", "neural").unwrap(),
+            "<speak>Tom &amp; Jerry</speak>"
+        );
+    }
+
+    #[test]
+    fn only_the_text_of_a_document_is_billed() {
+        let document = r#"<speak><p>Hello <break time="1s"/>there.</p></speak>"#;
+
+        assert_eq!(billed_characters(document).unwrap(), 12);
+        assert!(billed_characters("<speak><p>Hello</speak>").is_err());
+    }
+
+    #[test]
+    fn a_short_document_is_a_single_part() {
+        let document = "<speak><p>Hello there.</p></speak>";
+
+        let parts = split(document, 3000, 6000).unwrap();
+
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].text, document);
+        assert_eq!(parts[0].start, 0);
+    }
+
+    #[test]
+    fn a_long_document_is_split_at_its_paragraphs_and_sentences() {
+        let sentence = "<s>This sentence is spoken.</s>";
+        let paragraph = format!("<p>{sentence}{sentence}{sentence}</p>");
+        let document =
+            format!("<speak><prosody rate=\"slow\">{paragraph}{paragraph}</prosody></speak>");
+
+        let parts = split(&document, 60, 6000).unwrap();
+
+        assert!(parts.len() > 1);
+        for part in &parts {
+            validate(&part.text, Some(Engine::Neural)).unwrap();
+            assert!(billed(&part.text) <= 60, "{}", part.text);
+            assert!(
+                part.text.starts_with("<speak><prosody rate=\"slow\">"),
+                "{}",
+                part.text
+            );
+            assert!(part.text.ends_with("</prosody></speak>"), "{}", part.text);
+        }
+        let spoken: usize = parts.iter().map(|part| billed(&part.text)).sum();
+        assert_eq!(spoken, billed(&document));
+    }
+
+    #[test]
+    fn the_content_of_a_part_is_where_it_starts_within_the_document() {
+        let document = "<speak><p>One.</p><break time=\"1s\"/><p>Two.</p></speak>";
+
+        let parts = split(document, 5, 6000).unwrap();
+
+        assert_eq!(parts.len(), 2);
+        for part in &parts {
+            let content = &part.text[part.opening..];
+            assert!(
+                document[part.start..].starts_with(&content[..6]),
+                "{content}"
+            );
+        }
+    }
+
+    #[test]
+    fn the_tags_count_toward_the_total_limit() {
+        let paragraph = "<p><prosody volume=\"loud\">Hi.</prosody></p>";
+        let document = format!("<speak>{paragraph}{paragraph}{paragraph}</speak>");
+
+        let parts = split(&document, 3000, 100).unwrap();
+
+        assert!(parts.len() > 1);
+        assert!(parts.iter().all(|part| part.text.chars().count() <= 100));
+    }
+
+    #[test]
+    fn a_sentence_over_the_limit_is_reported() {
+        let document = "<speak><s>Short.</s><s>This one is far too long.</s></speak>";
+
+        let error = split(document, 10, 6000).err().unwrap();
+
+        assert!(matches!(
+            error,
+            Error::TextTooLong {
+                characters: 25,
+                limit: 10
+            }
+        ));
+    }
+}
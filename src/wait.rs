@@ -0,0 +1,186 @@
+//! Waiting for asynchronous jobs by polling their status with exponential backoff.
+use indicatif::{ProgressBar, ProgressStyle};
+use std::future::Future;
+use std::time::Duration;
+use tokio::time::Instant;
+
+use crate::error::{Error, Result};
+
+const FIRST_DELAY: Duration = Duration::from_secs(2);
+const MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// How long to keep polling a job before giving up on it.
+#[derive(Clone, Copy)]
+pub struct Wait {
+    pub timeout: Duration,
+}
+
+impl Default for Wait {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(600),
+        }
+    }
+}
+
+impl Wait {
+    /// Fetches the state of a job until its status is no longer one of the
+    /// `pending` ones, showing a spinner in the meantime.
+    pub async fn until_done<T, F, Fut, S>(
+        &self,
+        job: &str,
+        pending: &[&str],
+        status_of: S,
+        mut fetch: F,
+    ) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+        S: Fn(&T) -> Option<&str>,
+    {
+        let spinner = ProgressBar::new_spinner();
+        if let Ok(style) = ProgressStyle::with_template("{spinner:.green} {msg} [{elapsed}]") {
+            spinner.set_style(style);
+        }
+        spinner.enable_steady_tick(Duration::from_millis(120));
+        let started = Instant::now();
+        let mut delay = FIRST_DELAY;
+        loop {
+            let state = match fetch().await {
+                Ok(state) => state,
+                Err(error) => {
+                    spinner.finish_and_clear();
+                    return Err(error);
+                }
+            };
+            let status = match status_of(&state) {
+                Some(status) if pending.contains(&status) => status.to_string(),
+                _ => {
+                    spinner.finish_and_clear();
+                    return Ok(state);
+                }
+            };
+            let elapsed = started.elapsed();
+            if elapsed >= self.timeout {
+                spinner.finish_and_clear();
+                return Err(Error::WaitTimeout {
+                    job: job.to_string(),
+                    status,
+                    seconds: self.timeout.as_secs(),
+                });
+            }
+            spinner.set_message(format!("Waiting for the job '{job}', which is {status}"));
+            tokio::time::sleep(delay.min(self.timeout - elapsed)).await;
+            delay = next_delay(delay);
+        }
+    }
+}
+
+/// Doubles the delay between two polls, up to `MAX_DELAY`.
+fn next_delay(delay: Duration) -> Duration {
+    (delay * 2).min(MAX_DELAY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    const PENDING: [&str; 2] = ["QUEUED", "IN_PROGRESS"];
+
+    fn wait_for(seconds: u64) -> Wait {
+        Wait {
+            timeout: Duration::from_secs(seconds),
+        }
+    }
+
+    #[test]
+    fn the_delay_doubles_up_to_the_maximum() {
+        let delays: Vec<u64> =
+            std::iter::successors(Some(FIRST_DELAY), |delay| Some(next_delay(*delay)))
+                .take(8)
+                .map(|delay| delay.as_secs())
+                .collect();
+        assert_eq!(delays, [2, 4, 8, 16, 32, 60, 60, 60]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn polling_stops_at_the_first_status_that_is_not_pending() {
+        let statuses = ["QUEUED", "IN_PROGRESS", "COMPLETED", "IN_PROGRESS"];
+        let polls = Cell::new(0);
+        let started = Instant::now();
+
+        let state = wait_for(600)
+            .until_done(
+                "job",
+                &PENDING,
+                |status: &&str| Some(*status),
+                || {
+                    let status = statuses[polls.get()];
+                    polls.set(polls.get() + 1);
+                    async move { Ok(status) }
+                },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(state, "COMPLETED");
+        assert_eq!(polls.get(), 3);
+        assert_eq!(started.elapsed(), Duration::from_secs(2 + 4));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn a_job_still_pending_at_the_timeout_is_reported() {
+        let polls = Cell::new(0);
+
+        let error = wait_for(5)
+            .until_done(
+                "job",
+                &PENDING,
+                |status: &&str| Some(*status),
+                || {
+                    polls.set(polls.get() + 1);
+                    async { Ok("IN_PROGRESS") }
+                },
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            error,
+            Error::WaitTimeout { ref status, seconds: 5, .. } if status == "IN_PROGRESS"
+        ));
+        // Polled at 0, 2 and 5 seconds: the last delay is cut to the timeout.
+        assert_eq!(polls.get(), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn a_failed_poll_ends_the_wait() {
+        let error = wait_for(600)
+            .until_done(
+                "job",
+                &PENDING,
+                |status: &&str| Some(*status),
+                || async { Err::<&str, _>(Error::Service("throttled".to_string())) },
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, Error::Service(message) if message == "throttled"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn a_job_without_a_status_is_not_waited_for() {
+        let state = wait_for(600)
+            .until_done(
+                "job",
+                &PENDING,
+                |_: &Option<&str>| None,
+                || async { Ok(None) },
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(state, None);
+    }
+}
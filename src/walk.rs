@@ -0,0 +1,250 @@
+//! Finding the files of a local directory that the batch operations work on.
+//!
+//! The patterns are matched against the path of a file relative to the
+//! directory walked, with '/' separating the subdirectories on every platform.
+//! Only '**' matches across them, so '*.jpg' picks the images at the top of the
+//! directory while '**/*.jpg' picks them at any depth.
+use glob::{MatchOptions, Pattern};
+use std::collections::HashSet;
+use std::fs::read_dir;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result, WithPath};
+
+/// Which files of a directory are picked, and whether its subdirectories are
+/// walked as well.
+#[derive(Clone, Default)]
+pub struct Walk {
+    pub recursive: bool,
+    /// Only the files matching one of these are picked, unless there are none.
+    pub include: Vec<Pattern>,
+    /// The files and directories matching one of these are skipped.
+    pub exclude: Vec<Pattern>,
+    /// Only the files with one of these extensions are picked, unless there
+    /// are none, in which case the operation decides.
+    pub extensions: Vec<String>,
+}
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// A file found by a [`Walk`].
+pub struct WalkedFile {
+    pub path: PathBuf,
+    /// The path relative to the directory walked, such as 'faces/2023/a.jpg'.
+    pub relative: String,
+}
+
+impl WalkedFile {
+    /// The subdirectories of the file within the directory walked, ending in
+    /// '/', or an empty string when the file is directly in it.
+    pub fn relative_dir(&self) -> &str {
+        match self.relative.rfind('/') {
+            Some(index) => &self.relative[..=index],
+            None => "",
+        }
+    }
+}
+
+/// Parses a glob pattern such as '*.jpg' or 'raw/**'.
+pub fn parse_pattern(pattern: &str) -> Result<Pattern> {
+    Pattern::new(pattern.trim()).map_err(|error| Error::Glob {
+        pattern: pattern.to_string(),
+        message: error.msg.to_string(),
+    })
+}
+
+/// Parses a comma separated list of glob patterns, ignoring the empty ones.
+pub fn parse_patterns(patterns: &str) -> Result<Vec<Pattern>> {
+    patterns
+        .split(',')
+        .filter(|pattern| !pattern.trim().is_empty())
+        .map(parse_pattern)
+        .collect()
+}
+
+/// Parses a comma separated list of extensions such as 'jpg, .PNG' into
+/// lowercase extensions without the leading dot.
+pub fn parse_extensions(extensions: &str) -> Vec<String> {
+    extensions
+        .split(',')
+        .map(|extension| extension.trim().trim_start_matches('.').to_lowercase())
+        .filter(|extension| !extension.is_empty())
+        .collect()
+}
+
+impl Walk {
+    /// The files of `root` sorted by their relative path, keeping only the
+    /// ones with one of `default_extensions` when no extension was chosen and
+    /// the list isn't empty.
+    pub fn files(&self, root: &Path, default_extensions: &[&str]) -> Result<Vec<WalkedFile>> {
+        let mut files = Vec::new();
+        let mut visited = HashSet::from([root.canonicalize().with_path(root)?]);
+        self.walk(root, "", default_extensions, &mut visited, &mut files)?;
+        files.sort_by(|a, b| a.relative.cmp(&b.relative));
+        Ok(files)
+    }
+
+    /// Walks `dir`, skipping the subdirectories in `visited`, which every
+    /// directory walked is added to, so that a symbolic link to a directory
+    /// containing it is only followed once.
+    fn walk(
+        &self,
+        dir: &Path,
+        relative_dir: &str,
+        default_extensions: &[&str],
+        visited: &mut HashSet<PathBuf>,
+        files: &mut Vec<WalkedFile>,
+    ) -> Result<()> {
+        for entry in read_dir(dir).with_path(dir)? {
+            let path = entry.with_path(dir)?.path();
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let relative = format!("{relative_dir}{name}");
+            if self
+                .exclude
+                .iter()
+                .any(|pattern| pattern.matches_with(&relative, MATCH_OPTIONS))
+            {
+                continue;
+            }
+            if path.is_dir() {
+                if self.recursive && visited.insert(path.canonicalize().with_path(&path)?) {
+                    let relative_dir = format!("{relative}/");
+                    self.walk(&path, &relative_dir, default_extensions, visited, files)?;
+                }
+                continue;
+            }
+            if !path.is_file() || !self.has_extension(&path, default_extensions) {
+                continue;
+            }
+            if !self.include.is_empty()
+                && !self
+                    .include
+                    .iter()
+                    .any(|pattern| pattern.matches_with(&relative, MATCH_OPTIONS))
+            {
+                continue;
+            }
+            files.push(WalkedFile { path, relative });
+        }
+        Ok(())
+    }
+
+    fn has_extension(&self, path: &Path, default_extensions: &[&str]) -> bool {
+        let Some(extension) = path.extension() else {
+            return self.extensions.is_empty() && default_extensions.is_empty();
+        };
+        let extension = extension.to_string_lossy().to_lowercase();
+        if self.extensions.is_empty() {
+            default_extensions.is_empty() || default_extensions.contains(&extension.as_str())
+        } else {
+            self.extensions.contains(&extension)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{create_dir_all, write};
+    use tempfile::TempDir;
+
+    fn tree(files: &[&str]) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        for file in files {
+            let path = dir.path().join(file);
+            create_dir_all(path.parent().unwrap()).unwrap();
+            write(path, "").unwrap();
+        }
+        dir
+    }
+
+    fn relative_paths(walk: &Walk, root: &Path) -> Vec<String> {
+        walk.files(root, &["jpg", "png"])
+            .unwrap()
+            .into_iter()
+            .map(|file| file.relative)
+            .collect()
+    }
+
+    #[test]
+    fn the_subdirectories_are_only_walked_when_recursive() {
+        let dir = tree(&["b.jpg", "a.png", "notes.txt", "faces/2023/c.JPG"]);
+        let mut walk = Walk::default();
+        assert_eq!(relative_paths(&walk, dir.path()), ["a.png", "b.jpg"]);
+
+        walk.recursive = true;
+        assert_eq!(
+            relative_paths(&walk, dir.path()),
+            ["a.png", "b.jpg", "faces/2023/c.JPG"]
+        );
+    }
+
+    #[test]
+    fn a_star_does_not_match_across_directories() {
+        let dir = tree(&["a.jpg", "faces/b.jpg", "faces/2023/c.jpg"]);
+        let mut walk = Walk {
+            recursive: true,
+            include: parse_patterns("*.jpg").unwrap(),
+            ..Walk::default()
+        };
+        assert_eq!(relative_paths(&walk, dir.path()), ["a.jpg"]);
+
+        walk.include = parse_patterns("faces/*.jpg").unwrap();
+        assert_eq!(relative_paths(&walk, dir.path()), ["faces/b.jpg"]);
+
+        walk.include = parse_patterns("**/*.jpg").unwrap();
+        assert_eq!(
+            relative_paths(&walk, dir.path()),
+            ["a.jpg", "faces/2023/c.jpg", "faces/b.jpg"]
+        );
+    }
+
+    #[test]
+    fn an_excluded_directory_is_skipped() {
+        let dir = tree(&["a.jpg", "raw/b.jpg", "faces/raw.jpg"]);
+        let walk = Walk {
+            recursive: true,
+            exclude: parse_patterns("raw, *.png").unwrap(),
+            ..Walk::default()
+        };
+        assert_eq!(
+            relative_paths(&walk, dir.path()),
+            ["a.jpg", "faces/raw.jpg"]
+        );
+    }
+
+    #[test]
+    fn the_chosen_extensions_replace_the_default_ones() {
+        let dir = tree(&["a.jpg", "b.webp", "c.WEBP"]);
+        let walk = Walk {
+            extensions: parse_extensions("webp, .png"),
+            ..Walk::default()
+        };
+        assert_eq!(relative_paths(&walk, dir.path()), ["b.webp", "c.WEBP"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn a_symbolic_link_cycle_is_only_walked_once() {
+        let dir = tree(&["faces/a.jpg"]);
+        std::os::unix::fs::symlink(dir.path(), dir.path().join("faces/loop")).unwrap();
+        let walk = Walk {
+            recursive: true,
+            ..Walk::default()
+        };
+        assert_eq!(relative_paths(&walk, dir.path()), ["faces/a.jpg"]);
+    }
+
+    #[test]
+    fn an_invalid_pattern_is_reported() {
+        let error = parse_patterns("*.jpg, [a").unwrap_err();
+        assert!(matches!(error, Error::Glob { pattern, .. } if pattern == " [a"));
+    }
+}
@@ -0,0 +1,95 @@
+//! Where the files written by the operations go.
+//!
+//! Every run of an operation gets its own timestamped directory under the output
+//! root, e.g. `DLearningClientOutputs/face_images-20231017-101530/`, so running
+//! an option again never overwrites or deletes the files of an earlier run.
+//! Intermediate files go to a temporary directory that is removed once dropped.
+use chrono::Local;
+use std::env::{current_dir, temp_dir};
+use std::fs::create_dir_all;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+use crate::error::{Result, WithPath};
+
+/// The output root used when neither the command line nor the profile sets one.
+pub const DEFAULT_OUTPUT_ROOT: &str = "DLearningClientOutputs";
+
+/// Resolves a path against the current directory, so the run directories are
+/// reported the same way whether the output root is relative or not.
+pub fn absolute(path: impl AsRef<Path>) -> Result<PathBuf> {
+    let path = path.as_ref();
+    match path.is_absolute() {
+        true => Ok(path.to_path_buf()),
+        false => Ok(current_dir().with_path(".")?.join(path)),
+    }
+}
+
+/// Creates a new directory named after the operation and the current time.
+pub fn run_dir(root: &Path, name: &str) -> Result<PathBuf> {
+    let root = absolute(root)?;
+    let stamped = format!("{name}-{}", Local::now().format("%Y%m%d-%H%M%S"));
+    let mut dir = root.join(&stamped);
+    let mut attempt = 1;
+    while dir.exists() {
+        attempt += 1;
+        dir = root.join(format!("{stamped}-{attempt}"));
+    }
+    create_dir_all(&dir).with_path(&dir)?;
+    Ok(dir)
+}
+
+/// Creates a temporary directory for files that are only needed during the run.
+pub fn scratch_dir() -> Result<TempDir> {
+    tempfile::Builder::new()
+        .prefix("dlearningclient-")
+        .tempdir()
+        .with_path(temp_dir())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runs_get_their_own_directories_even_within_the_same_second() {
+        let root = TempDir::new().unwrap();
+        let output_root = root.path().join("outputs");
+
+        let first = run_dir(&output_root, "texts").unwrap();
+        let second = run_dir(&output_root, "texts").unwrap();
+
+        assert!(first.is_dir() && second.is_dir());
+        assert_ne!(first, second);
+        assert_eq!(first.parent(), Some(output_root.as_path()));
+        let name = |dir: &Path| dir.file_name().unwrap().to_string_lossy().into_owned();
+        let stamp = name(&first)["texts-".len()..].to_string();
+        assert_eq!(stamp.len(), "20231017-101530".len(), "{stamp}");
+        // The second run only gets a numbered name when the clock hasn't ticked.
+        let second_name = name(&second);
+        assert!(
+            second_name == format!("texts-{stamp}-2") || !second_name.contains(&stamp),
+            "{second_name}"
+        );
+    }
+
+    #[test]
+    fn a_relative_path_is_resolved_against_the_current_directory() {
+        let path = absolute("DLearningClientOutputs").unwrap();
+
+        assert!(path.is_absolute());
+        assert!(path.ends_with(DEFAULT_OUTPUT_ROOT));
+        assert_eq!(absolute(&path).unwrap(), path);
+    }
+
+    #[test]
+    fn the_scratch_directory_is_removed_once_dropped() {
+        let scratch = scratch_dir().unwrap();
+        let path = scratch.path().to_path_buf();
+        assert!(path.is_dir());
+
+        drop(scratch);
+
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,444 @@
+//! Reading the XML documents that are checked before they are sent, such as
+//! SSML and pronunciation lexicons.
+//!
+//! The reader only knows about well formed XML: the tags are handed out one
+//! at a time to be checked against what the service accepts, with the line and
+//! column to report a problem at.
+use crate::error::{Error, Result};
+
+#[derive(Clone, Copy)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// An attribute as it's written in a tag, with its entities replaced.
+pub struct Attribute<'a> {
+    pub name: &'a str,
+    pub value: String,
+    pub position: Position,
+}
+
+pub struct StartTag<'a> {
+    pub name: &'a str,
+    pub attributes: Vec<Attribute<'a>>,
+    /// Whether the tag closes itself, like `<break/>`.
+    pub closed: bool,
+    pub position: Position,
+    /// The element the tag is within, or `None` for the root.
+    pub parent: Option<&'a str>,
+}
+
+impl StartTag<'_> {
+    /// The attributes, leaving out the namespaces, which can go on any tag.
+    pub fn attributes(&self) -> impl Iterator<Item = &Attribute<'_>> {
+        self.attributes
+            .iter()
+            .filter(|attribute| !attribute.name.starts_with("xmlns"))
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes()
+            .find(|attribute| attribute.name == name)
+            .map(|attribute| attribute.value.as_str())
+    }
+}
+
+pub enum Event<'a> {
+    Start(StartTag<'a>),
+    End {
+        name: &'a str,
+    },
+    /// The text between two tags, with its entities replaced.
+    Text {
+        text: String,
+        position: Position,
+    },
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '-' | '_' | ':' | '.')
+}
+
+/// Reads a document one character at a time, checking that its tags are
+/// closed in order and that a single `root` element is around everything.
+pub struct Reader<'a> {
+    text: &'a str,
+    offset: usize,
+    position: Position,
+    /// What the document is, such as 'SSML', for the errors.
+    kind: &'static str,
+    root: &'static str,
+    open: Vec<(&'a str, Position)>,
+    implied_root: bool,
+    root_closed: bool,
+    started: bool,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(text: &'a str, kind: &'static str, root: &'static str) -> Self {
+        Self {
+            text,
+            offset: 0,
+            position: Position { line: 1, column: 1 },
+            kind,
+            root,
+            open: Vec::new(),
+            implied_root: false,
+            root_closed: false,
+            started: false,
+        }
+    }
+
+    /// Reads a part of a document, as if the `root` element was around it.
+    pub fn fragment(text: &'a str, kind: &'static str, root: &'static str) -> Self {
+        let mut reader = Self::new(text, kind, root);
+        reader.open.push((root, reader.position));
+        reader.implied_root = true;
+        reader
+    }
+
+    /// The element the reader is within, such as the one of the last text.
+    pub fn within(&self) -> Option<&'a str> {
+        self.open.last().map(|(name, _)| *name)
+    }
+
+    /// How far into the text the reader is, in bytes, which is right after
+    /// the last tag or text it handed out.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn error_at(&self, position: Position, message: impl Into<String>) -> Error {
+        Error::Markup {
+            kind: self.kind,
+            line: position.line,
+            column: position.column,
+            message: message.into(),
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.text[self.offset..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.offset += c.len_utf8();
+        if c == '\n' {
+            self.position.line += 1;
+            self.position.column = 1;
+        } else {
+            self.position.column += 1;
+        }
+        Some(c)
+    }
+
+    fn eat(&mut self, expected: &str) -> bool {
+        if !self.rest().starts_with(expected) {
+            return false;
+        }
+        for _ in expected.chars() {
+            self.bump();
+        }
+        true
+    }
+
+    /// Skips the whitespace, returning whether there was any.
+    fn skip_whitespace(&mut self) -> bool {
+        let start = self.offset;
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+        self.offset > start
+    }
+
+    fn skip_past(&mut self, end: &str, what: &str) -> Result<()> {
+        let start = self.position;
+        loop {
+            if self.eat(end) {
+                return Ok(());
+            }
+            if self.bump().is_none() {
+                return Err(self.error_at(start, format!("{what} is never closed")));
+            }
+        }
+    }
+
+    fn name(&mut self) -> Result<&'a str> {
+        let start = self.offset;
+        while self.peek().is_some_and(is_name_char) {
+            self.bump();
+        }
+        if self.offset == start {
+            return Err(self.error_at(
+                self.position,
+                "expected a name; a '<' that isn't a tag has to be written as '&lt;'",
+            ));
+        }
+        Ok(&self.text[start..self.offset])
+    }
+
+    /// Reads an entity such as '&amp;' or '&#233;' into its character.
+    fn entity(&mut self) -> Result<char> {
+        let start = self.position;
+        self.bump();
+        let name_start = self.offset;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '#')
+        {
+            self.bump();
+        }
+        let name = &self.text[name_start..self.offset];
+        if name.is_empty() || !self.eat(";") {
+            return Err(self.error_at(
+                start,
+                "a '&' that isn't an entity has to be written as '&amp;'",
+            ));
+        }
+        let c = match name {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => name.strip_prefix('#').and_then(|number| {
+                match number.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => number.parse().ok(),
+                }
+                .and_then(char::from_u32)
+            }),
+        };
+        c.ok_or_else(|| self.error_at(start, format!("'&{name};' isn't an XML entity")))
+    }
+
+    fn attribute_value(&mut self, attribute: &str) -> Result<String> {
+        let start = self.position;
+        let quote = match self.peek() {
+            Some(quote @ ('"' | '\'')) => quote,
+            _ => {
+                return Err(self.error_at(
+                    start,
+                    format!("the value of '{attribute}' has to be in quotes"),
+                ))
+            }
+        };
+        self.bump();
+        let mut value = String::new();
+        loop {
+            match self.peek() {
+                None => {
+                    return Err(
+                        self.error_at(start, format!("the value of '{attribute}' is never closed"))
+                    )
+                }
+                Some(c) if c == quote => {
+                    self.bump();
+                    return Ok(value);
+                }
+                Some('<') => {
+                    return Err(self.error_at(
+                        self.position,
+                        format!("the value of '{attribute}' can't have a '<'"),
+                    ))
+                }
+                Some('&') => value.push(self.entity()?),
+                Some(c) => {
+                    value.push(c);
+                    self.bump();
+                }
+            }
+        }
+    }
+
+    fn start_tag(&mut self) -> Result<StartTag<'a>> {
+        let position = self.position;
+        self.bump();
+        let name = self.name()?;
+        let mut attributes: Vec<Attribute> = Vec::new();
+        loop {
+            let spaced = self.skip_whitespace();
+            let closed = self.eat("/>");
+            if closed || self.eat(">") {
+                return Ok(StartTag {
+                    name,
+                    attributes,
+                    closed,
+                    position,
+                    parent: self.open.last().map(|(parent, _)| *parent),
+                });
+            }
+            if self.peek().is_none() {
+                return Err(self.error_at(position, format!("<{name}> is never finished")));
+            }
+            if !spaced {
+                return Err(self.error_at(
+                    self.position,
+                    format!("expected a space, '>' or '/>' in <{name}>"),
+                ));
+            }
+            let attribute_position = self.position;
+            let attribute = self.name()?;
+            self.skip_whitespace();
+            if !self.eat("=") {
+                return Err(
+                    self.error_at(self.position, format!("expected '=' after '{attribute}'"))
+                );
+            }
+            self.skip_whitespace();
+            let value = self.attribute_value(attribute)?;
+            if attributes.iter().any(|written| written.name == attribute) {
+                return Err(self.error_at(
+                    attribute_position,
+                    format!("'{attribute}' is given twice in <{name}>"),
+                ));
+            }
+            attributes.push(Attribute {
+                name: attribute,
+                value,
+                position: attribute_position,
+            });
+        }
+    }
+
+    fn end_tag(&mut self) -> Result<&'a str> {
+        let position = self.position;
+        self.eat("</");
+        let name = self.name()?;
+        self.skip_whitespace();
+        if !self.eat(">") {
+            return Err(self.error_at(self.position, format!("expected '>' after </{name}")));
+        }
+        if self.implied_root && self.open.len() == 1 {
+            return Err(self.error_at(position, format!("</{name}> closes a tag that isn't open")));
+        }
+        match self.open.pop() {
+            Some((opened, _)) if opened == name => {}
+            Some((opened, at)) => {
+                return Err(self.error_at(
+                    position,
+                    format!(
+                        "</{name}> doesn't close the <{opened}> at line {}, column {}",
+                        at.line, at.column
+                    ),
+                ))
+            }
+            None => {
+                return Err(
+                    self.error_at(position, format!("</{name}> closes a tag that isn't open"))
+                )
+            }
+        }
+        self.root_closed = self.open.is_empty();
+        Ok(name)
+    }
+
+    /// The next tag or text of the document, or `None` at its end.
+    pub fn next(&mut self) -> Result<Option<Event<'a>>> {
+        if !self.started {
+            self.started = true;
+            self.eat("\u{feff}");
+            self.skip_whitespace();
+            if self.rest().starts_with("<?xml") {
+                self.skip_past("?>", "The XML declaration")?;
+            }
+        }
+        loop {
+            let Some(c) = self.peek() else {
+                return self.finish().map(|()| None);
+            };
+            let outside = self.open.is_empty();
+            if self.rest().starts_with("<!--") {
+                self.skip_past("-->", "The comment")?;
+            } else if self.rest().starts_with("</") {
+                let name = self.end_tag()?;
+                return Ok(Some(Event::End { name }));
+            } else if self.rest().starts_with("<!") || self.rest().starts_with("<?") {
+                return Err(self.error_at(
+                    self.position,
+                    "only tags, text and comments can be in the document",
+                ));
+            } else if c == '<' {
+                let tag = self.start_tag()?;
+                let root = self.root;
+                if outside && self.root_closed {
+                    return Err(self.error_at(
+                        tag.position,
+                        format!("<{}> is after the end of </{root}>", tag.name),
+                    ));
+                }
+                if outside && tag.name != root {
+                    return Err(self.error_at(
+                        tag.position,
+                        format!(
+                            "the document has to start with <{root}>, not <{}>",
+                            tag.name
+                        ),
+                    ));
+                }
+                if !outside && tag.name == root {
+                    return Err(self.error_at(
+                        tag.position,
+                        format!("<{root}> can only be around the whole document"),
+                    ));
+                }
+                if !tag.closed {
+                    self.open.push((tag.name, tag.position));
+                } else if outside {
+                    self.root_closed = true;
+                }
+                return Ok(Some(Event::Start(tag)));
+            } else if outside {
+                if !c.is_whitespace() {
+                    return Err(self.error_at(
+                        self.position,
+                        format!("the text has to be within <{}>", self.root),
+                    ));
+                }
+                self.bump();
+            } else {
+                return self.text().map(Some);
+            }
+        }
+    }
+
+    fn text(&mut self) -> Result<Event<'a>> {
+        let position = self.position;
+        let mut text = String::new();
+        while let Some(c) = self.peek() {
+            match c {
+                '<' => break,
+                '&' => text.push(self.entity()?),
+                c => {
+                    text.push(c);
+                    self.bump();
+                }
+            }
+        }
+        Ok(Event::Text { text, position })
+    }
+
+    fn finish(&mut self) -> Result<()> {
+        let implied = usize::from(self.implied_root);
+        if self.open.len() > implied {
+            let (name, position) = self.open[self.open.len() - 1];
+            return Err(self.error_at(position, format!("<{name}> is never closed")));
+        }
+        if self.implied_root {
+            return Ok(());
+        }
+        if !self.root_closed {
+            return Err(self.error_at(
+                self.position,
+                format!("the document has no <{}>", self.root),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,754 @@
+//! Runs the subcommands against the fake backend, which answers every service
+//! call from a directory of fixture files, so that each service is exercised
+//! through the same clients the menus use without reaching AWS.
+use std::fs::{create_dir_all, read, read_dir, read_to_string, remove_dir, write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use tempfile::TempDir;
+
+/// A fixture directory, and the directory the commands are run from, with the
+/// configuration and the job ledger kept within it as well.
+struct Fixtures {
+    dir: TempDir,
+}
+
+impl Fixtures {
+    fn new() -> Self {
+        let fixtures = Self {
+            dir: TempDir::new().unwrap(),
+        };
+        for dir in ["fixtures", "work", "config", "data"] {
+            create_dir_all(fixtures.path(dir)).unwrap();
+        }
+        fixtures
+    }
+
+    fn path(&self, relative: &str) -> PathBuf {
+        self.dir.path().join(relative)
+    }
+
+    /// Writes a fixture file, such as 'voices.json' or 'objects/<bucket>/<key>'.
+    fn fixture(&self, relative: &str, content: &str) -> &Self {
+        let path = self.path("fixtures").join(relative);
+        create_dir_all(path.parent().unwrap()).unwrap();
+        write(path, content).unwrap();
+        self
+    }
+
+    /// Writes a file of the directory the commands are run from.
+    fn file(&self, relative: &str, content: &str) -> PathBuf {
+        let path = self.path("work").join(relative);
+        create_dir_all(path.parent().unwrap()).unwrap();
+        write(&path, content).unwrap();
+        path
+    }
+
+    fn run(&self, args: &[&str]) -> Output {
+        Command::new(env!("CARGO_BIN_EXE_dlearningclient"))
+            .arg("--fixtures")
+            .arg(self.path("fixtures"))
+            .args(args)
+            .current_dir(self.path("work"))
+            .env("XDG_CONFIG_HOME", self.path("config"))
+            .env("XDG_DATA_HOME", self.path("data"))
+            .env("NO_COLOR", "1")
+            .output()
+            .unwrap()
+    }
+
+    /// The directory of the only run written under the output root.
+    fn only_run_dir(&self, output_root: &str) -> PathBuf {
+        let mut runs = read_dir(self.path("work").join(output_root)).unwrap();
+        let run_dir = runs.next().unwrap().unwrap().path();
+        assert!(runs.next().is_none());
+        run_dir
+    }
+
+    /// Runs the command, which has to succeed, and returns what it printed.
+    fn succeed(&self, args: &[&str]) -> String {
+        let output = self.run(args);
+        let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+        assert!(
+            output.status.success(),
+            "{args:?} failed:\n{stdout}{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        stdout
+    }
+
+    /// Runs the command, which has to fail, and returns what it printed.
+    fn fail(&self, args: &[&str]) -> String {
+        let output = self.run(args);
+        let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+        assert_eq!(
+            output.status.code(),
+            Some(1),
+            "{args:?} didn't fail:\n{stdout}"
+        );
+        stdout
+    }
+}
+
+fn write_image(path: &Path) {
+    create_dir_all(path.parent().unwrap()).unwrap();
+    image::RgbImage::from_pixel(8, 8, image::Rgb([200, 120, 80]))
+        .save(path)
+        .unwrap();
+}
+
+const FACE: &str = r#"{
+    "gender": "Female", "gender_confidence": 99.1,
+    "age_low": 25, "age_high": 33,
+    "smile": true, "smile_confidence": 90.0,
+    "beard": false, "beard_confidence": 95.0,
+    "width": 0.5, "height": 0.5, "left": 0.25, "top": 0.25
+}"#;
+
+#[test]
+fn s3_upload_copies_the_images_into_the_bucket() {
+    let fixtures = Fixtures::new();
+    create_dir_all(fixtures.path("fixtures/objects/photos")).unwrap();
+    write_image(&fixtures.path("work/images/a.png"));
+    write_image(&fixtures.path("work/images/b.png"));
+    fixtures.file("images/notes.txt", "not an image");
+
+    let stdout = fixtures.succeed(&[
+        "rekognition",
+        "upload-images",
+        "--bucket",
+        "photos",
+        "--dir",
+        "images",
+        "--prefix",
+        "uploads/",
+    ]);
+
+    for name in ["a.png", "b.png"] {
+        assert!(fixtures
+            .path("fixtures/objects/photos/uploads")
+            .join(name)
+            .is_file());
+    }
+    assert!(stdout.contains("2 image(s) are uploaded"), "{stdout}");
+    assert!(
+        stdout.contains("2 of 3 file(s) succeeded, 1 skipped"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn converted_images_are_uploaded_next_to_the_ones_of_the_same_name() {
+    let fixtures = Fixtures::new();
+    create_dir_all(fixtures.path("fixtures/objects/photos")).unwrap();
+    write_image(&fixtures.path("work/images/a.png"));
+    write_image(&fixtures.path("work/images/a.jpg"));
+
+    fixtures.succeed(&[
+        "rekognition",
+        "upload-images",
+        "--bucket",
+        "photos",
+        "--dir",
+        "images",
+        "--prefix",
+        "uploads",
+        "--convert-to",
+        "png",
+    ]);
+
+    for name in ["a.png", "a.jpg.png"] {
+        assert!(fixtures
+            .path("fixtures/objects/photos/uploads")
+            .join(name)
+            .is_file());
+    }
+}
+
+#[test]
+fn objects_of_the_same_name_are_downloaded_under_numbered_names() {
+    let fixtures = Fixtures::new();
+    write_image(&fixtures.path("fixtures/objects/photos/stars/a/x.png"));
+    write_image(&fixtures.path("fixtures/objects/photos/stars/b/x.png"));
+    fixtures.fixture("celebrities.json", "{}");
+
+    fixtures.succeed(&[
+        "rekognition",
+        "recognize-celebrities",
+        "--bucket",
+        "photos",
+        "--prefix",
+        "stars/",
+        "--output-dir",
+        "outputs",
+    ]);
+
+    let downloaded = fixtures.only_run_dir("outputs").join("DownloadedImages");
+    assert!(downloaded.join("x.png").is_file());
+    assert!(downloaded.join("x (2).png").is_file());
+}
+
+#[test]
+fn a_failed_upload_is_retried_when_the_run_is_resumed() {
+    let fixtures = Fixtures::new();
+    write_image(&fixtures.path("work/images/a.png"));
+    write_image(&fixtures.path("work/images/b.png"));
+    // The object can't be written over a directory, so only 'b.png' fails.
+    create_dir_all(fixtures.path("fixtures/objects/photos/uploads/b.png")).unwrap();
+    let upload = [
+        "rekognition",
+        "upload-images",
+        "--bucket",
+        "photos",
+        "--dir",
+        "images",
+        "--prefix",
+        "uploads/",
+        "--output-dir",
+        "outputs",
+    ];
+
+    let stdout = fixtures.succeed(&upload);
+    assert!(stdout.contains("1 of 2 file(s) succeeded"), "{stdout}");
+    assert!(stdout.contains("1 file(s) failed"), "{stdout}");
+
+    remove_dir(fixtures.path("fixtures/objects/photos/uploads/b.png")).unwrap();
+    let run_dir = fixtures.only_run_dir("outputs");
+    let mut resume = upload.to_vec();
+    resume.extend(["--resume", run_dir.to_str().unwrap()]);
+    let stdout = fixtures.succeed(&resume);
+
+    assert!(stdout.contains("2 of 2 file(s) succeeded"), "{stdout}");
+    assert!(stdout.contains("1 of them were already done"), "{stdout}");
+    assert!(fixtures
+        .path("fixtures/objects/photos/uploads/b.png")
+        .is_file());
+    assert_eq!(fixtures.only_run_dir("outputs"), run_dir);
+}
+
+#[test]
+fn only_a_run_of_the_same_operation_can_be_resumed() {
+    let fixtures = Fixtures::new();
+    fixtures.fixture("faces.json", "{}");
+    write_image(&fixtures.path("work/images/a.png"));
+    create_dir_all(fixtures.path("work/outputs/texts-20231015-101500")).unwrap();
+
+    let stdout = fixtures.fail(&[
+        "rekognition",
+        "detect-faces",
+        "--image",
+        "images",
+        "--output-dir",
+        "outputs",
+        "--resume",
+        "outputs/texts-20231015-101500",
+    ]);
+
+    assert!(
+        stdout.contains("is not the directory of a 'detected_faces' run"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn an_upload_to_a_missing_bucket_fails() {
+    let fixtures = Fixtures::new();
+    write_image(&fixtures.path("work/images/a.png"));
+
+    let stdout = fixtures.succeed(&[
+        "rekognition",
+        "upload-images",
+        "--bucket",
+        "missing",
+        "--dir",
+        "images",
+        "--prefix",
+        "uploads/",
+    ]);
+
+    assert!(stdout.contains("0 of 1 file(s) succeeded"), "{stdout}");
+    assert!(
+        stdout.contains("The bucket 'missing' doesn't exist"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn the_redacted_images_keep_their_relative_path_in_the_bucket() {
+    let fixtures = Fixtures::new();
+    fixtures.fixture("faces.json", "{}");
+    create_dir_all(fixtures.path("fixtures/objects/photos")).unwrap();
+    write_image(&fixtures.path("work/images/a.png"));
+    write_image(&fixtures.path("work/images/sub/a.png"));
+
+    fixtures.succeed(&[
+        "rekognition",
+        "redact-faces",
+        "--image",
+        "images",
+        "--recursive",
+        "--upload-bucket",
+        "photos",
+        "--upload-prefix",
+        "redacted/",
+        "--output-dir",
+        "outputs",
+    ]);
+
+    for key in ["a.png", "sub/a.png"] {
+        assert!(fixtures
+            .path("fixtures/objects/photos/redacted")
+            .join(key)
+            .is_file());
+    }
+}
+
+#[test]
+fn rekognition_detects_the_faces_of_an_object() {
+    let fixtures = Fixtures::new();
+    fixtures.fixture("faces.json", &format!(r#"{{"photos/a.png": [{FACE}]}}"#));
+
+    fixtures.succeed(&[
+        "rekognition",
+        "detect-faces",
+        "--bucket",
+        "photos",
+        "--key",
+        "a.png",
+        "--output-dir",
+        "outputs",
+    ]);
+
+    let run_dir = fixtures.only_run_dir("outputs");
+    assert!(run_dir
+        .file_name()
+        .unwrap()
+        .to_string_lossy()
+        .starts_with("detected_faces-"));
+    let details = read_to_string(run_dir.join("FaceDetail.txt")).unwrap();
+    assert!(details.contains("Gender: Female"), "{details}");
+}
+
+#[test]
+fn the_faces_of_a_local_directory_are_detected_without_a_bucket() {
+    let fixtures = Fixtures::new();
+    write_image(&fixtures.path("work/images/a.png"));
+    fixtures.file("images/notes.txt", "not an image");
+    fixtures.fixture("faces.json", &format!(r#"{{"images/a.png": [{FACE}]}}"#));
+
+    let stdout = fixtures.succeed(&[
+        "rekognition",
+        "detect-faces",
+        "--image",
+        "images",
+        "--output-dir",
+        "outputs",
+        "--format",
+        "jsonl",
+        "--output",
+        "-",
+    ]);
+
+    assert_eq!(
+        stdout.matches(r#""image":"images/a.png""#).count(),
+        1,
+        "{stdout}"
+    );
+    assert!(!stdout.contains("notes.txt"), "{stdout}");
+}
+
+#[test]
+fn celebrities_under_a_prefix_are_written_to_a_report() {
+    let fixtures = Fixtures::new();
+    write_image(&fixtures.path("fixtures/objects/photos/stars/a.png"));
+    fixtures.fixture(
+        "celebrities.json",
+        r#"{"photos/stars/a.png": [{"name": "Jane Doe", "match_confidence": 99.5}]}"#,
+    );
+
+    let stdout = fixtures.succeed(&[
+        "rekognition",
+        "recognize-celebrities",
+        "--bucket",
+        "photos",
+        "--prefix",
+        "stars/",
+        "--output-dir",
+        "outputs",
+        "--format",
+        "jsonl",
+        "--output",
+        "-",
+    ]);
+
+    assert!(stdout.contains(r#""name":"Jane Doe""#), "{stdout}");
+    let run_dir = fixtures.only_run_dir("outputs");
+    assert!(run_dir.join("DownloadedImages/a.png").is_file());
+    assert!(read(run_dir.join("celebrities.pdf"))
+        .unwrap()
+        .starts_with(b"%PDF"));
+}
+
+#[test]
+fn polly_speaks_the_language_of_the_voice_when_none_is_given() {
+    let fixtures = Fixtures::new();
+    fixtures.file("speech.txt", "Hello there");
+
+    let stdout = fixtures.succeed(&[
+        "polly",
+        "synthesize",
+        "--engine",
+        "neural",
+        "--voice",
+        "Joanna",
+        "--text-type",
+        "text",
+        "--text-file",
+        "speech.txt",
+        "--bucket",
+        "audio",
+    ]);
+
+    assert!(
+        stdout.contains("'Joanna' voice (neural, the voice's language)"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn polly_synthesizes_to_a_local_file() {
+    let fixtures = Fixtures::new();
+    fixtures.file("speech.txt", "Hello there.");
+
+    fixtures.succeed(&[
+        "polly",
+        "synthesize-to-file",
+        "--engine",
+        "neural",
+        "--voice",
+        "Joanna",
+        "--text-file",
+        "speech.txt",
+        "--file",
+        "speech.mp3",
+    ]);
+
+    let audio = read_to_string(fixtures.path("work/speech.mp3")).unwrap();
+    assert_eq!(audio, "[Joanna: Hello there.]\n");
+}
+
+#[test]
+fn a_text_type_or_format_that_is_not_listed_is_rejected() {
+    let fixtures = Fixtures::new();
+    fixtures.file("speech.txt", "Hello there.");
+
+    for (flag, value) in [("--text-type", "html"), ("--audio-format", "wav")] {
+        let output = fixtures.run(&[
+            "polly",
+            "synthesize",
+            "--engine",
+            "neural",
+            "--voice",
+            "Joanna",
+            "--text-file",
+            "speech.txt",
+            "--bucket",
+            "speech",
+            flag,
+            value,
+        ]);
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert_eq!(output.status.code(), Some(2), "{stderr}");
+        assert!(stderr.contains("possible values"), "{stderr}");
+    }
+}
+
+#[test]
+fn speech_marks_are_written_next_to_the_audio_with_their_subtitles() {
+    let fixtures = Fixtures::new();
+    fixtures.file("speech.txt", "Hello there. How are you?");
+
+    fixtures.succeed(&[
+        "polly",
+        "synthesize-to-file",
+        "--engine",
+        "neural",
+        "--voice",
+        "Joanna",
+        "--text-file",
+        "speech.txt",
+        "--file",
+        "speech.mp3",
+        "--speech-marks",
+        "sentence,word",
+    ]);
+
+    let marks = read_to_string(fixtures.path("work/speech.marks.jsonl")).unwrap();
+    assert!(marks.contains(r#""value":"How are you?""#), "{marks}");
+    let webvtt = read_to_string(fixtures.path("work/speech.vtt")).unwrap();
+    assert!(webvtt.starts_with("WEBVTT\n"), "{webvtt}");
+    assert!(webvtt.contains("Hello there."), "{webvtt}");
+    let srt = read_to_string(fixtures.path("work/speech.srt")).unwrap();
+    assert!(srt.starts_with("1\n00:00:00,000 --> "), "{srt}");
+    assert!(srt.contains("How are you?"), "{srt}");
+}
+
+const VOICES: &str = r#"[
+    {"gender": "Female", "voice_id": "Joanna", "language_code": "en-US", "language_name": "US English", "voice_name": "Joanna", "supported_engines": ["neural"]},
+    {"gender": "Male", "voice_id": "Matthew", "language_code": "en-US", "language_name": "US English", "voice_name": "Matthew", "supported_engines": ["standard"]}
+]"#;
+
+#[test]
+fn every_voice_of_the_engine_is_sampled_on_a_gallery() {
+    let fixtures = Fixtures::new();
+    fixtures.fixture("voices.json", VOICES);
+    fixtures.file("sample.txt", "Tom & Jerry");
+
+    fixtures.succeed(&[
+        "polly",
+        "sample-voices",
+        "--engine",
+        "neural",
+        "--text-file",
+        "sample.txt",
+        "--output-dir",
+        "outputs",
+    ]);
+
+    let run_dir = fixtures.only_run_dir("outputs");
+    assert!(run_dir.join("Joanna.mp3").is_file());
+    assert!(!run_dir.join("Matthew.mp3").exists());
+    let page = read_to_string(run_dir.join("index.html")).unwrap();
+    assert!(
+        page.contains("<blockquote>Tom &amp; Jerry</blockquote>"),
+        "{page}"
+    );
+    assert!(page.contains("src=\"Joanna.mp3\""), "{page}");
+}
+
+#[test]
+fn a_sample_over_the_total_limit_of_a_request_is_rejected() {
+    let fixtures = Fixtures::new();
+    fixtures.fixture("voices.json", VOICES);
+    fixtures.file(
+        "sample.ssml",
+        &format!(
+            "<speak>Hello{}</speak>",
+            r#"<break time="1s"/>"#.repeat(400)
+        ),
+    );
+
+    let stdout = fixtures.fail(&[
+        "polly",
+        "sample-voices",
+        "--engine",
+        "neural",
+        "--text-type",
+        "ssml",
+        "--text-file",
+        "sample.ssml",
+    ]);
+
+    assert!(stdout.contains("6000"), "{stdout}");
+    assert!(!fixtures.path("work/DLearningClientOutputs").exists());
+}
+
+#[test]
+fn long_ssml_is_synthesized_in_documents_of_their_own() {
+    let fixtures = Fixtures::new();
+    fixtures.file(
+        "speech.ssml",
+        &format!("<speak>{}</speak>", "<p>Hello there.</p>".repeat(300)),
+    );
+
+    fixtures.succeed(&[
+        "polly",
+        "synthesize-to-file",
+        "--engine",
+        "neural",
+        "--voice",
+        "Joanna",
+        "--text-type",
+        "ssml",
+        "--text-file",
+        "speech.ssml",
+        "--file",
+        "speech.mp3",
+    ]);
+
+    let audio = read_to_string(fixtures.path("work/speech.mp3")).unwrap();
+    let parts: Vec<_> = audio.lines().collect();
+    assert_eq!(parts.len(), 2, "{audio}");
+    for part in parts {
+        assert!(part.starts_with("[Joanna: <speak><p>"), "{part}");
+        assert!(part.ends_with("</p></speak>]"), "{part}");
+    }
+}
+
+#[test]
+fn invalid_ssml_is_reported_before_it_is_sent() {
+    let fixtures = Fixtures::new();
+    fixtures.file(
+        "speech.ssml",
+        "<speak>Hello <break time=\"3\"/> there</speak>",
+    );
+
+    let stdout = fixtures.fail(&[
+        "polly",
+        "synthesize",
+        "--engine",
+        "neural",
+        "--voice",
+        "Joanna",
+        "--text-file",
+        "speech.ssml",
+        "--bucket",
+        "speech",
+    ]);
+
+    assert!(stdout.contains("The SSML is invalid at line 1"), "{stdout}");
+    assert!(!stdout.contains("[fake]"), "{stdout}");
+}
+
+#[test]
+fn a_lexicon_name_that_is_not_a_file_name_is_rejected() {
+    let fixtures = Fixtures::new();
+
+    let stdout = fixtures.fail(&["polly", "get-lexicon", "--name", "../names"]);
+
+    assert!(
+        stdout.contains("'../names' isn't a lexicon name"),
+        "{stdout}"
+    );
+    assert!(!fixtures.path("work/names.pls").exists());
+}
+
+#[test]
+fn a_lexicon_that_is_not_stored_fails_the_synthesis() {
+    let fixtures = Fixtures::new();
+    fixtures.file("speech.txt", "Hello there.");
+
+    let stdout = fixtures.fail(&[
+        "polly",
+        "synthesize-to-file",
+        "--engine",
+        "neural",
+        "--voice",
+        "Joanna",
+        "--text-file",
+        "speech.txt",
+        "--lexicon",
+        "missing",
+    ]);
+
+    assert!(
+        stdout.contains("The service call failed: The lexicon 'missing' was not found"),
+        "{stdout}"
+    );
+    assert!(!fixtures.path("work/DLearningClientOutputs").exists());
+}
+
+#[test]
+fn transcribe_reports_the_job_status() {
+    let fixtures = Fixtures::new();
+    fixtures.fixture(
+        "transcription_jobs.json",
+        r#"{"interview": {"status": "COMPLETED"}}"#,
+    );
+
+    let stdout = fixtures.succeed(&["transcribe", "status", "--job-name", "interview"]);
+
+    assert!(stdout.contains("The job Status is COMPLETED"), "{stdout}");
+}
+
+#[test]
+fn translate_translates_a_text_file() {
+    let fixtures = Fixtures::new();
+    fixtures.file("hello.txt", "Hello there.");
+
+    let stdout = fixtures.succeed(&[
+        "translate",
+        "text",
+        "--text-file",
+        "hello.txt",
+        "--target",
+        "fr",
+    ]);
+
+    assert!(stdout.contains("[fr] Hello there."), "{stdout}");
+}
+
+#[test]
+fn translate_writes_the_translated_document() {
+    let fixtures = Fixtures::new();
+    fixtures.file("notes.txt", "Hello there.");
+
+    fixtures.succeed(&[
+        "translate",
+        "document",
+        "--document-type",
+        "Plain",
+        "--document",
+        "notes.txt",
+        "--target",
+        "fr",
+        "--output-dir",
+        "outputs",
+    ]);
+
+    let run_dir = fixtures.only_run_dir("outputs");
+    let translated = read_to_string(run_dir.join("notes_fr.txt")).unwrap();
+    assert_eq!(translated, "Hello there.");
+}
+
+#[test]
+fn a_failed_call_is_reported() {
+    let fixtures = Fixtures::new();
+
+    let stdout = fixtures.fail(&[
+        "translate",
+        "document",
+        "--document-type",
+        "Plain",
+        "--document",
+        "missing.txt",
+        "--target",
+        "fr",
+    ]);
+
+    assert!(
+        stdout.contains("Error while accessing 'missing.txt'"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn the_profile_fills_in_the_flags_that_are_not_given() {
+    let fixtures = Fixtures::new();
+    let config = fixtures.path("config/dlearningclient/config.toml");
+    create_dir_all(config.parent().unwrap()).unwrap();
+    write(
+        config,
+        "default_profile = \"work\"\n\n[profiles.work]\ntranslate_target = \"de\"\n",
+    )
+    .unwrap();
+    fixtures.file("hello.txt", "Hello there.");
+
+    let stdout = fixtures.succeed(&["translate", "text", "--text-file", "hello.txt"]);
+
+    assert!(stdout.contains("[de] Hello there."), "{stdout}");
+}
+
+#[test]
+fn a_flag_missing_from_the_profile_too_is_reported() {
+    let fixtures = Fixtures::new();
+    fixtures.file("hello.txt", "Hello there.");
+
+    let stdout = fixtures.fail(&["translate", "text", "--text-file", "hello.txt"]);
+
+    assert!(
+        stdout.contains("Give --target, or set 'translate_target' in the active profile"),
+        "{stdout}"
+    );
+}